@@ -0,0 +1,1060 @@
+//! A compact binary encoding of [`RRule`] and [`RRuleSet`], to cache them without parsing
+//! their iCalendar string again.
+//!
+//! The bytes start with the version of the encoding and the kind of the value, and end with a
+//! CRC-32 checksum of the bytes before it. Integers are written as LEB128 varints, with a
+//! zigzag encoding for signed integers. The `BY` rule parts with small ranges, like `BYHOUR`,
+//! are written as bitmasks. A datetime is written as its timezone and its Unix timestamp, which
+//! is relative to `DTSTART` for the datetimes of a set. A timezone is written as its name, or
+//! with its definition if it is a [`VTimezone`].
+use std::{marker::PhantomData, ops::RangeInclusive, str::FromStr};
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Weekday,
+};
+
+use crate::{
+    core::{DstGap, DstOverlap, DstPolicy, Observance},
+    parser::{parse_timezone, ParseError},
+    validator::validate_rrule::{by_set_pos_range, by_weekday_range, EASTER_RANGE, YEAR_DAY_RANGE},
+    Frequency, NWeekday, Period, PeriodEnd, RRule, RRuleError, RRuleSet, RScale,
+    RecurrenceOverride, RecurrenceRange, Skip, Tz, Unvalidated, VTimezone,
+};
+
+/// The version of the encoding, which is increased whenever the encoding changes.
+const VERSION: u8 = 1;
+
+/// The kinds of values, which are written after the version.
+const KIND_RRULE: u8 = 0;
+const KIND_RRULE_SET: u8 = 1;
+
+/// The timezones of datetimes, which are written before their timestamp.
+const TZ_BASE: u8 = 0;
+const TZ_LOCAL: u8 = 1;
+const TZ_FIXED: u8 = 2;
+const TZ_NAMED: u8 = 3;
+const TZ_DEFINED: u8 = 4;
+
+/// The values of the enums, which are written as their index.
+const FREQUENCIES: [Frequency; 7] = [
+    Frequency::Yearly,
+    Frequency::Monthly,
+    Frequency::Weekly,
+    Frequency::Daily,
+    Frequency::Hourly,
+    Frequency::Minutely,
+    Frequency::Secondly,
+];
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+const RSCALES: [RScale; 3] = [RScale::Gregorian, RScale::Hebrew, RScale::Chinese];
+const SKIPS: [Skip; 3] = [Skip::Omit, Skip::Backward, Skip::Forward];
+const DST_GAPS: [DstGap; 2] = [DstGap::ShiftForward, DstGap::Skip];
+const DST_OVERLAPS: [DstOverlap; 4] = [
+    DstOverlap::Earliest,
+    DstOverlap::Latest,
+    DstOverlap::Both,
+    DstOverlap::Skip,
+];
+const RECURRENCE_RANGES: [RecurrenceRange; 2] = [
+    RecurrenceRange::ThisInstance,
+    RecurrenceRange::ThisAndFuture,
+];
+
+/// The table of the CRC-32 checksum, with the polynomial of zlib and PNG.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, byte| {
+        CRC_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+impl RRule {
+    /// Encodes the rule to its compact binary form, see [`RRule::from_binary`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{RRule, Tz};
+    /// use chrono::TimeZone;
+    ///
+    /// let dt_start = Tz::UTC.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap();
+    /// let rrule = "FREQ=WEEKLY;BYDAY=MO,FR"
+    ///     .parse::<RRule<_>>()
+    ///     .unwrap()
+    ///     .validate(dt_start)
+    ///     .unwrap();
+    /// let bytes = rrule.to_binary();
+    /// assert!(bytes.len() < rrule.to_string().len());
+    /// assert_eq!(RRule::from_binary(&bytes).unwrap(), rrule);
+    /// ```
+    #[must_use]
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new(KIND_RRULE);
+        encoder.rrule(self, None);
+        encoder.finish()
+    }
+
+    /// Decodes a rule from the bytes written by [`RRule::to_binary`].
+    ///
+    /// The rule was validated before it was encoded, and the checksum shows that the bytes
+    /// weren't changed since, so the rule isn't validated again. The checksum detects
+    /// corrupted bytes, but it doesn't protect against bytes which were crafted on purpose, so
+    /// the values of the rule parts are still checked to be in their ranges.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the checksum doesn't match, if the bytes are of another
+    /// version of the encoding, or if a timezone can't be found.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, RRuleError> {
+        let mut decoder = Decoder::new(bytes, KIND_RRULE)?;
+        let rrule = decoder.rrule(None)?;
+        decoder.finish()?;
+        Ok(rrule)
+    }
+}
+
+impl RRuleSet {
+    /// Encodes the set to its compact binary form, see [`RRuleSet::from_binary`]. The options
+    /// of the set, like [`RRuleSet::before`] and [`RRuleSet::dst_overlap`], and the overrides
+    /// of occurrences are encoded as well.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+    ///     RRULE:FREQ=WEEKLY;COUNT=10;BYDAY=MO,FR\n\
+    ///     EXDATE;TZID=Europe/Berlin:20250110T090000"
+    ///     .parse()
+    ///     .unwrap();
+    /// let bytes = rrule_set.to_binary();
+    /// assert!(bytes.len() < rrule_set.to_string().len() / 2);
+    /// assert_eq!(RRuleSet::from_binary(&bytes).unwrap(), rrule_set);
+    /// ```
+    #[must_use]
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new(KIND_RRULE_SET);
+        encoder.rrule_set(self);
+        encoder.finish()
+    }
+
+    /// Decodes a set from the bytes written by [`RRuleSet::to_binary`].
+    ///
+    /// Like [`RRule::from_binary`], the rules of the set aren't validated again when the
+    /// checksum matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the checksum doesn't match, if the bytes are of another
+    /// version of the encoding, or if a timezone can't be found.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, RRuleError> {
+        let mut decoder = Decoder::new(bytes, KIND_RRULE_SET)?;
+        let rrule_set = decoder.rrule_set()?;
+        decoder.finish()?;
+        Ok(rrule_set)
+    }
+}
+
+/// Returns the index of an enum value in `values`, which is how it is written.
+fn index<T: PartialEq>(value: T, values: &[T]) -> u8 {
+    let index = values
+        .iter()
+        .position(|candidate| *candidate == value)
+        .expect("all values of the enum are listed");
+    u8::try_from(index).expect("enums have less than 256 values")
+}
+
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn new(kind: u8) -> Self {
+        Self {
+            bytes: vec![VERSION, kind],
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let checksum = crc32(&self.bytes);
+        self.bytes.extend(checksum.to_le_bytes());
+        self.bytes
+    }
+
+    fn byte(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value & 0x7f) as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn signed(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.varint(len as u64);
+    }
+
+    fn string(&mut self, string: &str) {
+        self.len(string.len());
+        self.bytes.extend(string.as_bytes());
+    }
+
+    /// Writes values in `0..64` as the bits of a mask.
+    fn bitmask(&mut self, values: impl IntoIterator<Item = u8>) {
+        let mask = values
+            .into_iter()
+            .fold(0_u64, |mask, value| mask | 1 << value);
+        self.varint(mask);
+    }
+
+    fn timezone(&mut self, tz: Tz, base: Option<Tz>) {
+        match tz {
+            _ if base == Some(tz) => self.byte(TZ_BASE),
+            Tz::Local(_) => self.byte(TZ_LOCAL),
+            Tz::Fixed(offset) => {
+                self.byte(TZ_FIXED);
+                self.offset(offset);
+            }
+            #[cfg(feature = "chrono-tz")]
+            Tz::Tz(_) => {
+                self.byte(TZ_NAMED);
+                self.string(tz.name());
+            }
+            Tz::VTimezone(vtimezone) => {
+                self.byte(TZ_DEFINED);
+                self.vtimezone(vtimezone);
+            }
+        }
+    }
+
+    /// Writes the `TZID` and the observances of the timezone, or its changes of offset if it
+    /// was created from them, as it may not be found by its name when it is decoded.
+    fn vtimezone(&mut self, vtimezone: &VTimezone) {
+        self.string(vtimezone.get_tzid());
+        let observances = vtimezone.get_observances();
+        self.len(observances.len());
+        for observance in observances {
+            self.naive_datetime(observance.dt_start);
+            self.offset(observance.offset_from);
+            self.offset(observance.offset_to);
+            self.byte(u8::from(observance.rrule.is_some()));
+            if let Some(rrule) = &observance.rrule {
+                self.string(&rrule.to_string());
+            }
+            self.len(observance.rdates.len());
+            for rdate in &observance.rdates {
+                self.naive_datetime(*rdate);
+            }
+        }
+        if observances.is_empty() {
+            let (initial, changes) = vtimezone.get_changes();
+            self.offset(initial);
+            self.len(changes.len());
+            for (at, offset) in changes {
+                self.naive_datetime(at);
+                self.offset(offset);
+            }
+        }
+    }
+
+    fn offset(&mut self, offset: FixedOffset) {
+        self.signed(offset.local_minus_utc().into());
+    }
+
+    fn naive_datetime(&mut self, dt: NaiveDateTime) {
+        let dt = dt.and_utc();
+        self.signed(dt.timestamp());
+        self.varint(dt.timestamp_subsec_nanos().into());
+    }
+
+    fn datetime(&mut self, dt: &DateTime<Tz>, base: Option<&DateTime<Tz>>) {
+        self.timezone(dt.timezone(), base.map(DateTime::timezone));
+        self.signed(dt.timestamp() - base.map_or(0, DateTime::timestamp));
+        self.varint(dt.timestamp_subsec_nanos().into());
+    }
+
+    fn date(&mut self, date: NaiveDate) {
+        self.signed(date.num_days_from_ce().into());
+    }
+
+    fn duration(&mut self, duration: Duration) {
+        self.signed(duration.num_seconds());
+        self.signed(duration.subsec_nanos().into());
+    }
+
+    fn rrule(&mut self, rrule: &RRule, base: Option<&DateTime<Tz>>) {
+        let flags = [
+            rrule.count.is_some(),
+            rrule.until.is_some(),
+            rrule.by_easter.is_some(),
+            rrule.include_dtstart.is_some(),
+            rrule.include_dtstart == Some(true),
+            rrule.local_tzid.is_some(),
+            rrule.dtstart_is_floating,
+            rrule.rscale.is_some(),
+        ];
+        self.byte(index(rrule.freq, &FREQUENCIES) | index(rrule.week_start, &WEEKDAYS) << 3);
+        self.byte(flags_byte(flags));
+        self.byte(
+            index(rrule.skip, &SKIPS) | index(rrule.rscale.unwrap_or_default(), &RSCALES) << 2,
+        );
+        self.varint(rrule.interval.into());
+        if let Some(count) = rrule.count {
+            self.varint(count.into());
+        }
+        if let Some(until) = &rrule.until {
+            self.datetime(until, base);
+        }
+        if let Some(by_easter) = rrule.by_easter {
+            self.signed(by_easter.into());
+        }
+        if let Some(local_tzid) = rrule.local_tzid {
+            self.timezone(local_tzid, None);
+        }
+
+        self.len(rrule.by_set_pos.len());
+        for by_set_pos in &rrule.by_set_pos {
+            self.signed((*by_set_pos).into());
+        }
+        self.bitmask(rrule.by_month.iter().copied());
+        self.bitmask(rrule.by_leap_month.iter().copied());
+        self.bitmask(rrule.by_month_day.iter().map(|day| day.unsigned_abs()));
+        self.bitmask(rrule.by_n_month_day.iter().map(|day| day.unsigned_abs()));
+        self.len(rrule.by_year_day.len());
+        for by_year_day in &rrule.by_year_day {
+            self.signed((*by_year_day).into());
+        }
+        // The positive and negative week numbers as separate bitmasks
+        let by_week_no = |negative: bool| {
+            rrule
+                .by_week_no
+                .iter()
+                .filter(move |week| week.is_negative() == negative)
+                .map(|week| week.unsigned_abs())
+        };
+        self.bitmask(by_week_no(false));
+        self.bitmask(by_week_no(true));
+
+        // Every weekday as a bitmask, and the nth weekdays as their `n` and weekday
+        let mut by_nth_weekday = vec![];
+        let mut by_every_weekday = vec![];
+        for by_weekday in &rrule.by_weekday {
+            match *by_weekday {
+                NWeekday::Every(weekday) => by_every_weekday.push(index(weekday, &WEEKDAYS)),
+                NWeekday::Nth(n, weekday) => by_nth_weekday.push((n, weekday)),
+            }
+        }
+        self.bitmask(by_every_weekday);
+        self.len(by_nth_weekday.len());
+        for (n, weekday) in by_nth_weekday {
+            self.signed(i64::from(n) * 7 + i64::from(index(weekday, &WEEKDAYS)));
+        }
+
+        self.bitmask(rrule.by_hour.iter().copied());
+        self.bitmask(rrule.by_minute.iter().copied());
+        self.bitmask(rrule.by_second.iter().copied());
+    }
+
+    fn rrule_set(&mut self, rrule_set: &RRuleSet) {
+        let dt_start = &rrule_set.dt_start;
+        self.datetime(dt_start, None);
+        let flags = [
+            rrule_set.dt_end.is_some(),
+            rrule_set.duration.is_some(),
+            rrule_set.before.is_some(),
+            rrule_set.after.is_some(),
+            rrule_set.limited,
+            false,
+            false,
+            false,
+        ];
+        self.byte(flags_byte(flags));
+        self.byte(
+            index(rrule_set.dst.gap, &DST_GAPS) | index(rrule_set.dst.overlap, &DST_OVERLAPS) << 1,
+        );
+        for dt in [&rrule_set.dt_end, &rrule_set.before, &rrule_set.after]
+            .into_iter()
+            .flatten()
+        {
+            self.datetime(dt, Some(dt_start));
+        }
+        if let Some(duration) = rrule_set.duration {
+            self.duration(duration);
+        }
+
+        for rrules in [&rrule_set.rrule, &rrule_set.exrule] {
+            self.len(rrules.len());
+            for rrule in rrules {
+                self.rrule(rrule, Some(dt_start));
+            }
+        }
+        for dates in [&rrule_set.rdate, &rrule_set.exdate] {
+            self.len(dates.len());
+            for dt in dates {
+                self.datetime(dt, Some(dt_start));
+            }
+        }
+        for dates in [&rrule_set.rdate_date, &rrule_set.exdate_date] {
+            self.len(dates.len());
+            for date in dates {
+                self.date(*date);
+            }
+        }
+
+        self.len(rrule_set.rdate_period.len());
+        for period in &rrule_set.rdate_period {
+            self.datetime(&period.get_start(), Some(dt_start));
+            match period.get_period_end() {
+                PeriodEnd::DateTime(end) => {
+                    self.byte(0);
+                    self.datetime(end, Some(dt_start));
+                }
+                PeriodEnd::Duration(duration) => {
+                    self.byte(1);
+                    self.duration(*duration);
+                }
+            }
+        }
+
+        self.len(rrule_set.overrides.len());
+        for (recurrence_id, recurrence_override) in &rrule_set.overrides {
+            self.datetime(recurrence_id, Some(dt_start));
+            let start = recurrence_override.get_start();
+            let duration = recurrence_override.get_duration();
+            self.byte(
+                u8::from(start.is_some())
+                    | u8::from(duration.is_some()) << 1
+                    | index(recurrence_override.get_range(), &RECURRENCE_RANGES) << 2,
+            );
+            if let Some(start) = start {
+                self.datetime(start, Some(dt_start));
+            }
+            if let Some(duration) = duration {
+                self.duration(*duration);
+            }
+        }
+    }
+}
+
+fn flags_byte(flags: [bool; 8]) -> u8 {
+    flags
+        .iter()
+        .enumerate()
+        .fold(0, |byte, (bit, flag)| byte | u8::from(*flag) << bit)
+}
+
+fn invalid(reason: &str) -> ParseError {
+    ParseError::InvalidBinary(reason.into())
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn new(bytes: &'a [u8], kind: u8) -> Result<Self, ParseError> {
+        let checksum_idx = bytes
+            .len()
+            .checked_sub(4)
+            .ok_or_else(|| invalid("the bytes are too short"))?;
+        let (bytes, checksum) = bytes.split_at(checksum_idx);
+        let mut checksum_bytes = [0; 4];
+        checksum_bytes.copy_from_slice(checksum);
+        if crc32(bytes) != u32::from_le_bytes(checksum_bytes) {
+            return Err(invalid("the checksum doesn't match"));
+        }
+
+        let mut decoder = Self { bytes };
+        let version = decoder.byte()?;
+        if version != VERSION {
+            return Err(ParseError::InvalidBinary(format!(
+                "version {version} isn't supported, only version {VERSION} is"
+            )));
+        }
+        if decoder.byte()? != kind {
+            return Err(invalid(match kind {
+                KIND_RRULE => "the bytes don't encode an `RRule`",
+                _ => "the bytes don't encode an `RRuleSet`",
+            }));
+        }
+        Ok(decoder)
+    }
+
+    fn finish(self) -> Result<(), ParseError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid("found bytes after the end of the value"))
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, ParseError> {
+        let (byte, bytes) = self
+            .bytes
+            .split_first()
+            .ok_or_else(|| invalid("the bytes end in the middle of a value"))?;
+        self.bytes = bytes;
+        Ok(*byte)
+    }
+
+    fn varint(&mut self) -> Result<u64, ParseError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("a varint is longer than 64 bits"))
+    }
+
+    fn signed(&mut self) -> Result<i64, ParseError> {
+        let zigzag = self.varint()?;
+        Ok((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64))
+    }
+
+    /// Reads a signed integer which has to fit in `T`.
+    fn int<T: TryFrom<i64>>(&mut self, value: &str) -> Result<T, ParseError> {
+        let int = self.signed()?;
+        T::try_from(int).map_err(|_| out_of_range(value))
+    }
+
+    /// Reads an unsigned integer which has to fit in `T`.
+    fn uint<T: TryFrom<u64>>(&mut self, value: &str) -> Result<T, ParseError> {
+        let uint = self.varint()?;
+        T::try_from(uint).map_err(|_| out_of_range(value))
+    }
+
+    /// Reads the length of a list, which can't be longer than the remaining bytes.
+    fn len(&mut self) -> Result<usize, ParseError> {
+        let len = self.varint()?;
+        match usize::try_from(len) {
+            Ok(len) if len <= self.bytes.len() => Ok(len),
+            _ => Err(invalid("a list is longer than the bytes")),
+        }
+    }
+
+    fn string(&mut self, value: &str) -> Result<&'a str, ParseError> {
+        let len = self.len()?;
+        let (string, bytes) = self.bytes.split_at(len);
+        self.bytes = bytes;
+        std::str::from_utf8(string)
+            .map_err(|_| ParseError::InvalidBinary(format!("{value} isn't valid UTF-8")))
+    }
+
+    /// Reads a list of integers in `range`, which can't be zero.
+    fn nonzero_ints<T>(
+        &mut self,
+        value: &str,
+        range: &RangeInclusive<T>,
+    ) -> Result<Vec<T>, ParseError>
+    where
+        T: TryFrom<i64> + PartialOrd + Default,
+    {
+        (0..self.len()?)
+            .map(|_| {
+                let int = self.int(value)?;
+                if int == T::default() || !range.contains(&int) {
+                    return Err(out_of_range(value));
+                }
+                Ok(int)
+            })
+            .collect()
+    }
+
+    /// Reads a bitmask of values in `range`, see [`Encoder::bitmask`].
+    fn bitmask(&mut self, value: &str, range: RangeInclusive<u8>) -> Result<Vec<u8>, ParseError> {
+        let mask = self.varint()?;
+        let values = (0..64_u8)
+            .filter(|bit| mask & 1 << bit != 0)
+            .collect::<Vec<_>>();
+        if values.iter().any(|value| !range.contains(value)) {
+            return Err(out_of_range(value));
+        }
+        Ok(values)
+    }
+
+    fn timezone(&mut self, base: Option<Tz>) -> Result<Tz, ParseError> {
+        match (self.byte()?, base) {
+            (TZ_BASE, Some(base)) => Ok(base),
+            (TZ_LOCAL, _) => Ok(Tz::LOCAL),
+            (TZ_FIXED, _) => self.offset().map(Tz::Fixed),
+            (TZ_NAMED, _) => parse_timezone(self.string("the name of a timezone")?),
+            (TZ_DEFINED, _) => self.vtimezone().and_then(Tz::try_from),
+            _ => Err(out_of_range("the kind of a timezone")),
+        }
+    }
+
+    /// Reads a timezone, see [`Encoder::vtimezone`]. The onsets of its observances are
+    /// calculated again.
+    fn vtimezone(&mut self) -> Result<VTimezone, ParseError> {
+        let invalid_definition = |_| invalid("the definition of a timezone isn't valid");
+        let tzid = self.string("the `TZID` of a timezone")?.to_string();
+        let mut observances = vec![];
+        for _ in 0..self.len()? {
+            let dt_start = self.naive_datetime()?;
+            let offset_from = self.offset()?;
+            let offset_to = self.offset()?;
+            let rrule = match self.byte()? {
+                0 => None,
+                1 => Some(
+                    RRule::<Unvalidated>::from_str(self.string("the `RRULE` of a timezone")?)
+                        .map_err(invalid_definition)?,
+                ),
+                _ => return Err(out_of_range("the `RRULE` of a timezone")),
+            };
+            let rdates = (0..self.len()?)
+                .map(|_| self.naive_datetime())
+                .collect::<Result<_, _>>()?;
+            observances.push(Observance {
+                dt_start,
+                offset_from,
+                offset_to,
+                rrule,
+                rdates,
+            });
+        }
+        if !observances.is_empty() {
+            return VTimezone::new(tzid, observances).map_err(invalid_definition);
+        }
+
+        let initial = self.offset()?;
+        let changes = (0..self.len()?)
+            .map(|_| Ok((self.naive_datetime()?, self.offset()?)))
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        if changes.windows(2).any(|pair| pair[0].0 > pair[1].0) {
+            return Err(invalid("the changes of offset of a timezone aren't sorted"));
+        }
+        Ok(VTimezone::from_transitions(tzid, initial, &changes))
+    }
+
+    fn offset(&mut self) -> Result<FixedOffset, ParseError> {
+        let seconds = self.int("the offset of a timezone")?;
+        FixedOffset::east_opt(seconds).ok_or_else(|| out_of_range("the offset of a timezone"))
+    }
+
+    fn naive_datetime(&mut self) -> Result<NaiveDateTime, ParseError> {
+        let timestamp = self.signed()?;
+        let nanos = self.uint("a timestamp")?;
+        DateTime::from_timestamp(timestamp, nanos)
+            .map(|dt| dt.naive_utc())
+            .ok_or_else(|| out_of_range("a timestamp"))
+    }
+
+    fn datetime(&mut self, base: Option<&DateTime<Tz>>) -> Result<DateTime<Tz>, ParseError> {
+        let tz = self.timezone(base.map(DateTime::timezone))?;
+        let timestamp = self
+            .signed()?
+            .checked_add(base.map_or(0, DateTime::timestamp))
+            .ok_or_else(|| out_of_range("a timestamp"))?;
+        let nanos = self.uint("a timestamp")?;
+        tz.timestamp_opt(timestamp, nanos)
+            .single()
+            .ok_or_else(|| out_of_range("a timestamp"))
+    }
+
+    fn date(&mut self) -> Result<NaiveDate, ParseError> {
+        let days = self.int("a date")?;
+        NaiveDate::from_num_days_from_ce_opt(days).ok_or_else(|| out_of_range("a date"))
+    }
+
+    fn duration(&mut self) -> Result<Duration, ParseError> {
+        let seconds = self.signed()?;
+        let nanos = self.int::<i32>("a duration")?;
+        Duration::try_seconds(seconds)
+            .and_then(|duration| duration.checked_add(&Duration::nanoseconds(nanos.into())))
+            .ok_or_else(|| out_of_range("a duration"))
+    }
+
+    fn rrule(&mut self, base: Option<&DateTime<Tz>>) -> Result<RRule, ParseError> {
+        let freq_and_week_start = self.byte()?;
+        let flags = self.byte()?;
+        let flag = |bit: u8| flags & 1 << bit != 0;
+        let skip_and_rscale = self.byte()?;
+        let freq = variant(freq_and_week_start & 0b111, &FREQUENCIES, "FREQ")?;
+        let week_start = variant(freq_and_week_start >> 3, &WEEKDAYS, "WKST")?;
+        let skip = variant(skip_and_rscale & 0b11, &SKIPS, "SKIP")?;
+        let rscale = variant(skip_and_rscale >> 2, &RSCALES, "RSCALE")?;
+
+        let interval = self.uint("INTERVAL")?;
+        if interval == 0 {
+            return Err(out_of_range("INTERVAL"));
+        }
+        let count = flag(0).then(|| self.uint("COUNT")).transpose()?;
+        let until = flag(1).then(|| self.datetime(base)).transpose()?;
+        let by_easter = flag(2).then(|| self.int("BYEASTER")).transpose()?;
+        if by_easter.is_some_and(|by_easter| !EASTER_RANGE.contains(&by_easter)) {
+            return Err(out_of_range("BYEASTER"));
+        }
+        let include_dtstart = flag(3).then_some(flag(4));
+        let local_tzid = flag(5).then(|| self.timezone(None)).transpose()?;
+
+        let by_set_pos = self.nonzero_ints("BYSETPOS", &by_set_pos_range(freq))?;
+        let by_month = self.bitmask("BYMONTH", 1..=12)?;
+        let by_leap_month = self.bitmask("BYMONTH", 1..=12)?;
+        let by_month_day = self.bitmask("BYMONTHDAY", 1..=31)?;
+        let by_n_month_day = self.bitmask("BYMONTHDAY", 1..=31)?;
+        let by_year_day = self.nonzero_ints("BYYEARDAY", &YEAR_DAY_RANGE)?;
+        let by_week_no = self.bitmask("BYWEEKNO", 1..=53)?;
+        let by_n_week_no = self.bitmask("BYWEEKNO", 1..=53)?;
+
+        let mut by_weekday = vec![];
+        for weekday in self.bitmask("BYDAY", 0..=6)? {
+            by_weekday.push(NWeekday::Every(variant(weekday, &WEEKDAYS, "BYDAY")?));
+        }
+        for _ in 0..self.len()? {
+            let n_and_weekday = self.signed()?;
+            let weekday =
+                u8::try_from(n_and_weekday.rem_euclid(7)).map_err(|_| out_of_range("BYDAY"))?;
+            let n = i16::try_from(n_and_weekday.div_euclid(7))
+                .ok()
+                .filter(|n| *n != 0 && by_weekday_range(freq).contains(n))
+                .ok_or_else(|| out_of_range("BYDAY"))?;
+            by_weekday.push(NWeekday::Nth(n, variant(weekday, &WEEKDAYS, "BYDAY")?));
+        }
+        // A validated rule keeps its weekdays sorted
+        by_weekday.sort_unstable();
+        let by_hour = self.bitmask("BYHOUR", 0..=23)?;
+        let by_minute = self.bitmask("BYMINUTE", 0..=59)?;
+        let by_second = self.bitmask("BYSECOND", 0..=59)?;
+
+        Ok(RRule {
+            freq,
+            interval,
+            count,
+            until,
+            week_start,
+            by_set_pos,
+            by_month,
+            by_leap_month,
+            by_month_day: by_month_day.into_iter().map(to_signed).collect(),
+            by_n_month_day: by_n_month_day.into_iter().rev().map(to_negative).collect(),
+            by_year_day,
+            by_week_no: by_n_week_no
+                .into_iter()
+                .rev()
+                .map(to_negative)
+                .chain(by_week_no.into_iter().map(to_signed))
+                .collect(),
+            by_weekday,
+            by_hour,
+            by_minute,
+            by_second,
+            by_easter,
+            include_dtstart,
+            local_tzid,
+            dtstart_is_floating: flag(6),
+            rscale: flag(7).then_some(rscale),
+            skip,
+            stage: PhantomData,
+        })
+    }
+
+    fn rrule_set(&mut self) -> Result<RRuleSet, ParseError> {
+        let dt_start = self.datetime(None)?;
+        let flags = self.byte()?;
+        let flag = |bit: u8| flags & 1 << bit != 0;
+        let dst = self.byte()?;
+        let dst = DstPolicy {
+            gap: variant(dst & 1, &DST_GAPS, "the DST gap")?,
+            overlap: variant(dst >> 1, &DST_OVERLAPS, "the DST overlap")?,
+        };
+
+        let mut rrule_set = RRuleSet::new(dt_start);
+        rrule_set.dt_end = flag(0)
+            .then(|| self.datetime(Some(&dt_start)))
+            .transpose()?;
+        rrule_set.before = flag(2)
+            .then(|| self.datetime(Some(&dt_start)))
+            .transpose()?;
+        rrule_set.after = flag(3)
+            .then(|| self.datetime(Some(&dt_start)))
+            .transpose()?;
+        rrule_set.duration = flag(1).then(|| self.duration()).transpose()?;
+        rrule_set.limited = flag(4);
+        rrule_set.dst = dst;
+
+        rrule_set.rrule = (0..self.len()?)
+            .map(|_| self.rrule(Some(&dt_start)))
+            .collect::<Result<_, _>>()?;
+        rrule_set.exrule = (0..self.len()?)
+            .map(|_| self.rrule(Some(&dt_start)))
+            .collect::<Result<_, _>>()?;
+        rrule_set.rdate = (0..self.len()?)
+            .map(|_| self.datetime(Some(&dt_start)))
+            .collect::<Result<_, _>>()?;
+        rrule_set.exdate = (0..self.len()?)
+            .map(|_| self.datetime(Some(&dt_start)))
+            .collect::<Result<_, _>>()?;
+        rrule_set.rdate_date = (0..self.len()?)
+            .map(|_| self.date())
+            .collect::<Result<_, _>>()?;
+        rrule_set.exdate_date = (0..self.len()?)
+            .map(|_| self.date())
+            .collect::<Result<_, _>>()?;
+
+        for _ in 0..self.len()? {
+            let start = self.datetime(Some(&dt_start))?;
+            let period = match self.byte()? {
+                0 => Period::new(start, self.datetime(Some(&dt_start))?),
+                1 => Period::with_duration(start, self.duration()?),
+                _ => return Err(out_of_range("the end of a period")),
+            };
+            rrule_set.rdate_period.push(period);
+        }
+
+        for _ in 0..self.len()? {
+            let recurrence_id = self.datetime(Some(&dt_start))?;
+            let flags = self.byte()?;
+            let range = variant(flags >> 2, &RECURRENCE_RANGES, "RANGE")?;
+            let mut recurrence_override = if flags & 1 == 1 {
+                RecurrenceOverride::moved(self.datetime(Some(&dt_start))?)
+            } else {
+                RecurrenceOverride::cancelled()
+            };
+            if flags & 1 << 1 != 0 {
+                recurrence_override = recurrence_override.duration(self.duration()?);
+            }
+            rrule_set
+                .overrides
+                .insert(recurrence_id, recurrence_override.range(range));
+        }
+        Ok(rrule_set)
+    }
+}
+
+/// Returns the value of an enum at `index`, see [`index`].
+fn variant<T: Copy>(index: u8, values: &[T], value: &str) -> Result<T, ParseError> {
+    values
+        .get(usize::from(index))
+        .copied()
+        .ok_or_else(|| out_of_range(value))
+}
+
+fn out_of_range(value: &str) -> ParseError {
+    ParseError::InvalidBinary(format!("{value} is out of range"))
+}
+
+fn to_signed(value: u8) -> i8 {
+    i8::try_from(value).expect("the bitmask values are checked to be in range")
+}
+
+fn to_negative(value: u8) -> i8 {
+    -to_signed(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replaces the checksum of `bytes` after they were changed.
+    fn with_checksum(mut bytes: Vec<u8>) -> Vec<u8> {
+        bytes.truncate(bytes.len() - 4);
+        let checksum = crc32(&bytes);
+        bytes.extend(checksum.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn varints() {
+        for value in [
+            0,
+            1,
+            -1,
+            63,
+            -64,
+            64,
+            i64::from(i32::MAX),
+            i64::MIN,
+            i64::MAX,
+        ] {
+            let mut encoder = Encoder::new(KIND_RRULE);
+            encoder.signed(value);
+            let bytes = encoder.finish();
+            let mut decoder = Decoder::new(&bytes, KIND_RRULE).unwrap();
+            assert_eq!(decoder.signed().unwrap(), value);
+            assert!(decoder.finish().is_ok());
+        }
+    }
+
+    #[test]
+    fn invalid_bytes_with_checksum() {
+        let rrule_set: RRuleSet = "DTSTART:20250101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+            .parse()
+            .unwrap();
+        let bytes = rrule_set.to_binary();
+
+        let mut version = bytes.clone();
+        version[0] = VERSION + 1;
+        let mut trailing = bytes.clone();
+        trailing.insert(bytes.len() - 4, 0);
+        let mut truncated = bytes.clone();
+        truncated.remove(bytes.len() - 5);
+        let tests = [
+            (version, "version 2 isn't supported, only version 1 is"),
+            (trailing, "found bytes after the end of the value"),
+            (truncated, "the bytes end in the middle of a value"),
+        ];
+        for (bytes, reason) in tests {
+            assert_eq!(
+                RRuleSet::from_binary(&with_checksum(bytes)),
+                Err(RRuleError::ParserError(ParseError::InvalidBinary(
+                    reason.into()
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_rules_outside_of_validated_ranges() {
+        let dt_start = Tz::UTC.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap();
+        let rrule = "FREQ=MONTHLY;BYMONTHDAY=1"
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .validate(dt_start)
+            .unwrap();
+        let tests = [
+            (
+                RRule {
+                    interval: 0,
+                    ..rrule.clone()
+                },
+                "INTERVAL",
+            ),
+            (
+                RRule {
+                    by_set_pos: vec![1, 0],
+                    ..rrule.clone()
+                },
+                "BYSETPOS",
+            ),
+            (
+                RRule {
+                    by_set_pos: vec![32],
+                    ..rrule.clone()
+                },
+                "BYSETPOS",
+            ),
+            (
+                RRule {
+                    by_year_day: vec![0],
+                    ..rrule.clone()
+                },
+                "BYYEARDAY",
+            ),
+            (
+                RRule {
+                    by_year_day: vec![-367],
+                    ..rrule.clone()
+                },
+                "BYYEARDAY",
+            ),
+            (
+                RRule {
+                    by_easter: Some(400),
+                    ..rrule.clone()
+                },
+                "BYEASTER",
+            ),
+            (
+                RRule {
+                    by_weekday: vec![NWeekday::Nth(0, Weekday::Mon)],
+                    ..rrule.clone()
+                },
+                "BYDAY",
+            ),
+            (
+                RRule {
+                    by_weekday: vec![NWeekday::Nth(6, Weekday::Mon)],
+                    ..rrule
+                },
+                "BYDAY",
+            ),
+        ];
+        for (rrule, value) in tests {
+            assert_eq!(
+                RRule::from_binary(&rrule.to_binary()),
+                Err(RRuleError::ParserError(ParseError::InvalidBinary(format!(
+                    "{value} is out of range"
+                )))),
+                "{rrule}"
+            );
+        }
+    }
+
+    #[test]
+    fn timezone_from_changes_of_offset() {
+        let offset = |hours| FixedOffset::east_opt(hours * 3600).unwrap();
+        let at = |year| {
+            NaiveDate::from_ymd_opt(year, 3, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let tests = [
+            VTimezone::from_transitions(
+                "Test/Changes".into(),
+                offset(1),
+                &[(at(2000), offset(2)), (at(2010), offset(3))],
+            ),
+            VTimezone::from_transitions("Test/Fixed".into(), offset(5), &[]),
+        ];
+        for vtimezone in tests {
+            let dt_start = Tz::try_from(vtimezone)
+                .unwrap()
+                .with_ymd_and_hms(2005, 1, 1, 9, 0, 0)
+                .unwrap();
+            let rrule = "FREQ=YEARLY;COUNT=10"
+                .parse::<RRule<Unvalidated>>()
+                .unwrap()
+                .validate(dt_start)
+                .unwrap();
+            let rrule_set = RRuleSet::new(dt_start).rrule(rrule);
+            let decoded = RRuleSet::from_binary(&rrule_set.to_binary()).unwrap();
+            assert_eq!(decoded, rrule_set);
+            assert_eq!(decoded.all(20).dates, rrule_set.all(20).dates);
+        }
+    }
+}
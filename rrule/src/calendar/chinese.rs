@@ -0,0 +1,318 @@
+//! The Chinese calendar, calculated from the positions of the sun and the moon as seen
+//! from Beijing (UTC+8), with the rules in use since 1645.
+//!
+//! The astronomical formulas are the lower accuracy ones from "Astronomical Algorithms"
+//! by Jean Meeus, which place the new moons and solar terms within a minute or so.
+//! That is enough to find the right days, except when one of them falls within
+//! minutes of midnight.
+
+use super::{date_from_day, CalendarMonth};
+use chrono::Datelike;
+
+/// The julian day at which day 0 starts.
+const JULIAN_DAY_0: f64 = 1_721_424.5;
+/// The offset of Beijing time from UTC, in days.
+const BEIJING_OFFSET: f64 = 8.0 / 24.0;
+const J2000: f64 = 2_451_545.0;
+const SYNODIC_MONTH: f64 = 29.530_588_861;
+const TROPICAL_YEAR: f64 = 365.242_189;
+
+fn sin_deg(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+/// The difference between terrestrial time and universal time in days, using the
+/// polynomials of Espenak and Meeus.
+#[allow(clippy::cast_precision_loss)]
+fn delta_t(julian_day: f64) -> f64 {
+    let year = 2000.0 + (julian_day - J2000) / TROPICAL_YEAR;
+    let long_term = |year: f64| -20.0 + 32.0 * ((year - 1820.0) / 100.0).powi(2);
+    let seconds = match year {
+        y if y < 1900.0 => long_term(y),
+        y if y < 1920.0 => {
+            let t = y - 1900.0;
+            -2.79 + 1.494_119 * t - 0.059_893_9 * t.powi(2) + 0.006_196_6 * t.powi(3)
+                - 0.000_197 * t.powi(4)
+        }
+        y if y < 1941.0 => {
+            let t = y - 1920.0;
+            21.20 + 0.844_93 * t - 0.076_100 * t.powi(2) + 0.002_093_6 * t.powi(3)
+        }
+        y if y < 1961.0 => {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        }
+        y if y < 1986.0 => {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        }
+        y if y < 2005.0 => {
+            let t = y - 2000.0;
+            63.86 + 0.3345 * t - 0.060_374 * t.powi(2)
+                + 0.001_727_5 * t.powi(3)
+                + 0.000_651_814 * t.powi(4)
+                + 0.000_023_735_99 * t.powi(5)
+        }
+        y if y < 2050.0 => {
+            let t = y - 2000.0;
+            62.92 + 0.322_17 * t + 0.005_589 * t.powi(2)
+        }
+        y if y < 2150.0 => long_term(y) - 0.5628 * (2150.0 - y),
+        y => long_term(y),
+    };
+    seconds / 86_400.0
+}
+
+/// The apparent longitude of the sun in degrees at the julian day `julian_day` (UT).
+fn solar_longitude(julian_day: f64) -> f64 {
+    let t = (julian_day + delta_t(julian_day) - J2000) / 36_525.0;
+    let mean_longitude = 280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t.powi(2);
+    let mean_anomaly = 357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t.powi(2);
+    let center = (1.914_602 - 0.004_817 * t - 0.000_014 * t.powi(2)) * sin_deg(mean_anomaly)
+        + (0.019_993 - 0.000_101 * t) * sin_deg(2.0 * mean_anomaly)
+        + 0.000_289 * sin_deg(3.0 * mean_anomaly);
+    let omega = 125.04 - 1934.136 * t;
+    (mean_longitude + center - 0.005_69 - 0.004_78 * sin_deg(omega)).rem_euclid(360.0)
+}
+
+/// The julian day (UT) of the new moon with number `k`, counted from the one of
+/// 6 January 2000.
+#[allow(clippy::cast_precision_loss)]
+fn new_moon(k: i32) -> f64 {
+    let k = f64::from(k);
+    let t = k / 1236.85;
+    let julian_ephemeris_day = 2_451_550.097_66 + SYNODIC_MONTH * k + 0.000_154_37 * t.powi(2)
+        - 0.000_000_150 * t.powi(3)
+        + 0.000_000_000_73 * t.powi(4);
+    let e = 1.0 - 0.002_516 * t - 0.000_007_4 * t.powi(2);
+    let m = 2.5534 + 29.105_356_70 * k - 0.000_001_4 * t.powi(2) - 0.000_000_11 * t.powi(3);
+    let mp = 201.5643 + 385.816_935_28 * k + 0.010_758_2 * t.powi(2) + 0.000_012_38 * t.powi(3)
+        - 0.000_000_058 * t.powi(4);
+    let f = 160.7108 + 390.670_502_84 * k - 0.001_611_8 * t.powi(2) - 0.000_002_27 * t.powi(3)
+        + 0.000_000_011 * t.powi(4);
+    let omega = 124.7746 - 1.563_755_88 * k + 0.002_067_2 * t.powi(2) + 0.000_002_15 * t.powi(3);
+
+    let correction = -0.407_20 * sin_deg(mp)
+        + 0.172_41 * e * sin_deg(m)
+        + 0.016_08 * sin_deg(2.0 * mp)
+        + 0.010_39 * sin_deg(2.0 * f)
+        + 0.007_39 * e * sin_deg(mp - m)
+        - 0.005_14 * e * sin_deg(mp + m)
+        + 0.002_08 * e * e * sin_deg(2.0 * m)
+        - 0.001_11 * sin_deg(mp - 2.0 * f)
+        - 0.000_57 * sin_deg(mp + 2.0 * f)
+        + 0.000_56 * e * sin_deg(2.0 * mp + m)
+        - 0.000_42 * sin_deg(3.0 * mp)
+        + 0.000_42 * e * sin_deg(m + 2.0 * f)
+        + 0.000_38 * e * sin_deg(m - 2.0 * f)
+        - 0.000_24 * e * sin_deg(2.0 * mp - m)
+        - 0.000_17 * sin_deg(omega)
+        - 0.000_07 * sin_deg(mp + 2.0 * m)
+        + 0.000_04 * sin_deg(2.0 * mp - 2.0 * f)
+        + 0.000_04 * sin_deg(3.0 * m)
+        + 0.000_03 * sin_deg(mp + m - 2.0 * f)
+        + 0.000_03 * sin_deg(2.0 * mp + 2.0 * f)
+        - 0.000_03 * sin_deg(mp + m + 2.0 * f)
+        + 0.000_03 * sin_deg(mp - m + 2.0 * f)
+        - 0.000_02 * sin_deg(mp - m - 2.0 * f)
+        - 0.000_02 * sin_deg(3.0 * mp + m)
+        + 0.000_02 * sin_deg(4.0 * mp);
+
+    // The corrections for the positions of the planets.
+    let planetary = [
+        (0.000_325, 299.77 + 0.107_408 * k - 0.009_173 * t.powi(2)),
+        (0.000_165, 251.88 + 0.016_321 * k),
+        (0.000_164, 251.83 + 26.651_886 * k),
+        (0.000_126, 349.42 + 36.412_478 * k),
+        (0.000_110, 84.66 + 18.206_239 * k),
+        (0.000_062, 141.74 + 53.303_771 * k),
+        (0.000_060, 207.14 + 2.453_732 * k),
+        (0.000_056, 154.84 + 7.306_860 * k),
+        (0.000_047, 34.52 + 27.261_239 * k),
+        (0.000_042, 207.19 + 0.121_824 * k),
+        (0.000_040, 291.34 + 1.844_379 * k),
+        (0.000_037, 161.72 + 24.198_154 * k),
+        (0.000_035, 239.56 + 25.513_099 * k),
+        (0.000_023, 331.55 + 3.592_518 * k),
+    ]
+    .into_iter()
+    .map(|(amplitude, argument)| amplitude * sin_deg(argument))
+    .sum::<f64>();
+
+    let julian_ephemeris_day = julian_ephemeris_day + correction + planetary;
+    julian_ephemeris_day - delta_t(julian_ephemeris_day)
+}
+
+/// The day in Beijing at the julian day `julian_day` (UT).
+#[allow(clippy::cast_possible_truncation)]
+fn beijing_day(julian_day: f64) -> i32 {
+    (julian_day - JULIAN_DAY_0 + BEIJING_OFFSET).floor() as i32
+}
+
+/// The julian day (UT) at which `day` starts in Beijing.
+fn beijing_midnight(day: i32) -> f64 {
+    f64::from(day) + JULIAN_DAY_0 - BEIJING_OFFSET
+}
+
+/// The number of the last new moon which falls on or before `day` in Beijing.
+#[allow(clippy::cast_possible_truncation)]
+fn new_moon_on_or_before(day: i32) -> i32 {
+    let mut k = ((beijing_midnight(day) - 2_451_550.1) / SYNODIC_MONTH).floor() as i32;
+    while beijing_day(new_moon(k)) > day {
+        k -= 1;
+    }
+    while beijing_day(new_moon(k + 1)) <= day {
+        k += 1;
+    }
+    k
+}
+
+/// The day of the winter solstice of `year` in Beijing.
+fn winter_solstice(year: i32) -> i32 {
+    let mut julian_day = beijing_midnight(
+        chrono::NaiveDate::from_ymd_opt(year, 12, 21)
+            .expect("year to be within the supported range")
+            .num_days_from_ce(),
+    );
+    for _ in 0..5 {
+        let difference = (270.0 - solar_longitude(julian_day) + 180.0).rem_euclid(360.0) - 180.0;
+        julian_day += difference * TROPICAL_YEAR / 360.0;
+    }
+    beijing_day(julian_day)
+}
+
+/// The number of the principal solar term which was last passed at the start of `day`,
+/// each term covers 30 degrees of the longitude of the sun.
+#[allow(clippy::cast_possible_truncation)]
+fn principal_term(day: i32) -> i32 {
+    (solar_longitude(beijing_midnight(day)) / 30.0).floor() as i32
+}
+
+/// The first days of the months from the month 11 which contains the winter solstice of
+/// `year`, up to and including the month 11 of the next year, with the index of the
+/// leap month if there is one.
+fn sui(year: i32) -> (Vec<i32>, Option<usize>) {
+    let first = new_moon_on_or_before(winter_solstice(year));
+    let last = new_moon_on_or_before(winter_solstice(year + 1));
+    let first_days = (first..=last)
+        .map(|k| beijing_day(new_moon(k)))
+        .collect::<Vec<_>>();
+
+    // With 13 months, the first one without a principal solar term is the leap month.
+    let leap_month = if first_days.len() == 14 {
+        first_days
+            .windows(2)
+            .position(|month| principal_term(month[0]) == principal_term(month[1]))
+    } else {
+        None
+    };
+    (first_days, leap_month)
+}
+
+pub(super) fn months(first_day: i32, next_day: i32) -> Vec<CalendarMonth> {
+    // The first year starts in the suì of two years earlier, which is needed to know
+    // whether it has a leap month.
+    let first_year = date_from_day(first_day).year() - 2;
+    let last_year = date_from_day(next_day).year();
+
+    let mut months: Vec<CalendarMonth> = vec![];
+    for year in first_year..=last_year {
+        let (first_days, leap_month) = sui(year);
+        let mut month = 11;
+        for (index, first_days) in first_days.windows(2).enumerate() {
+            let leap = leap_month == Some(index);
+            if index > 0 && !leap {
+                month = month % 12 + 1;
+            }
+            months.push(CalendarMonth {
+                first_day: first_days[0],
+                len: u8::try_from(first_days[1] - first_days[0])
+                    .expect("a month has 29 or 30 days"),
+                month,
+                leap,
+                year_leap_month: None,
+            });
+        }
+    }
+
+    // A year runs from month 1 up to the next month 1, its leap month can be in
+    // either suì.
+    let mut year_start = 0;
+    while year_start < months.len() {
+        let year_end = (year_start + 1..months.len())
+            .find(|&index| months[index].month == 1 && !months[index].leap)
+            .unwrap_or(months.len());
+        let year_leap_month = months[year_start..year_end]
+            .iter()
+            .find(|month| month.leap)
+            .map(|month| month.month);
+        for month in &mut months[year_start..year_end] {
+            month.year_leap_month = year_leap_month;
+        }
+        year_start = year_end;
+    }
+
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RScale;
+    use chrono::NaiveDate;
+
+    fn day(year: i32, month: u32, day: u32) -> i32 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .num_days_from_ce()
+    }
+
+    #[test]
+    fn finds_new_years() {
+        let new_years = [
+            (2013, 2, 10),
+            (2014, 1, 31),
+            (2015, 2, 19),
+            (2016, 2, 8),
+            (2017, 1, 28),
+            (2018, 2, 16),
+            (2019, 2, 5),
+            (2020, 1, 25),
+            (2021, 2, 12),
+            (2022, 2, 1),
+            (2023, 1, 22),
+            (2024, 2, 10),
+            (2025, 1, 29),
+            (2026, 2, 17),
+        ];
+        for (year, month, new_year) in new_years {
+            let months = super::super::months(RScale::Chinese, day(year, 1, 1), day(year, 12, 31));
+            let first = months
+                .iter()
+                .find(|month| month.month == 1 && !month.leap)
+                .unwrap();
+            assert_eq!(
+                date_from_day(first.first_day),
+                NaiveDate::from_ymd_opt(year, month, new_year).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn finds_leap_months() {
+        let leap_months = [
+            (2012, Some(4)),
+            (2014, Some(9)),
+            (2017, Some(6)),
+            (2019, None),
+            (2020, Some(4)),
+            (2023, Some(2)),
+            (2025, Some(6)),
+            (2033, Some(11)),
+        ];
+        for (year, leap_month) in leap_months {
+            let months = super::super::months(RScale::Chinese, day(year, 7, 1), day(year, 7, 2));
+            assert_eq!(months[0].year_leap_month, leap_month, "{year}");
+        }
+    }
+}
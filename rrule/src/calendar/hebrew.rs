@@ -0,0 +1,136 @@
+//! The arithmetic Hebrew calendar, following "Calendrical Calculations" by
+//! Reingold and Dershowitz.
+//!
+//! The months are numbered from Tishri, like `RSCALE=HEBREW` in RFC 7529, so Adar I
+//! of leap years is `5L` and Adar or Adar II is `6`.
+
+use super::{date_from_day, CalendarMonth};
+use chrono::Datelike;
+
+/// The day of 1 Tishri of the year 1 AM.
+const EPOCH: i64 = -1_373_427;
+
+fn is_leap_year(year: i64) -> bool {
+    (7 * year + 1).rem_euclid(19) < 7
+}
+
+/// The number of days between the epoch and the molad of Tishri of `year`, postponed
+/// when that day is a Sunday, Wednesday or Friday.
+fn elapsed_days(year: i64) -> i64 {
+    let months_elapsed = (235 * year - 234).div_euclid(19);
+    let parts_elapsed = 12_084 + 13_753 * months_elapsed;
+    let days = 29 * months_elapsed + parts_elapsed.div_euclid(25_920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+/// Postpones the new year to keep the year length within 353-355 or 383-385 days.
+fn year_length_correction(year: i64) -> i64 {
+    let previous = elapsed_days(year - 1);
+    let current = elapsed_days(year);
+    let next = elapsed_days(year + 1);
+    if next - current == 356 {
+        2
+    } else if current - previous == 382 {
+        1
+    } else {
+        0
+    }
+}
+
+/// The day of 1 Tishri of `year`.
+fn new_year(year: i64) -> i64 {
+    EPOCH + elapsed_days(year) + year_length_correction(year)
+}
+
+/// The months of `year`, in the order they occur.
+fn year_months(year: i64) -> Vec<CalendarMonth> {
+    let first_day = new_year(year);
+    let year_len = new_year(year + 1) - first_day;
+    let leap = is_leap_year(year);
+    // Heshvan has 30 days in long years, Kislev has 29 days in short years.
+    let heshvan = if year_len % 10 == 5 { 30 } else { 29 };
+    let kislev = if year_len % 10 == 3 { 29 } else { 30 };
+
+    let mut lengths = vec![(1, false, 30), (2, false, heshvan), (3, false, kislev)];
+    lengths.extend([(4, false, 29), (5, false, 30)]);
+    if leap {
+        lengths.push((5, true, 30));
+    }
+    lengths.extend([(6, false, 29), (7, false, 30), (8, false, 29)]);
+    lengths.extend([
+        (9, false, 30),
+        (10, false, 29),
+        (11, false, 30),
+        (12, false, 29),
+    ]);
+
+    let mut first_day = i32::try_from(first_day).expect("day to be within the supported range");
+    lengths
+        .into_iter()
+        .map(|(month, is_leap_month, len)| {
+            let calendar_month = CalendarMonth {
+                first_day,
+                len,
+                month,
+                leap: is_leap_month,
+                year_leap_month: leap.then_some(5),
+            };
+            first_day += i32::from(len);
+            calendar_month
+        })
+        .collect()
+}
+
+pub(super) fn months(first_day: i32, next_day: i32) -> Vec<CalendarMonth> {
+    // The Hebrew year starts in September or October of the Gregorian year before.
+    let mut year = i64::from(date_from_day(first_day).year()) + 3760;
+    while new_year(year) > i64::from(first_day) {
+        year -= 1;
+    }
+    while new_year(year + 1) <= i64::from(first_day) {
+        year += 1;
+    }
+
+    let mut months = vec![];
+    while new_year(year) < i64::from(next_day) {
+        months.extend(year_months(year));
+        year += 1;
+    }
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn day(year: i32, month: u32, day: u32) -> i32 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .num_days_from_ce()
+    }
+
+    #[test]
+    fn finds_new_years() {
+        assert_eq!(new_year(5784), i64::from(day(2023, 9, 16)));
+        assert_eq!(new_year(5785), i64::from(day(2024, 10, 3)));
+        assert_eq!(new_year(5786), i64::from(day(2025, 9, 23)));
+    }
+
+    #[test]
+    fn finds_leap_months() {
+        // 5784 is a leap year, Adar I started on 10 February 2024.
+        let months = months(day(2024, 1, 1), day(2024, 12, 31));
+        let adar_1 = months.iter().find(|month| month.leap).unwrap();
+        assert_eq!((adar_1.month, adar_1.first_day), (5, day(2024, 2, 10)));
+        // Passover, 15 Nisan
+        let nisan = months.iter().find(|month| month.month == 7).unwrap();
+        assert_eq!(nisan.first_day + 14, day(2024, 4, 23));
+        // 5785 has no leap month.
+        assert_eq!(months.last().unwrap().year_leap_month, None);
+    }
+}
@@ -0,0 +1,112 @@
+//! The months of the calendar systems which can be selected with `RSCALE`.
+//!
+//! Days are counted like [`chrono::NaiveDate::num_days_from_ce`], so 1 January of year 1
+//! of the Gregorian calendar is day 1.
+
+mod chinese;
+mod hebrew;
+
+use crate::RScale;
+use chrono::{Datelike, NaiveDate};
+
+/// A month of a calendar system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CalendarMonth {
+    /// The first day of the month.
+    pub first_day: i32,
+    /// The number of days in the month.
+    pub len: u8,
+    /// The number of the month, as used by `BYMONTH`.
+    pub month: u8,
+    /// Whether this is the leap month which follows the month with the same number.
+    pub leap: bool,
+    /// The leap month of the calendar year this month belongs to, if it has one.
+    pub year_leap_month: Option<u8>,
+}
+
+impl CalendarMonth {
+    /// The day after the last day of the month.
+    pub fn next_first_day(&self) -> i32 {
+        self.first_day + i32::from(self.len)
+    }
+
+    /// The day of the month of `day`, counting from 1.
+    pub fn month_day(&self, day: i32) -> u8 {
+        u8::try_from(day - self.first_day + 1).expect("day is within the month")
+    }
+}
+
+/// Returns the consecutive months of `rscale` which contain the days `first_day..next_day`.
+pub(crate) fn months(rscale: RScale, first_day: i32, next_day: i32) -> Vec<CalendarMonth> {
+    let mut months = match rscale {
+        RScale::Gregorian => gregorian_months(first_day, next_day),
+        RScale::Hebrew => hebrew::months(first_day, next_day),
+        RScale::Chinese => chinese::months(first_day, next_day),
+    };
+    months.retain(|month| month.next_first_day() > first_day && month.first_day < next_day);
+    months
+}
+
+/// Returns the month of `rscale` which contains `date`.
+pub(crate) fn month_of(rscale: RScale, date: NaiveDate) -> CalendarMonth {
+    let day = date.num_days_from_ce();
+    months(rscale, day, day + 1)[0]
+}
+
+fn gregorian_months(first_day: i32, next_day: i32) -> Vec<CalendarMonth> {
+    let mut date = date_from_day(first_day)
+        .with_day(1)
+        .expect("every month has a first day");
+    let mut months = vec![];
+    while date.num_days_from_ce() < next_day {
+        let next = date
+            .checked_add_months(chrono::Months::new(1))
+            .expect("date to be within the supported range");
+        months.push(CalendarMonth {
+            first_day: date.num_days_from_ce(),
+            len: u8::try_from(next.num_days_from_ce() - date.num_days_from_ce())
+                .expect("a month has at most 31 days"),
+            month: u8::try_from(date.month()).expect("range 1-12 is covered by u8"),
+            leap: false,
+            year_leap_month: None,
+        });
+        date = next;
+    }
+    months
+}
+
+pub(crate) fn date_from_day(day: i32) -> NaiveDate {
+    NaiveDate::from_num_days_from_ce_opt(day).expect("day to be within the supported range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> i32 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .num_days_from_ce()
+    }
+
+    #[test]
+    fn finds_gregorian_months() {
+        let months = months(RScale::Gregorian, day(2024, 1, 31), day(2024, 3, 1));
+        assert_eq!(
+            months
+                .iter()
+                .map(|month| (month.month, month.len))
+                .collect::<Vec<_>>(),
+            vec![(1, 31), (2, 29)]
+        );
+        assert_eq!(months[1].first_day, day(2024, 2, 1));
+    }
+
+    #[test]
+    fn finds_month_of_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 10, 3).unwrap();
+        let month = month_of(RScale::Hebrew, date);
+        assert_eq!((month.month, month.leap), (1, false));
+        assert_eq!(month.month_day(date.num_days_from_ce()), 1);
+    }
+}
@@ -0,0 +1,247 @@
+use super::datetime::datetime_to_ical_format;
+use super::recurrence_override::RecurrenceOverride;
+use super::rruleset::RRuleSet;
+use crate::parser::{parse_components, parse_recurrence_id, ComponentLines};
+use crate::{RRuleError, Tz};
+use chrono::DateTime;
+use log::warn;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The kind of an iCalendar component which has a recurrence set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    /// A `VEVENT` component.
+    Event,
+    /// A `VTODO` component.
+    Todo,
+}
+
+impl Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Event => write!(f, "VEVENT"),
+            Self::Todo => write!(f, "VTODO"),
+        }
+    }
+}
+
+/// A `VEVENT` or `VTODO` of a [`Calendar`], with the [`RRuleSet`] of its occurrences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarComponent {
+    kind: ComponentKind,
+    uid: Option<String>,
+    rrule_set: RRuleSet,
+}
+
+impl CalendarComponent {
+    /// Returns whether the component is a `VEVENT` or a `VTODO`.
+    #[must_use]
+    pub fn get_kind(&self) -> ComponentKind {
+        self.kind
+    }
+
+    /// Returns the `UID` of the component.
+    #[must_use]
+    pub fn get_uid(&self) -> Option<&str> {
+        self.uid.as_deref()
+    }
+
+    /// Returns the occurrences of the component.
+    #[must_use]
+    pub fn get_rrule_set(&self) -> &RRuleSet {
+        &self.rrule_set
+    }
+
+    /// Returns the occurrences of the component, consuming it.
+    #[must_use]
+    pub fn into_rrule_set(self) -> RRuleSet {
+        self.rrule_set
+    }
+}
+
+/// The components with occurrences of an iCalendar (`.ics`) document.
+///
+/// Every `VEVENT` and `VTODO` becomes a [`CalendarComponent`] with its `DTSTART`, `RRULE`,
+/// `EXRULE`, `RDATE`, `EXDATE` and `DTEND` or `DURATION` (`DUE` for a `VTODO`). Other
+/// properties and components are ignored, and so is a component without a `DTSTART`.
+/// A component without an `RRULE` or `RDATE` only occurs at its `DTSTART`.
+///
+/// A component with a `RECURRENCE-ID` is added to the component with the same `UID` as a
+/// [`RecurrenceOverride`], which cancels the occurrence if it has `STATUS:CANCELLED` and
+/// moves it to its `DTSTART` otherwise. A moved occurrence gets the duration of the `DTEND` or
+/// `DURATION` of the overriding component, and keeps its start if that has no `DTSTART`.
+///
+/// A `TZID` refers to a `VTIMEZONE` of the document if there is one with the same `TZID`,
+/// see [`VTimezone`](crate::VTimezone), and to the timezones of `chrono_tz` otherwise.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{Calendar, ComponentKind};
+///
+/// let calendar: Calendar = "BEGIN:VCALENDAR\r\n\
+///     BEGIN:VEVENT\r\n\
+///     UID:standup\r\n\
+///     SUMMARY:Standup\r\n\
+///     DTSTART:20210101T090000Z\r\n\
+///     RRULE:FREQ=DAILY;COUNT=3\r\n\
+///     END:VEVENT\r\n\
+///     BEGIN:VEVENT\r\n\
+///     UID:standup\r\n\
+///     RECURRENCE-ID:20210102T090000Z\r\n\
+///     DTSTART:20210102T100000Z\r\n\
+///     END:VEVENT\r\n\
+///     END:VCALENDAR\r\n"
+///     .parse()
+///     .unwrap();
+///
+/// let event = &calendar.get_components()[0];
+/// assert_eq!(event.get_kind(), ComponentKind::Event);
+/// assert_eq!(event.get_uid(), Some("standup"));
+/// let dates = event.get_rrule_set().clone().all(10).dates;
+/// assert_eq!(dates[1].to_rfc3339(), "2021-01-02T10:00:00+00:00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Calendar {
+    components: Vec<CalendarComponent>,
+    timezones: Vec<Tz>,
+}
+
+impl Calendar {
+    /// Returns the components in the order they are found in the document.
+    #[must_use]
+    pub fn get_components(&self) -> &Vec<CalendarComponent> {
+        &self.components
+    }
+
+    /// Returns the timezones defined by the `VTIMEZONE`s of the document.
+    #[must_use]
+    pub fn get_timezones(&self) -> &Vec<Tz> {
+        &self.timezones
+    }
+
+    /// Returns the components in the order they are found in the document, consuming the calendar.
+    #[must_use]
+    pub fn into_components(self) -> Vec<CalendarComponent> {
+        self.components
+    }
+}
+
+impl FromStr for Calendar {
+    type Err = RRuleError;
+
+    /// Creates a [`Calendar`] from an iCalendar document if the input is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if a component isn't closed or a recurrence property is invalid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let calendar = parse_components(s)?;
+        let timezones = calendar
+            .timezones
+            .into_iter()
+            .map(|timezone| Ok(Tz::try_from(timezone.into_vtimezone()?)?))
+            .collect::<Result<Vec<_>, RRuleError>>()?;
+
+        let mut components: Vec<CalendarComponent> = vec![];
+        let mut overrides = vec![];
+
+        for component in calendar.components {
+            if let Some(recurrence_id) = &component.recurrence_id {
+                let (recurrence_id, range) = parse_recurrence_id(recurrence_id, &timezones)?;
+                let recurrence_override = if component.cancelled {
+                    RecurrenceOverride::cancelled()
+                } else {
+                    moved_override(&component, &recurrence_id, &timezones)?
+                };
+                overrides.push((
+                    component.uid,
+                    recurrence_id,
+                    recurrence_override.range(range),
+                ));
+                continue;
+            }
+
+            let Some(rrule_set) = component_rrule_set(&component.lines, &timezones)? else {
+                continue;
+            };
+            components.push(CalendarComponent {
+                kind: component.kind,
+                uid: component.uid,
+                rrule_set,
+            });
+        }
+
+        // The overriding components may be given before the component they override
+        for (uid, recurrence_id, recurrence_override) in overrides {
+            let Some(component) = components
+                .iter_mut()
+                .find(|component| uid.is_some() && component.uid == uid)
+            else {
+                warn!("Ignoring `RECURRENCE-ID:{recurrence_id}` without a component with the same UID");
+                continue;
+            };
+            component.rrule_set = component
+                .rrule_set
+                .clone()
+                .override_occurrence(recurrence_id, recurrence_override);
+        }
+
+        Ok(Self {
+            components,
+            timezones,
+        })
+    }
+}
+
+/// Creates the override of a component with a `RECURRENCE-ID`, which moves the occurrence to
+/// the `DTSTART` of the component and gives it the duration of its `DTEND` or `DURATION`.
+/// Without a `DTSTART`, the occurrence keeps its start.
+fn moved_override(
+    component: &ComponentLines,
+    recurrence_id: &DateTime<Tz>,
+    timezones: &[Tz],
+) -> Result<RecurrenceOverride, RRuleError> {
+    let mut lines = component.lines.clone();
+    let has_dt_start = lines
+        .iter()
+        .any(|line| line.to_uppercase().starts_with("DTSTART"));
+    if !has_dt_start {
+        lines.push(format!("DTSTART{}", datetime_to_ical_format(recurrence_id)));
+    }
+    let rrule_set = component_rrule_set(&lines, timezones)?.expect("the component has a `DTSTART`");
+
+    let start = *rrule_set.get_dt_start();
+    let duration = match (rrule_set.get_dt_end(), rrule_set.get_duration()) {
+        (Some(dt_end), _) => Some(*dt_end - start),
+        (None, duration) => duration.copied(),
+    };
+    let recurrence_override = RecurrenceOverride::moved(start);
+    Ok(match duration {
+        Some(duration) => recurrence_override.duration(duration),
+        None => recurrence_override,
+    })
+}
+
+/// Parses the recurrence properties of a component, or returns `None` if it has no `DTSTART`.
+fn component_rrule_set(lines: &[String], timezones: &[Tz]) -> Result<Option<RRuleSet>, RRuleError> {
+    let Some(dt_start) = lines
+        .iter()
+        .find(|line| line.to_uppercase().starts_with("DTSTART"))
+    else {
+        return Ok(None);
+    };
+
+    let mut lines = lines.to_vec();
+    let generates_dates = lines.iter().any(|line| {
+        let line = line.to_uppercase();
+        line.starts_with("RRULE") || line.starts_with("RDATE")
+    });
+    if !generates_dates {
+        // A component without recurrence only occurs at its start
+        lines.push(format!("RDATE{}", &dt_start["DTSTART".len()..]));
+    }
+
+    RRuleSet::parse_with_timezones(&lines.join("\n"), timezones).map(Some)
+}
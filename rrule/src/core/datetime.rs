@@ -1,11 +1,5 @@
 use super::timezone::Tz;
-use chrono::{Datelike, Duration, NaiveTime, Timelike};
-
-pub(crate) fn duration_from_midnight(time: NaiveTime) -> Duration {
-    Duration::hours(i64::from(time.hour()))
-        + Duration::minutes(i64::from(time.minute()))
-        + Duration::seconds(i64::from(time.second()))
-}
+use chrono::{Datelike, Duration, Timelike, Utc};
 
 pub(crate) fn get_month(dt: &chrono::DateTime<Tz>) -> u8 {
     u8::try_from(dt.month()).expect("month is between 1-12 which is covered by u8")
@@ -36,16 +30,129 @@ pub(crate) fn datetime_to_ical_format(dt: &chrono::DateTime<Tz>) -> String {
     let tz = dt.timezone();
     match tz {
         Tz::Local(_) => {}
-        Tz::Tz(tz) => match tz {
-            chrono_tz::UTC => {
-                tz_postfix = "Z".to_string();
-            }
-            tz => {
-                tz_prefix = format!(";TZID={}", tz.name());
-            }
-        },
+        // Without the `chrono-tz` feature, UTC is a fixed offset
+        _ if tz == Tz::UTC => {
+            tz_postfix = "Z".to_string();
+        }
+        #[cfg(feature = "chrono-tz")]
+        Tz::Tz(tz) => {
+            tz_prefix = format!(";TZID={}", tz.name());
+        }
+        Tz::VTimezone(_) | Tz::Fixed(_) => {
+            // A `TZID` which contains a delimiter of the content line needs to be quoted,
+            // like the `UTC+05:30` of a fixed offset
+            tz_prefix = if tz.name().contains([':', ';', ',']) {
+                format!(";TZID=\"{}\"", tz.name())
+            } else {
+                format!(";TZID={}", tz.name())
+            };
+        }
     }
 
     let dt = dt.format("%Y%m%dT%H%M%S");
     format!("{}:{}{}", tz_prefix, dt, tz_postfix)
 }
+
+/// Generates an iCalendar date-time string format with the prefix symbols like
+/// [`datetime_to_ical_format`], but with the Windows name of the timezone if it has one.
+/// Like: `;TZID=Eastern Standard Time:19970714T133000`
+#[cfg(feature = "windows-zones")]
+pub(crate) fn datetime_to_windows_ical_format(dt: &chrono::DateTime<Tz>) -> String {
+    match dt.timezone().windows_name() {
+        Some(name) if dt.timezone() != Tz::UTC => {
+            format!(";TZID={}:{}", name, dt.format("%Y%m%dT%H%M%S"))
+        }
+        _ => datetime_to_ical_format(dt),
+    }
+}
+
+/// Adds a duration to a datetime, where a result outside the range of `chrono` becomes the
+/// earliest or latest datetime.
+pub(crate) fn add_duration_clamped(
+    dt: &chrono::DateTime<Tz>,
+    duration: Duration,
+) -> chrono::DateTime<Tz> {
+    dt.checked_add_signed(duration).unwrap_or_else(|| {
+        let bound = if duration < Duration::zero() {
+            chrono::DateTime::<Utc>::MIN_UTC
+        } else {
+            chrono::DateTime::<Utc>::MAX_UTC
+        };
+        bound.with_timezone(&dt.timezone())
+    })
+}
+
+/// Generates an iCalendar duration string, like `PT1H30M`, `P2D` or `-P1W`.
+/// ref: <https://icalendar.org/iCalendar-RFC-5545/3-3-6-duration.html>
+pub(crate) fn duration_to_ical_format(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let total = duration.num_seconds().unsigned_abs();
+
+    let (days, rem) = (total / 86_400, total % 86_400);
+    let (hours, rem) = (rem / 3_600, rem % 3_600);
+    let (minutes, seconds) = (rem / 60, rem % 60);
+
+    if total == 0 {
+        return "PT0S".to_string();
+    }
+    if rem == 0 && hours == 0 && days % 7 == 0 {
+        return format!("{sign}P{}W", days / 7);
+    }
+
+    let mut result = format!("{sign}P");
+    if days > 0 {
+        result.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 {
+        result.push('T');
+        if hours > 0 {
+            result.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            result.push_str(&format!("{minutes}M"));
+        }
+        if seconds > 0 {
+            result.push_str(&format!("{seconds}S"));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn clamps_datetimes_plus_durations() {
+        let dt = Tz::UTC.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap();
+        assert_eq!(
+            add_duration_clamped(&dt, Duration::hours(2)),
+            Tz::UTC.with_ymd_and_hms(2025, 1, 6, 11, 0, 0).unwrap()
+        );
+        assert_eq!(
+            add_duration_clamped(&dt, Duration::weeks(20_000_000)),
+            chrono::DateTime::<Utc>::MAX_UTC.with_timezone(&Tz::UTC)
+        );
+        assert_eq!(
+            add_duration_clamped(&dt, -Duration::weeks(20_000_000)),
+            chrono::DateTime::<Utc>::MIN_UTC.with_timezone(&Tz::UTC)
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        let tests = [
+            (Duration::zero(), "PT0S"),
+            (Duration::hours(2), "PT2H"),
+            (Duration::minutes(90), "PT1H30M"),
+            (Duration::days(1) + Duration::seconds(5), "P1DT5S"),
+            (Duration::weeks(2), "P2W"),
+            (Duration::days(-3), "-P3D"),
+        ];
+        for (duration, expected) in tests {
+            assert_eq!(duration_to_ical_format(duration), expected);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::timezone::Tz;
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+
+/// How an occurrence is resolved when its local time doesn't exist, because it falls in the
+/// gap where the clocks are set forward for daylight saving time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DstGap {
+    /// The local time is moved forward by the length of the gap, so `02:30` becomes `03:30`
+    /// when the clocks skip from `02:00` to `03:00`. This is how RFC 5545 interprets such times.
+    #[default]
+    ShiftForward,
+    /// The occurrence is left out.
+    Skip,
+}
+
+/// How an occurrence is resolved when its local time exists twice, because it falls in the
+/// overlap where the clocks are set back at the end of daylight saving time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DstOverlap {
+    /// The first of the two datetimes, which still has the daylight saving time offset.
+    /// This is how RFC 5545 interprets such times.
+    #[default]
+    Earliest,
+    /// The second of the two datetimes, which has the standard time offset.
+    Latest,
+    /// Both datetimes are occurrences.
+    Both,
+    /// The occurrence is left out.
+    Skip,
+}
+
+/// The resolution of local times around daylight saving time transitions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub(crate) struct DstPolicy {
+    pub gap: DstGap,
+    pub overlap: DstOverlap,
+}
+
+impl DstPolicy {
+    /// Returns the datetimes of a local time in `tz`: none if the occurrence is skipped,
+    /// and two if both datetimes of an overlap are used.
+    pub(crate) fn resolve(self, tz: Tz, local: NaiveDateTime) -> LocalResult<DateTime<Tz>> {
+        match local.and_local_timezone(tz) {
+            LocalResult::Single(dt) => LocalResult::Single(dt),
+            LocalResult::Ambiguous(earliest, latest) => match self.overlap {
+                DstOverlap::Earliest => LocalResult::Single(earliest),
+                DstOverlap::Latest => LocalResult::Single(latest),
+                DstOverlap::Both => LocalResult::Ambiguous(earliest, latest),
+                DstOverlap::Skip => LocalResult::None,
+            },
+            LocalResult::None => match self.gap {
+                DstGap::ShiftForward => {
+                    // Interpreting the local time with the offset before the gap moves it
+                    // forward by the length of the gap.
+                    let before = tz
+                        .offset_from_utc_datetime(&(local - Duration::days(1)))
+                        .fix();
+                    local
+                        .checked_sub_signed(Duration::seconds(before.local_minus_utc().into()))
+                        .map_or(LocalResult::None, |utc| {
+                            LocalResult::Single(tz.from_utc_datetime(&utc))
+                        })
+                }
+                DstGap::Skip => LocalResult::None,
+            },
+        }
+    }
+}
+
+/// Returns the datetimes of a resolved local time, in ascending order.
+pub(crate) fn resolved_datetimes(
+    resolved: LocalResult<DateTime<Tz>>,
+) -> impl Iterator<Item = DateTime<Tz>> {
+    let (first, second) = match resolved {
+        LocalResult::Single(dt) => (Some(dt), None),
+        LocalResult::Ambiguous(earliest, latest) => (Some(earliest), Some(latest)),
+        LocalResult::None => (None, None),
+    };
+    first.into_iter().chain(second)
+}
@@ -0,0 +1,209 @@
+//! Conversions from and to the datetimes of the `jiff` crate.
+use chrono::{DateTime, FixedOffset, Offset};
+use jiff::{
+    tz::{self, TimeZone},
+    Timestamp, Zoned,
+};
+
+use super::{lookup_timezone, rrule::RRule, timezone::Tz, RRuleSet, Unvalidated};
+use crate::{parser::ParseError, RRuleError};
+
+impl Tz {
+    /// Converts a `jiff` time zone. A time zone with an IANA name is looked up with the
+    /// installed `TimezoneProvider`, any other time zone needs to have a fixed offset.
+    #[must_use]
+    pub fn from_jiff(time_zone: &TimeZone) -> Option<Self> {
+        if let Some(name) = time_zone.iana_name() {
+            return lookup_timezone(name);
+        }
+        let offset = time_zone.to_fixed_offset().ok()?;
+        FixedOffset::east_opt(offset.seconds()).map(Self::Fixed)
+    }
+
+    /// Converts to a `jiff` time zone. Timezones are looked up by name in the time zone database
+    /// of `jiff`, so a `VTimezone` converts only if its `TZID` is an IANA name.
+    #[must_use]
+    pub fn to_jiff(&self) -> Option<TimeZone> {
+        match self {
+            Self::Local(_) => Some(TimeZone::system()),
+            _ if *self == Self::UTC => Some(TimeZone::UTC),
+            Self::Fixed(offset) => tz::Offset::from_seconds(offset.local_minus_utc())
+                .ok()
+                .map(TimeZone::fixed),
+            _ => TimeZone::get(self.name()).ok(),
+        }
+    }
+}
+
+/// Converts a [`Zoned`] to a datetime in the same timezone.
+pub(crate) fn zoned_to_datetime(zoned: &Zoned) -> Result<DateTime<Tz>, ParseError> {
+    let time_zone = zoned.time_zone();
+    let tz = Tz::from_jiff(time_zone).ok_or_else(|| {
+        ParseError::InvalidTimezone(time_zone.iana_name().unwrap_or("unnamed").into())
+    })?;
+    Ok(timestamp_to_datetime(zoned.timestamp()).with_timezone(&tz))
+}
+
+fn timestamp_to_datetime(timestamp: Timestamp) -> DateTime<chrono::Utc> {
+    let nanoseconds = timestamp.as_nanosecond();
+    let seconds = i64::try_from(nanoseconds.div_euclid(1_000_000_000))
+        .expect("timestamps of 10000 years are covered by i64");
+    let subsec = u32::try_from(nanoseconds.rem_euclid(1_000_000_000))
+        .expect("nanoseconds of a second are covered by u32");
+    DateTime::from_timestamp(seconds, subsec).expect("range of jiff is covered by chrono")
+}
+
+/// Converts a datetime to a [`Zoned`], or returns `None` if it is outside the range of `jiff`.
+/// A timezone without a `jiff` equivalent keeps its offset only.
+pub(crate) fn datetime_to_zoned(dt: &DateTime<Tz>) -> Option<Zoned> {
+    let nanoseconds = i32::try_from(dt.timestamp_subsec_nanos()).ok()?;
+    let timestamp = Timestamp::new(dt.timestamp(), nanoseconds).ok()?;
+    let time_zone = match dt.timezone().to_jiff() {
+        Some(time_zone) => time_zone,
+        None => {
+            TimeZone::fixed(tz::Offset::from_seconds(dt.offset().fix().local_minus_utc()).ok()?)
+        }
+    };
+    Some(timestamp.to_zoned(time_zone))
+}
+
+impl RRule<Unvalidated> {
+    /// Validates the [`RRule`] with a `jiff` `dt_start` and creates an [`RRuleSet`] struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`] if the time zone of `dt_start` has no equivalent [`Tz`], or if the
+    /// rrule is invalid.
+    pub fn build_zoned(self, dt_start: &Zoned) -> Result<RRuleSet, RRuleError> {
+        self.build(zoned_to_datetime(dt_start)?)
+    }
+}
+
+impl RRuleSet {
+    /// Only return recurrences that comes before this `jiff` datetime, see [`RRuleSet::before`].
+    #[must_use]
+    pub fn before_zoned(self, dt: &Zoned) -> Self {
+        let tz = self.get_dt_start().timezone();
+        self.before(timestamp_to_datetime(dt.timestamp()).with_timezone(&tz))
+    }
+
+    /// Only return recurrences that comes after this `jiff` datetime, see [`RRuleSet::after`].
+    #[must_use]
+    pub fn after_zoned(self, dt: &Zoned) -> Self {
+        let tz = self.get_dt_start().timezone();
+        self.after(timestamp_to_datetime(dt.timestamp()).with_timezone(&tz))
+    }
+}
+
+/// An iterator adapter which yields the datetimes of an iterator as `jiff::Zoned`, in the time zone
+/// returned by [`Tz::to_jiff`]. The iterator ends at the first datetime outside the range of `jiff`.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{RRuleSet, ZonedIterator};
+///
+/// let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20250101T090000\nRRULE:FREQ=DAILY;COUNT=2"
+///     .parse()
+///     .unwrap();
+/// let dates = rrule_set.into_iter().zoned().collect::<Vec<_>>();
+/// assert_eq!(dates[1].to_string(), "2025-01-02T09:00:00+01:00[Europe/Berlin]");
+/// ```
+pub trait ZonedIterator: Iterator<Item = DateTime<Tz>> + Sized {
+    /// Yields the datetimes as `jiff::Zoned`.
+    fn zoned(self) -> ZonedIter<Self> {
+        ZonedIter { inner: self }
+    }
+}
+
+impl<I: Iterator<Item = DateTime<Tz>>> ZonedIterator for I {}
+
+/// The iterator returned by [`ZonedIterator::zoned`].
+#[derive(Clone, Debug)]
+pub struct ZonedIter<I> {
+    inner: I,
+}
+
+impl<I: Iterator<Item = DateTime<Tz>>> Iterator for ZonedIter<I> {
+    type Item = Zoned;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        datetime_to_zoned(&self.inner.next()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+
+    use super::*;
+
+    #[test]
+    fn converts_time_zones() {
+        let tests = [
+            (TimeZone::UTC, Tz::UTC),
+            (
+                TimeZone::get("America/New_York").unwrap(),
+                Tz::America__New_York,
+            ),
+            (
+                TimeZone::fixed(tz::offset(-3)),
+                Tz::Fixed(FixedOffset::west_opt(3 * 3600).unwrap()),
+            ),
+        ];
+        for (time_zone, tz) in tests {
+            assert_eq!(Tz::from_jiff(&time_zone), Some(tz));
+            assert_eq!(tz.to_jiff(), Some(time_zone));
+        }
+    }
+
+    #[test]
+    fn converts_datetimes() {
+        let zoned: Zoned = "2025-03-30T03:30:00.5+02:00[Europe/Berlin]"
+            .parse()
+            .unwrap();
+        let dt = zoned_to_datetime(&zoned).unwrap();
+        assert_eq!(
+            dt,
+            Tz::Europe__Berlin
+                .with_ymd_and_hms(2025, 3, 30, 3, 30, 0)
+                .unwrap()
+                + chrono::Duration::milliseconds(500)
+        );
+        assert_eq!(datetime_to_zoned(&dt), Some(zoned));
+
+        let before_epoch: Zoned = "1969-12-31T23:59:59.25Z[UTC]".parse().unwrap();
+        let dt = zoned_to_datetime(&before_epoch).unwrap();
+        assert_eq!(dt.timestamp(), -1);
+        assert_eq!(dt.timestamp_subsec_millis(), 250);
+        assert_eq!(datetime_to_zoned(&dt), Some(before_epoch));
+    }
+
+    #[test]
+    fn builds_and_iterates_with_zoned() {
+        let dt_start: Zoned = "2025-01-06T09:00:00[America/New_York]".parse().unwrap();
+        let rrule_set = "FREQ=WEEKLY"
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .build_zoned(&dt_start)
+            .unwrap()
+            .after_zoned(&"2025-03-01T00:00:00Z[UTC]".parse().unwrap())
+            .before_zoned(&"2025-03-20T00:00:00Z[UTC]".parse().unwrap());
+
+        let dates = rrule_set
+            .all(10)
+            .dates
+            .into_iter()
+            .zoned()
+            .map(|zoned| zoned.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                "2025-03-03T09:00:00-05:00[America/New_York]",
+                "2025-03-10T09:00:00-04:00[America/New_York]",
+                "2025-03-17T09:00:00-04:00[America/New_York]",
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! The JSON Schemas of the [`Structured`](super::Structured) serde representation. The ranges of
+//! the rule parts are the ones checked by the validator, so a payload which matches the schema
+//! only fails validation on rules which depend on the frequency or on `dt_start`.
+use std::{borrow::Cow, ops::RangeInclusive};
+
+use chrono::Month;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use super::{Frequency, RScale, Skip};
+use crate::{
+    parser::DURATION_PATTERN,
+    validator::validate_rrule::{
+        by_set_pos_range, by_weekday_range, EASTER_RANGE, HOUR_RANGE, MINUTE_RANGE,
+        MONTH_DAY_RANGE, MONTH_RANGE, WEEK_NO_RANGE, YEAR_DAY_RANGE,
+    },
+};
+
+/// Implements [`JsonSchema`] for an enum which is serialized as one of its `names`.
+macro_rules! string_enum_schema {
+    ($ty:ty, $name:literal, [$($value:expr),+ $(,)?]) => {
+        impl JsonSchema for $ty {
+            fn schema_name() -> Cow<'static, str> {
+                $name.into()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                json_schema!({
+                    "type": "string",
+                    "enum": [$($value.to_string()),+],
+                })
+            }
+        }
+    };
+}
+
+string_enum_schema!(
+    Frequency,
+    "Frequency",
+    [
+        Frequency::Yearly,
+        Frequency::Monthly,
+        Frequency::Weekly,
+        Frequency::Daily,
+        Frequency::Hourly,
+        Frequency::Minutely,
+        Frequency::Secondly,
+    ]
+);
+string_enum_schema!(
+    RScale,
+    "RScale",
+    [RScale::Gregorian, RScale::Hebrew, RScale::Chinese]
+);
+string_enum_schema!(Skip, "Skip", [Skip::Omit, Skip::Backward, Skip::Forward]);
+
+/// The schema of an integer in `range`, which can't be zero unless `zero` is set.
+fn integer<T: Copy + Into<i64>>(range: &RangeInclusive<T>, zero: bool) -> Schema {
+    let (minimum, maximum) = ((*range.start()).into(), (*range.end()).into());
+    if zero {
+        json_schema!({"type": "integer", "minimum": minimum, "maximum": maximum})
+    } else {
+        json_schema!({
+            "type": "integer",
+            "minimum": minimum,
+            "maximum": maximum,
+            "not": {"const": 0},
+        })
+    }
+}
+
+/// The schema of a list of integers in `range`, see [`integer`].
+fn integers<T: Copy + Into<i64>>(range: &RangeInclusive<T>, zero: bool) -> Schema {
+    json_schema!({"type": "array", "items": integer(range, zero)})
+}
+
+pub(crate) fn by_set_pos(_: &mut SchemaGenerator) -> Schema {
+    // The range of a yearly rule is the widest
+    integers(&by_set_pos_range(Frequency::Yearly), false)
+}
+
+pub(crate) fn by_month(_: &mut SchemaGenerator) -> Schema {
+    let names = MONTH_RANGE
+        .clone()
+        .filter_map(|month| Month::try_from(month).ok())
+        .map(|month| month.name())
+        .collect::<Vec<_>>();
+    json_schema!({"type": "array", "items": {"type": "string", "enum": names}})
+}
+
+pub(crate) fn by_leap_month(_: &mut SchemaGenerator) -> Schema {
+    integers(&MONTH_RANGE, true)
+}
+
+pub(crate) fn by_month_day(_: &mut SchemaGenerator) -> Schema {
+    integers(&MONTH_DAY_RANGE, false)
+}
+
+pub(crate) fn by_year_day(_: &mut SchemaGenerator) -> Schema {
+    integers(&YEAR_DAY_RANGE, false)
+}
+
+pub(crate) fn by_week_no(_: &mut SchemaGenerator) -> Schema {
+    integers(&WEEK_NO_RANGE, false)
+}
+
+pub(crate) fn by_weekday_n(_: &mut SchemaGenerator) -> Schema {
+    // The range of a yearly rule is the widest
+    integer(&by_weekday_range(Frequency::Yearly), false)
+}
+
+pub(crate) fn by_hour(_: &mut SchemaGenerator) -> Schema {
+    integers(&HOUR_RANGE, true)
+}
+
+pub(crate) fn by_minute(_: &mut SchemaGenerator) -> Schema {
+    integers(&MINUTE_RANGE, true)
+}
+
+pub(crate) fn by_easter(_: &mut SchemaGenerator) -> Schema {
+    integer(&EASTER_RANGE, true)
+}
+
+pub(crate) fn duration(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({"type": "string", "pattern": DURATION_PATTERN})
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema_for;
+    use serde_json::{json, Value};
+
+    use crate::{RRule, RRuleSet, Structured, Unvalidated};
+
+    /// Deserializes a set with a yearly rule which has `value` for `field`.
+    fn deserialize_with(field: &str, value: Value) -> Result<(), serde_json::Error> {
+        let mut rrule =
+            json!({"freq": "YEARLY", "by_hour": [9], "by_minute": [0], "by_second": [0]});
+        rrule[field] = value;
+        let rrule_set = json!({
+            "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+            "rrule": [rrule],
+        });
+        serde_json::from_value::<Structured<RRuleSet>>(rrule_set).map(|_| ())
+    }
+
+    #[test]
+    fn schema_ranges_agree_with_validation() {
+        let schema = schema_for!(Structured<RRuleSet>);
+        let rrule = &schema.as_value()["$defs"]["RRule"]["properties"];
+        let tests = [
+            ("by_set_pos", false),
+            ("by_month_day", false),
+            ("by_year_day", false),
+            ("by_week_no", false),
+            ("by_hour", true),
+            ("by_minute", true),
+            ("by_second", true),
+        ];
+        for (field, zero) in tests {
+            let items = &rrule[field]["items"];
+            let minimum = items["minimum"].as_i64().unwrap();
+            let maximum = items["maximum"].as_i64().unwrap();
+            assert_eq!(items["not"] == json!({"const": 0}), !zero, "{field}");
+
+            for valid in [minimum, maximum] {
+                assert!(deserialize_with(field, json!([valid])).is_ok(), "{field}");
+            }
+            assert!(
+                deserialize_with(field, json!([maximum + 1])).is_err(),
+                "{field}"
+            );
+            if minimum < 0 {
+                assert!(
+                    deserialize_with(field, json!([minimum - 1])).is_err(),
+                    "{field}"
+                );
+            }
+            // A `BYMONTHDAY` of 0 is dropped before validation
+            if field != "by_month_day" {
+                assert_eq!(deserialize_with(field, json!([0])).is_ok(), zero, "{field}");
+            }
+        }
+
+        let n = &schema.as_value()["$defs"]["NWeekday"]["properties"]["n"];
+        let maximum = n["maximum"].as_i64().unwrap();
+        assert_eq!(n["not"], json!({"const": 0}));
+        assert!(deserialize_with("by_weekday", json!([{"n": maximum, "weekday": "Mon"}])).is_ok());
+        assert!(
+            deserialize_with("by_weekday", json!([{"n": maximum + 1, "weekday": "Mon"}])).is_err()
+        );
+        assert!(deserialize_with("by_weekday", json!([{"n": 0, "weekday": "Mon"}])).is_err());
+    }
+
+    #[test]
+    fn schema_describes_the_structured_form() {
+        let schema = schema_for!(Structured<RRule<Unvalidated>>);
+        let schema = schema.as_value();
+        assert_eq!(schema["title"], "RRule");
+        assert_eq!(schema["required"], json!(["freq"]));
+        assert_eq!(schema["properties"]["freq"]["$ref"], "#/$defs/Frequency");
+        assert_eq!(
+            schema["$defs"]["Frequency"]["enum"],
+            json!(["YEARLY", "MONTHLY", "WEEKLY", "DAILY", "HOURLY", "MINUTELY", "SECONDLY"])
+        );
+        assert_eq!(
+            schema["properties"]["by_month"]["items"]["enum"][0],
+            "January"
+        );
+        assert_eq!(
+            schema["properties"]["by_weekday"]["items"]["$ref"],
+            "#/$defs/NWeekday"
+        );
+
+        let schema = schema_for!(Structured<RRuleSet>);
+        let schema = schema.as_value();
+        assert_eq!(schema["title"], "RRuleSet");
+        assert_eq!(schema["required"], json!(["dt_start"]));
+        assert_eq!(
+            schema["properties"]["rrule"]["items"]["$ref"],
+            "#/$defs/RRule"
+        );
+        assert!(schema["properties"]["duration"]["pattern"]
+            .as_str()
+            .unwrap()
+            .starts_with("^([+-])?P"));
+    }
+}
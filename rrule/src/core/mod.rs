@@ -1,16 +1,57 @@
+mod calendar_component;
 mod datetime;
+mod dst;
+#[cfg(feature = "jiff")]
+mod jiff_interop;
+#[cfg(feature = "json-schema")]
+mod json_schema;
+mod occurrence;
+mod period;
+mod recurrence_override;
 mod rrule;
 mod rruleset;
+#[cfg(feature = "serde")]
+mod structured;
+#[cfg(feature = "time")]
+mod time_interop;
 mod timezone;
 mod timezone_impl;
+mod timezone_provider;
 pub(crate) mod utils;
+mod vtimezone;
+#[cfg(feature = "windows-zones")]
+mod windows_zones;
+#[cfg(feature = "zoneinfo")]
+mod zoneinfo;
 
-pub use self::rrule::{Frequency, NWeekday, RRule};
+pub use self::calendar_component::{Calendar, CalendarComponent, ComponentKind};
+pub use self::dst::{DstGap, DstOverlap};
+pub use self::occurrence::{Occurrence, OccurrenceResult};
+pub use self::period::{Period, PeriodEnd};
+pub use self::recurrence_override::{RecurrenceOverride, RecurrenceRange};
+pub use self::rrule::{Frequency, NWeekday, RRule, RScale, Skip};
 pub use self::rruleset::{RRuleResult, RRuleSet};
 pub(crate) use datetime::{
-    duration_from_midnight, get_day, get_hour, get_minute, get_month, get_second,
+    add_duration_clamped, get_day, get_hour, get_minute, get_month, get_second,
 };
+pub(crate) use dst::{resolved_datetimes, DstPolicy};
+#[cfg(feature = "jiff")]
+pub use jiff_interop::{ZonedIter, ZonedIterator};
+#[cfg(feature = "serde")]
+pub use structured::Structured;
+#[cfg(feature = "time")]
+pub use time_interop::{OffsetDateTimeIter, OffsetDateTimeIterator};
 pub use timezone::Tz;
+pub(crate) use timezone_provider::lookup_timezone;
+#[cfg(feature = "chrono-tz")]
+pub use timezone_provider::ChronoTzProvider;
+pub use timezone_provider::{set_timezone_provider, TimezoneProvider};
+pub(crate) use vtimezone::Observance;
+pub use vtimezone::VTimezone;
+#[cfg(feature = "windows-zones")]
+pub(crate) use windows_zones::windows_to_iana;
+#[cfg(feature = "zoneinfo")]
+pub use zoneinfo::ZoneinfoProvider;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 /// An empty struct to keep the validated stage
@@ -0,0 +1,67 @@
+use super::timezone::Tz;
+use chrono::DateTime;
+
+/// A single occurrence of an [`RRuleSet`](crate::RRuleSet), spanning from `start` to `end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Occurrence {
+    /// The start of the occurrence.
+    pub start: DateTime<Tz>,
+    /// The end of the occurrence, this is the same as `start` if the event has no duration.
+    pub end: DateTime<Tz>,
+    /// The start of the occurrence as generated by the rules, which identifies it (`RECURRENCE-ID`).
+    /// This differs from `start` if the occurrence was moved by a [`RecurrenceOverride`](crate::RecurrenceOverride).
+    pub recurrence_id: DateTime<Tz>,
+}
+
+impl Occurrence {
+    /// Checks if the occurrence intersects the half-open range `[start, end)`.
+    ///
+    /// An occurrence without duration intersects the range if its start is within it.
+    #[must_use]
+    pub fn intersects(&self, start: &DateTime<Tz>, end: &DateTime<Tz>) -> bool {
+        self.start < *end && (self.end > *start || self.start >= *start)
+    }
+}
+
+/// The return result of `RRuleSet::overlapping`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OccurrenceResult {
+    /// List of occurrences.
+    pub occurrences: Vec<Occurrence>,
+    /// It is being true if the list of occurrences is limited.
+    /// To indicate that it can potentially contain more occurrences.
+    pub limited: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const UTC: Tz = Tz::UTC;
+
+    #[test]
+    fn intersects_half_open_range() {
+        let start = UTC.with_ymd_and_hms(2021, 10, 1, 8, 0, 0).unwrap();
+        let end = UTC.with_ymd_and_hms(2021, 10, 1, 10, 0, 0).unwrap();
+        let occurrence = |start_hour, end_hour| Occurrence {
+            start: UTC.with_ymd_and_hms(2021, 10, 1, start_hour, 0, 0).unwrap(),
+            end: UTC.with_ymd_and_hms(2021, 10, 1, end_hour, 0, 0).unwrap(),
+            recurrence_id: UTC.with_ymd_and_hms(2021, 10, 1, start_hour, 0, 0).unwrap(),
+        };
+
+        // Starts before and ends within the range
+        assert!(occurrence(7, 9).intersects(&start, &end));
+        // Covers the whole range
+        assert!(occurrence(7, 11).intersects(&start, &end));
+        // Ends at the start of the range
+        assert!(!occurrence(7, 8).intersects(&start, &end));
+        // Starts at the end of the range
+        assert!(!occurrence(10, 11).intersects(&start, &end));
+        // No duration, at the start of the range
+        assert!(occurrence(8, 8).intersects(&start, &end));
+        // No duration, at the end of the range
+        assert!(!occurrence(10, 10).intersects(&start, &end));
+    }
+}
@@ -0,0 +1,176 @@
+use super::datetime::{add_duration_clamped, duration_to_ical_format};
+use super::timezone::Tz;
+use chrono::{DateTime, Duration};
+use std::fmt::Display;
+
+/// A span of time as defined by the `PERIOD` value type.
+///
+/// A period is either given by an explicit start and end, like `19970101T180000Z/19970102T070000Z`,
+/// or by a start and a duration, like `19970101T180000Z/PT5H30M`. The form is kept so that
+/// the period is printed the same way it was parsed.
+/// ref: <https://icalendar.org/iCalendar-RFC-5545/3-3-9-period-of-time.html>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Period {
+    start: DateTime<Tz>,
+    end: PeriodEnd,
+}
+
+/// How the end of a [`Period`] is specified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeriodEnd {
+    /// The period ends at an explicit datetime.
+    DateTime(DateTime<Tz>),
+    /// The period lasts for a duration after its start.
+    Duration(Duration),
+}
+
+impl Period {
+    /// Creates a period with an explicit start and end.
+    #[must_use]
+    pub fn new(start: DateTime<Tz>, end: DateTime<Tz>) -> Self {
+        Self {
+            start,
+            end: PeriodEnd::DateTime(end),
+        }
+    }
+
+    /// Creates a period with a start and a duration.
+    #[must_use]
+    pub fn with_duration(start: DateTime<Tz>, duration: Duration) -> Self {
+        Self {
+            start,
+            end: PeriodEnd::Duration(duration),
+        }
+    }
+
+    /// Returns the start of the period.
+    #[must_use]
+    pub fn get_start(&self) -> DateTime<Tz> {
+        self.start
+    }
+
+    /// Returns the end of the period, computed from the duration if needed.
+    /// An end after the latest datetime of `chrono` becomes that datetime.
+    #[must_use]
+    pub fn get_end(&self) -> DateTime<Tz> {
+        match self.end {
+            PeriodEnd::DateTime(end) => end,
+            PeriodEnd::Duration(duration) => add_duration_clamped(&self.start, duration),
+        }
+    }
+
+    /// Returns the duration of the period, computed from the end if needed.
+    #[must_use]
+    pub fn get_duration(&self) -> Duration {
+        match self.end {
+            PeriodEnd::DateTime(end) => end - self.start,
+            PeriodEnd::Duration(duration) => duration,
+        }
+    }
+
+    /// Returns how the end of the period was specified.
+    #[must_use]
+    pub fn get_period_end(&self) -> &PeriodEnd {
+        &self.end
+    }
+}
+
+/// Formats a datetime of a period, which has to be either in UTC or floating.
+fn period_datetime_to_ical_format(dt: &DateTime<Tz>) -> String {
+    if dt.timezone().is_local() {
+        dt.format("%Y%m%dT%H%M%S").to_string()
+    } else {
+        dt.with_timezone(&Tz::UTC)
+            .format("%Y%m%dT%H%M%SZ")
+            .to_string()
+    }
+}
+
+/// Formats the period for an `RDATE` with `format_datetime`, which puts the `TZID` of the start
+/// in front, like `;TZID=Europe/Berlin:19960403T040000/PT2H`. An explicit end is formatted in
+/// the timezone of the start, as both share the `TZID` of the property.
+pub(crate) fn period_to_ical_format(
+    period: &Period,
+    format_datetime: fn(&DateTime<Tz>) -> String,
+) -> String {
+    let start = format_datetime(&period.start);
+    let end = match period.end {
+        PeriodEnd::DateTime(end) => {
+            let end = format_datetime(&end.with_timezone(&period.start.timezone()));
+            end[end.rfind(':').map_or(0, |i| i + 1)..].to_string()
+        }
+        PeriodEnd::Duration(duration) => duration_to_ical_format(duration),
+    };
+    format!("{start}/{end}")
+}
+
+impl Display for Period {
+    /// Prints the period in the iCalendar `PERIOD` format, e.g. `19960403T020000Z/PT2H`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let start = period_datetime_to_ical_format(&self.start);
+        let end = match self.end {
+            PeriodEnd::DateTime(end) => period_datetime_to_ical_format(&end),
+            PeriodEnd::Duration(duration) => duration_to_ical_format(duration),
+        };
+        write!(f, "{start}/{end}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::core::datetime::datetime_to_ical_format;
+
+    #[test]
+    fn computes_end_and_duration() {
+        let start = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap();
+        let end = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap();
+
+        let period = Period::new(start, end);
+        assert_eq!(period.get_duration(), Duration::hours(2));
+        assert_eq!(period.get_end(), end);
+
+        let period = Period::with_duration(start, Duration::hours(2));
+        assert_eq!(period.get_duration(), Duration::hours(2));
+        assert_eq!(period.get_end(), end);
+    }
+
+    #[test]
+    fn formats_period() {
+        let start = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap();
+        let end = Tz::Europe__Berlin
+            .with_ymd_and_hms(1996, 4, 3, 6, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            Period::new(start, end).to_string(),
+            "19960403T020000Z/19960403T040000Z"
+        );
+        assert_eq!(
+            Period::with_duration(start, Duration::minutes(90)).to_string(),
+            "19960403T020000Z/PT1H30M"
+        );
+    }
+
+    #[test]
+    fn formats_period_with_tzid() {
+        let start = Tz::Europe__Berlin
+            .with_ymd_and_hms(1996, 4, 3, 4, 0, 0)
+            .unwrap();
+        let end = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap();
+
+        assert_eq!(
+            period_to_ical_format(&Period::new(start, end), datetime_to_ical_format),
+            ";TZID=Europe/Berlin:19960403T040000/19960403T060000"
+        );
+        assert_eq!(
+            period_to_ical_format(
+                &Period::with_duration(end, Duration::hours(2)),
+                datetime_to_ical_format
+            ),
+            ":19960403T040000Z/PT2H"
+        );
+    }
+}
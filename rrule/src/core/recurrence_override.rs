@@ -0,0 +1,105 @@
+use super::timezone::Tz;
+use chrono::{DateTime, Duration};
+
+/// The range of occurrences a [`RecurrenceOverride`] applies to, as given by the `RANGE`
+/// parameter of a `RECURRENCE-ID`.
+/// ref: <https://icalendar.org/iCalendar-RFC-5545/3-2-13-recurrence-identifier-range.html>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RecurrenceRange {
+    /// Only the overridden occurrence is changed.
+    #[default]
+    ThisInstance,
+    /// The overridden occurrence and all following occurrences are changed (`RANGE=THISANDFUTURE`).
+    ThisAndFuture,
+}
+
+/// Replaces an occurrence of an [`RRuleSet`](crate::RRuleSet), which is identified by its
+/// original start (the `RECURRENCE-ID`).
+///
+/// The occurrence is either moved to a new start or cancelled. A moved occurrence can have a
+/// duration of its own, which replaces the duration of the occurrence it overrides.
+/// With [`RecurrenceRange::ThisAndFuture`], all following occurrences are shifted by the
+/// same amount of time, or cancelled as well.
+///
+/// # Usage
+///
+/// ```
+/// use chrono::TimeZone;
+/// use rrule::{RRuleSet, RecurrenceOverride, Tz};
+///
+/// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;COUNT=3".parse().unwrap();
+///
+/// let rrule_set = rrule_set.override_occurrence(
+///     Tz::UTC.with_ymd_and_hms(2021, 1, 2, 9, 0, 0).unwrap(),
+///     RecurrenceOverride::moved(Tz::UTC.with_ymd_and_hms(2021, 1, 2, 14, 0, 0).unwrap()),
+/// );
+/// let dates = rrule_set.all(10).dates;
+/// assert_eq!(dates[1].to_rfc3339(), "2021-01-02T14:00:00+00:00");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecurrenceOverride {
+    start: Option<DateTime<Tz>>,
+    duration: Option<Duration>,
+    range: RecurrenceRange,
+}
+
+impl RecurrenceOverride {
+    /// Moves the occurrence to a new start.
+    #[must_use]
+    pub fn moved(start: DateTime<Tz>) -> Self {
+        Self {
+            start: Some(start),
+            duration: None,
+            range: RecurrenceRange::ThisInstance,
+        }
+    }
+
+    /// Cancels the occurrence.
+    #[must_use]
+    pub fn cancelled() -> Self {
+        Self {
+            start: None,
+            duration: None,
+            range: RecurrenceRange::ThisInstance,
+        }
+    }
+
+    /// Sets the duration of the moved occurrence, like the `DTEND` or `DURATION` of an
+    /// overriding `VEVENT`. This has no effect on a cancelled occurrence.
+    #[must_use]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets the range of occurrences this override applies to.
+    #[must_use]
+    pub fn range(mut self, range: RecurrenceRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Returns the new start of the occurrence, or `None` if it is cancelled.
+    #[must_use]
+    pub fn get_start(&self) -> Option<&DateTime<Tz>> {
+        self.start.as_ref()
+    }
+
+    /// Returns the duration of the moved occurrence, or `None` if it keeps its duration.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<&Duration> {
+        self.duration.as_ref()
+    }
+
+    /// Returns the range of occurrences this override applies to.
+    #[must_use]
+    pub fn get_range(&self) -> RecurrenceRange {
+        self.range
+    }
+
+    /// Returns `true` if the occurrence is cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.start.is_none()
+    }
+}
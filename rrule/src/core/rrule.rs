@@ -1,17 +1,21 @@
+use crate::calendar;
 use crate::core::get_day;
 use crate::core::get_hour;
 use crate::core::get_minute;
 use crate::core::get_month;
 use crate::core::get_second;
+use crate::core::DstPolicy;
 use crate::iter::RRuleIter;
 use crate::parser::str_to_weekday;
 use crate::parser::ContentLineCaptures;
 use crate::parser::ParseError;
+use crate::text::{self, English, Locale};
 use crate::validator::validate_rrule;
 use crate::validator::ValidationError;
 use crate::Tz;
 use crate::{RRuleError, RRuleSet, Unvalidated, Validated};
 use chrono::DateTime;
+use chrono::Utc;
 use chrono::{Datelike, Month, Weekday};
 #[cfg(feature = "serde")]
 use serde_with::{serde_as, DeserializeFromStr, SerializeDisplay};
@@ -74,6 +78,90 @@ impl FromStr for Frequency {
     }
 }
 
+/// The calendar system a recurrence is expanded in, as defined by the `RSCALE` rule part
+/// of [RFC 7529](https://datatracker.ietf.org/doc/html/rfc7529#section-3.1).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(DeserializeFromStr, SerializeDisplay))]
+pub enum RScale {
+    /// The Gregorian calendar, which is also used when no `RSCALE` is set.
+    #[default]
+    Gregorian,
+    /// The Hebrew calendar. The months are counted from Tishri, and Adar I of leap
+    /// years is the leap month `5L`.
+    Hebrew,
+    /// The Chinese calendar, as observed in Beijing. Any month can be followed by a leap
+    /// month, e.g. `6L`.
+    Chinese,
+}
+
+impl Display for RScale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Gregorian => "GREGORIAN",
+            Self::Hebrew => "HEBREW",
+            Self::Chinese => "CHINESE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RScale {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let rscale = match &value.to_uppercase()[..] {
+            "GREGORIAN" => Self::Gregorian,
+            "HEBREW" => Self::Hebrew,
+            "CHINESE" => Self::Chinese,
+            val => return Err(ParseError::InvalidRScale(val.to_string())),
+        };
+        Ok(rscale)
+    }
+}
+
+/// How invalid dates generated by a recurrence are handled, as defined by the `SKIP` rule
+/// part of [RFC 7529](https://datatracker.ietf.org/doc/html/rfc7529#section-3.2).
+///
+/// For example, `BYMONTHDAY=31` generates an invalid date in February.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(DeserializeFromStr, SerializeDisplay))]
+pub enum Skip {
+    /// Invalid dates are left out, which is the behavior of RFC 5545.
+    #[default]
+    Omit,
+    /// Invalid dates are moved back to the last valid day of the month,
+    /// e.g. February 31st becomes February 28th or 29th.
+    Backward,
+    /// Invalid dates are moved forward to the first valid day after them,
+    /// e.g. February 31st becomes March 1st.
+    Forward,
+}
+
+impl Display for Skip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Omit => "OMIT",
+            Self::Backward => "BACKWARD",
+            Self::Forward => "FORWARD",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Skip {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let skip = match &value.to_uppercase()[..] {
+            "OMIT" => Self::Omit,
+            "BACKWARD" => Self::Backward,
+            "FORWARD" => Self::Forward,
+            val => return Err(ParseError::InvalidSkip(val.to_string())),
+        };
+        Ok(skip)
+    }
+}
+
 /// This indicates the nth occurrence of a specific day within a MONTHLY or YEARLY RRULE.
 ///
 /// For example, `NWeekday::Nth(1, MO)` represents the first Monday within the month or year,
@@ -243,6 +331,10 @@ pub struct RRule<Stage = Validated> {
     /// The months to apply the recurrence to.
     /// Can be a value from 1 to 12.
     pub(crate) by_month: Vec<u8>,
+    /// The leap months to apply the recurrence to, written with a `L` suffix in `BYMONTH`,
+    /// e.g. `5L` is the leap month which follows month 5.
+    /// Can be a value from 1 to 12, and is only valid with a `RSCALE` which has leap months.
+    pub(crate) by_leap_month: Vec<u8>,
     /// The month days to apply the recurrence to.
     /// Can be a value from -31 to -1 and 1 to 31.
     pub(crate) by_month_day: Vec<i8>,
@@ -286,6 +378,12 @@ pub struct RRule<Stage = Validated> {
     /// This includes both DATE values and DATE-TIME values without timezone specification.
     /// This is used to properly handle LOCAL-TZID conversion for floating datetimes.
     pub(crate) dtstart_is_floating: bool,
+    /// The calendar system of the recurrence, see RFC 7529.
+    /// Controlled via the RSCALE parameter.
+    pub(crate) rscale: Option<RScale>,
+    /// How invalid dates, like February 30th, are handled, see RFC 7529.
+    /// Controlled via the SKIP parameter.
+    pub(crate) skip: Skip,
     /// A phantom data to have the stage (unvalidated or validated).
     #[cfg_attr(feature = "serde", serde_as(as = "ignore"))]
     pub(crate) stage: PhantomData<Stage>,
@@ -302,6 +400,7 @@ impl Default for RRule<Unvalidated> {
             week_start: Weekday::Mon,
             by_set_pos: Vec::new(),
             by_month: Vec::new(),
+            by_leap_month: Vec::new(),
             by_month_day: Vec::new(),
             by_n_month_day: Vec::new(),
             by_year_day: Vec::new(),
@@ -314,6 +413,8 @@ impl Default for RRule<Unvalidated> {
             include_dtstart: None,
             local_tzid: None,
             dtstart_is_floating: false,
+            rscale: None,
+            skip: Skip::Omit,
             stage: PhantomData,
         }
     }
@@ -389,6 +490,14 @@ impl RRule<Unvalidated> {
         self
     }
 
+    /// The leap months to apply the recurrence to, e.g. `5` for the leap month `5L`
+    /// which follows month 5. This requires a [`RScale`] which has leap months.
+    #[must_use]
+    pub fn by_leap_month(mut self, by_leap_month: Vec<u8>) -> Self {
+        self.by_leap_month = by_leap_month;
+        self
+    }
+
     /// If given, it must be either an integer, or a sequence of integers, meaning
     /// the month days to apply the recurrence to.
     #[must_use]
@@ -466,6 +575,21 @@ impl RRule<Unvalidated> {
         self
     }
 
+    /// Sets the calendar system of the recurrence.
+    #[must_use]
+    pub fn rscale(mut self, rscale: RScale) -> Self {
+        self.rscale = Some(rscale);
+        self
+    }
+
+    /// Sets how invalid dates, like February 30th, are handled.
+    /// This requires [`RRule::rscale`] to be set as well.
+    #[must_use]
+    pub fn skip(mut self, skip: Skip) -> Self {
+        self.skip = skip;
+        self
+    }
+
     /// Fills in some additional fields in order to make iter work correctly.
     pub(crate) fn finalize_parsed_rrule(mut self, dt_start: &DateTime<Tz>) -> Self {
         // TEMP: move negative months to another list
@@ -496,17 +620,32 @@ impl RRule<Unvalidated> {
             || !self.by_weekday.is_empty()
             || by_easter_is_some)
         {
+            // The month and day of `dt_start` in the calendar of the rule.
+            let (month, leap_month, day) = match self.rscale {
+                Some(rscale) if rscale != RScale::Gregorian => {
+                    let date = dt_start.date_naive();
+                    let calendar_month = calendar::month_of(rscale, date);
+                    let day = calendar_month.month_day(date.num_days_from_ce());
+                    (
+                        calendar_month.month,
+                        calendar_month.leap,
+                        i8::try_from(day).expect("a month has at most 31 days"),
+                    )
+                }
+                _ => (get_month(dt_start), false, get_day(dt_start)),
+            };
             match self.freq {
                 Frequency::Yearly => {
-                    if self.by_month.is_empty() {
-                        let month = get_month(dt_start);
-                        self.by_month = vec![month];
+                    if self.by_month.is_empty() && self.by_leap_month.is_empty() {
+                        if leap_month {
+                            self.by_leap_month = vec![month];
+                        } else {
+                            self.by_month = vec![month];
+                        }
                     }
-                    let day = get_day(dt_start);
                     self.by_month_day = vec![day];
                 }
                 Frequency::Monthly => {
-                    let day = get_day(dt_start);
                     self.by_month_day = vec![day];
                 }
                 Frequency::Weekly => {
@@ -547,6 +686,9 @@ impl RRule<Unvalidated> {
         self.by_month.sort_unstable();
         self.by_month.dedup();
 
+        self.by_leap_month.sort_unstable();
+        self.by_leap_month.dedup();
+
         self.by_month_day.sort_unstable();
         self.by_month_day.dedup();
 
@@ -610,6 +752,7 @@ impl RRule<Unvalidated> {
             week_start: rrule.week_start,
             by_set_pos: rrule.by_set_pos,
             by_month: rrule.by_month,
+            by_leap_month: rrule.by_leap_month,
             by_month_day: rrule.by_month_day,
             by_n_month_day: rrule.by_n_month_day,
             by_year_day: rrule.by_year_day,
@@ -622,6 +765,8 @@ impl RRule<Unvalidated> {
             include_dtstart: rrule.include_dtstart,
             local_tzid: rrule.local_tzid,
             dtstart_is_floating: rrule.dtstart_is_floating,
+            rscale: rrule.rscale,
+            skip: rrule.skip,
             stage: PhantomData,
         })
     }
@@ -636,11 +781,93 @@ impl RRule<Unvalidated> {
         let rrule_set = RRuleSet::new(dt_start).rrule(rrule);
         Ok(rrule_set)
     }
+
+    /// Parses an English description of a recurrence, e.g. "every other Tuesday at 9am until June".
+    ///
+    /// Everything returned by [`RRule::to_text`] can be parsed back. A date in `until` includes
+    /// the whole day, or the whole month if only the month is given. Dates without a year are
+    /// the first ones from today on, see [`RRule::from_text_relative_to`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRule;
+    ///
+    /// let rrule = RRule::from_text("every other Tuesday at 9am for 5 times").unwrap();
+    /// assert_eq!(
+    ///     rrule.to_string(),
+    ///     "FREQ=WEEKLY;COUNT=5;INTERVAL=2;BYHOUR=9;BYMINUTE=0;BYSECOND=0;BYDAY=TU"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError::ParserError`] if a phrase isn't understood or not supported.
+    pub fn from_text(text: &str) -> Result<Self, RRuleError> {
+        Self::from_text_relative_to(text, Utc::now().with_timezone(&Tz::UTC))
+    }
+
+    /// Parses an English description of a recurrence like [`RRule::from_text`], dates without
+    /// a year are the first ones on or after `reference`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError::ParserError`] if a phrase isn't understood or not supported.
+    pub fn from_text_relative_to(text: &str, reference: DateTime<Tz>) -> Result<Self, RRuleError> {
+        text::rrule_from_text(text, reference).map_err(From::from)
+    }
 }
 
 impl RRule {
-    pub(crate) fn iter_with_ctx(&self, dt_start: DateTime<Tz>, limited: bool) -> RRuleIter {
-        RRuleIter::new(self, &dt_start, limited)
+    pub(crate) fn iter_with_ctx(
+        &self,
+        dt_start: DateTime<Tz>,
+        limited: bool,
+        dst: DstPolicy,
+    ) -> RRuleIter {
+        RRuleIter::new(self, &dt_start, limited, dst)
+    }
+
+    /// Returns the first recurrence of the rule starting at `dt_start` after `dt`,
+    /// or at `dt` if `inclusive` is set.
+    ///
+    /// See [`RRuleSet::next_after`].
+    #[must_use]
+    pub fn next_after(
+        &self,
+        dt_start: DateTime<Tz>,
+        dt: DateTime<Tz>,
+        inclusive: bool,
+    ) -> Option<DateTime<Tz>> {
+        RRuleSet::new(dt_start)
+            .rrule(self.clone())
+            .next_after(dt, inclusive)
+    }
+
+    /// Returns the last recurrence of the rule starting at `dt_start` before `dt`,
+    /// or at `dt` if `inclusive` is set.
+    ///
+    /// See [`RRuleSet::prev_before`].
+    #[must_use]
+    pub fn prev_before(
+        &self,
+        dt_start: DateTime<Tz>,
+        dt: DateTime<Tz>,
+        inclusive: bool,
+    ) -> Option<DateTime<Tz>> {
+        RRuleSet::new(dt_start)
+            .rrule(self.clone())
+            .prev_before(dt, inclusive)
+    }
+
+    /// Checks if the rule starting at `dt_start` has a recurrence at exactly `dt`.
+    ///
+    /// See [`RRuleSet::is_occurrence`].
+    #[must_use]
+    pub fn is_occurrence(&self, dt_start: DateTime<Tz>, dt: DateTime<Tz>) -> bool {
+        RRuleSet::new(dt_start)
+            .rrule(self.clone())
+            .is_occurrence(dt)
     }
 }
 
@@ -662,8 +889,17 @@ impl<S> Display for RRule<S> {
     #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut res = Vec::with_capacity(15);
+        if let Some(rscale) = &self.rscale {
+            res.push(format!("RSCALE={}", rscale));
+        }
+
         res.push(format!("FREQ={}", &self.freq));
 
+        // Omit is the default, no need to expose it.
+        if self.skip != Skip::Omit {
+            res.push(format!("SKIP={}", &self.skip));
+        }
+
         if let Some(until) = &self.until {
             let maybe_zulu = if until.timezone().is_local() { "" } else { "Z" };
             res.push(format!(
@@ -698,12 +934,19 @@ impl<S> Display for RRule<S> {
             ));
         }
 
-        if !self.by_month.is_empty() {
+        if !self.by_month.is_empty() || !self.by_leap_month.is_empty() {
+            let mut by_month = self
+                .by_month
+                .iter()
+                .map(|month| (*month, ""))
+                .chain(self.by_leap_month.iter().map(|month| (*month, "L")))
+                .collect::<Vec<_>>();
+            by_month.sort_unstable();
             res.push(format!(
                 "BYMONTH={}",
-                self.by_month
+                by_month
                     .iter()
-                    .map(ToString::to_string)
+                    .map(|(month, leap)| format!("{month}{leap}"))
                     .collect::<Vec<_>>()
                     .join(",")
             ));
@@ -849,6 +1092,12 @@ impl<S> RRule<S> {
         &self.by_month
     }
 
+    /// Get the leap months of the recurrence, e.g. `5` for `BYMONTH=5L`.
+    #[must_use]
+    pub fn get_by_leap_month(&self) -> &[u8] {
+        &self.by_leap_month
+    }
+
     /// Get the `by_month_day` of the recurrence.
     #[must_use]
     pub fn get_by_month_day(&self) -> &[i8] {
@@ -903,4 +1152,56 @@ impl<S> RRule<S> {
     pub fn get_include_dtstart(&self) -> Option<&bool> {
         self.include_dtstart.as_ref()
     }
+
+    /// Get the calendar system of the recurrence.
+    #[must_use]
+    pub fn get_rscale(&self) -> Option<RScale> {
+        self.rscale
+    }
+
+    /// Get how invalid dates of the recurrence are handled.
+    #[must_use]
+    pub fn get_skip(&self) -> Skip {
+        self.skip
+    }
+
+    /// Describes the recurrence in English, e.g. "every 2 weeks on Monday and Friday".
+    ///
+    /// The parts which can't be described are left out, they are returned by
+    /// [`RRule::unsupported_text_parts`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{RRule, Unvalidated};
+    ///
+    /// let rrule: RRule<Unvalidated> = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z".parse().unwrap();
+    /// assert_eq!(rrule.to_text(), "every 2 weeks on Monday and Friday until March 3, 2025");
+    /// ```
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_text_with(&English)
+    }
+
+    /// Describes the recurrence with the words of `locale`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{English, RRule, Unvalidated};
+    ///
+    /// let rrule: RRule<Unvalidated> = "FREQ=MONTHLY;BYDAY=-1FR".parse().unwrap();
+    /// assert_eq!(rrule.to_text_with(&English), "every month on the last Friday");
+    /// ```
+    #[must_use]
+    pub fn to_text_with(&self, locale: &dyn Locale) -> String {
+        text::rrule_to_text(self, locale)
+    }
+
+    /// Returns the parts of the recurrence which can't be described by [`RRule::to_text`],
+    /// in the iCalendar format, e.g. `BYSETPOS=-1`.
+    #[must_use]
+    pub fn unsupported_text_parts(&self) -> Vec<String> {
+        text::unsupported_parts(self)
+    }
 }
@@ -1,10 +1,15 @@
-use crate::core::datetime::datetime_to_ical_format;
-use crate::core::utils::collect_with_error;
-use crate::parser::{ContentLine, Grammar};
-use crate::{ParseError, RRule, RRuleError, Tz};
-use chrono::DateTime;
+use crate::core::datetime::{datetime_to_ical_format, duration_to_ical_format};
+use crate::core::period::period_to_ical_format;
+use crate::core::utils::{collect_overlapping, collect_with_error};
+use crate::core::{DstGap, DstOverlap, DstPolicy, OccurrenceResult};
+use crate::iter::{OccurrenceIter, RRuleSetIter, RRuleSetRevIter};
+use crate::parser::{ContentLine, DateValues, Grammar};
+use crate::text::{self, English, Locale};
+use crate::{ParseError, Period, RRule, RRuleError, RecurrenceOverride, Tz, ValidationError};
+use chrono::{DateTime, Duration, NaiveDate};
 #[cfg(feature = "serde")]
 use serde_with::{serde_as, DeserializeFromStr, SerializeDisplay};
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -17,18 +22,35 @@ pub struct RRuleSet {
     pub(crate) rrule: Vec<RRule>,
     /// List of rdates.
     pub(crate) rdate: Vec<DateTime<Tz>>,
+    /// List of rdates with `VALUE=DATE`.
+    /// They occur at the start of the day in the timezone of `dt_start`.
+    pub(crate) rdate_date: Vec<NaiveDate>,
+    /// List of rdates with `VALUE=PERIOD`.
+    /// They occur at the start of the period.
+    pub(crate) rdate_period: Vec<Period>,
     /// List of exules.
     pub(crate) exrule: Vec<RRule>,
     /// List of exdates.
     pub(crate) exdate: Vec<DateTime<Tz>>,
+    /// List of exdates with `VALUE=DATE`.
+    /// They exclude every occurrence on that calendar day, in the timezone of the occurrence.
+    pub(crate) exdate_date: Vec<NaiveDate>,
+    /// Overrides of single occurrences, keyed by their original start (`RECURRENCE-ID`).
+    pub(crate) overrides: BTreeMap<DateTime<Tz>, RecurrenceOverride>,
     /// The start datetime of the recurring event.
     pub(crate) dt_start: DateTime<Tz>,
+    /// The end datetime of the first occurrence, can't be set together with `duration`.
+    pub(crate) dt_end: Option<DateTime<Tz>>,
+    /// The duration of every occurrence, can't be set together with `dt_end`.
+    pub(crate) duration: Option<Duration>,
     /// If set, all returned recurrences must be before this date.
     pub(crate) before: Option<DateTime<Tz>>,
     /// If set, all returned recurrences must be after this date.
     pub(crate) after: Option<DateTime<Tz>>,
     /// If validation limits are enabled
     pub(crate) limited: bool,
+    /// How local times in daylight saving time transitions are resolved.
+    pub(crate) dst: DstPolicy,
 }
 
 /// The return result of `RRuleSet::all`.
@@ -47,13 +69,20 @@ impl RRuleSet {
     pub fn new(dt_start: DateTime<Tz>) -> Self {
         Self {
             dt_start,
+            dt_end: None,
+            duration: None,
             rrule: vec![],
             rdate: vec![],
+            rdate_date: vec![],
+            rdate_period: vec![],
             exrule: vec![],
             exdate: vec![],
+            exdate_date: vec![],
+            overrides: BTreeMap::new(),
             before: None,
             after: None,
             limited: false,
+            dst: DstPolicy::default(),
         }
     }
 
@@ -84,6 +113,60 @@ impl RRuleSet {
         self
     }
 
+    /// Sets the end datetime of the first occurrence (`DTEND`).
+    ///
+    /// Every occurrence lasts as long as the time between `dt_start` and `dt_end`.
+    /// This replaces a duration set with [`RRuleSet::duration`].
+    #[must_use]
+    pub fn dt_end(mut self, dt_end: DateTime<Tz>) -> Self {
+        self.dt_end = Some(dt_end);
+        self.duration = None;
+        self
+    }
+
+    /// Sets the duration of every occurrence (`DURATION`).
+    ///
+    /// This replaces an end datetime set with [`RRuleSet::dt_end`].
+    #[must_use]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self.dt_end = None;
+        self
+    }
+
+    /// Sets how an occurrence is resolved when its local time falls in the gap where the
+    /// clocks are set forward for daylight saving time. Defaults to [`DstGap::ShiftForward`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{DstGap, RRuleSet};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20210327T023000\n\
+    ///     RRULE:FREQ=DAILY;COUNT=3"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let dates = rrule_set.clone().all(10).dates;
+    /// assert_eq!(dates[1].to_rfc3339(), "2021-03-28T03:30:00+02:00");
+    ///
+    /// let dates = rrule_set.dst_gap(DstGap::Skip).all(10).dates;
+    /// assert_eq!(dates[1].to_rfc3339(), "2021-03-29T02:30:00+02:00");
+    /// ```
+    #[must_use]
+    pub fn dst_gap(mut self, gap: DstGap) -> Self {
+        self.dst.gap = gap;
+        self
+    }
+
+    /// Sets how an occurrence is resolved when its local time exists twice, because the clocks
+    /// are set back at the end of daylight saving time. Defaults to [`DstOverlap::Earliest`].
+    #[must_use]
+    pub fn dst_overlap(mut self, overlap: DstOverlap) -> Self {
+        self.dst.overlap = overlap;
+        self
+    }
+
     /// Adds a new rrule to the set.
     #[must_use]
     pub fn rrule(mut self, rrule: RRule) -> Self {
@@ -113,6 +196,51 @@ impl RRuleSet {
         self
     }
 
+    /// Adds a new date-valued rdate (`RDATE;VALUE=DATE`) to the set.
+    ///
+    /// The occurrence is generated at the start of the day in the timezone of `dt_start`.
+    #[must_use]
+    pub fn rdate_date(mut self, rdate: NaiveDate) -> Self {
+        self.rdate_date.push(rdate);
+        self
+    }
+
+    /// Adds a new period-valued rdate (`RDATE;VALUE=PERIOD`) to the set.
+    ///
+    /// The occurrence is generated at the start of the period, use
+    /// [`RRuleSetIter::next_with_period`](crate::RRuleSetIter::next_with_period) to get the period as well.
+    #[must_use]
+    pub fn rdate_period(mut self, rdate: Period) -> Self {
+        self.rdate_period.push(rdate);
+        self
+    }
+
+    /// Adds a new date-valued exdate (`EXDATE;VALUE=DATE`) to the set.
+    ///
+    /// All occurrences which fall on this calendar day, in their own timezone, are excluded.
+    #[must_use]
+    pub fn exdate_date(mut self, exdate: NaiveDate) -> Self {
+        self.exdate_date.push(exdate);
+        self
+    }
+
+    /// Overrides the occurrence which originally starts at `recurrence_id`.
+    ///
+    /// The occurrence can be moved or cancelled, see [`RecurrenceOverride`].
+    /// An override for a `recurrence_id` which is not an occurrence of the set has no effect,
+    /// not even on the following occurrences with `RANGE=THISANDFUTURE`.
+    /// Overrides are not part of the iCalendar string created by `to_string`, because
+    /// they are specified in separate components with a `RECURRENCE-ID` property.
+    #[must_use]
+    pub fn override_occurrence(
+        mut self,
+        recurrence_id: DateTime<Tz>,
+        recurrence_override: RecurrenceOverride,
+    ) -> Self {
+        self.overrides.insert(recurrence_id, recurrence_override);
+        self
+    }
+
     /// Sets the rrules of the set.
     #[must_use]
     pub fn set_rrules(mut self, rrules: Vec<RRule>) -> Self {
@@ -142,6 +270,27 @@ impl RRuleSet {
         self
     }
 
+    /// Sets the date-valued rdates of the set.
+    #[must_use]
+    pub fn set_rdate_dates(mut self, rdates: Vec<NaiveDate>) -> Self {
+        self.rdate_date = rdates;
+        self
+    }
+
+    /// Sets the period-valued rdates of the set.
+    #[must_use]
+    pub fn set_rdate_periods(mut self, rdates: Vec<Period>) -> Self {
+        self.rdate_period = rdates;
+        self
+    }
+
+    /// Sets the date-valued exdates of the set.
+    #[must_use]
+    pub fn set_exdate_dates(mut self, exdates: Vec<NaiveDate>) -> Self {
+        self.exdate_date = exdates;
+        self
+    }
+
     /// Returns the rrules of the set.
     #[must_use]
     pub fn get_rrule(&self) -> &Vec<RRule> {
@@ -166,12 +315,123 @@ impl RRuleSet {
         &self.exdate
     }
 
+    /// Returns the date-valued rdates of the set.
+    #[must_use]
+    pub fn get_rdate_date(&self) -> &Vec<NaiveDate> {
+        &self.rdate_date
+    }
+
+    /// Returns the period-valued rdates of the set.
+    #[must_use]
+    pub fn get_rdate_period(&self) -> &Vec<Period> {
+        &self.rdate_period
+    }
+
+    /// Returns the date-valued exdates of the set.
+    #[must_use]
+    pub fn get_exdate_date(&self) -> &Vec<NaiveDate> {
+        &self.exdate_date
+    }
+
+    /// Returns the overrides of the set, keyed by the original start of the occurrence.
+    #[must_use]
+    pub fn get_overrides(&self) -> &BTreeMap<DateTime<Tz>, RecurrenceOverride> {
+        &self.overrides
+    }
+
     /// Returns the start datetime of the recurring event.
     #[must_use]
     pub fn get_dt_start(&self) -> &DateTime<Tz> {
         &self.dt_start
     }
 
+    /// Returns the end datetime of the first occurrence, if set with `DTEND`.
+    #[must_use]
+    pub fn get_dt_end(&self) -> Option<&DateTime<Tz>> {
+        self.dt_end.as_ref()
+    }
+
+    /// Returns the duration of every occurrence, if set with `DURATION`.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<&Duration> {
+        self.duration.as_ref()
+    }
+
+    /// Returns how local times in the gap of a daylight saving time transition are resolved.
+    #[must_use]
+    pub fn get_dst_gap(&self) -> DstGap {
+        self.dst.gap
+    }
+
+    /// Returns how local times in the overlap of a daylight saving time transition are resolved.
+    #[must_use]
+    pub fn get_dst_overlap(&self) -> DstOverlap {
+        self.dst.overlap
+    }
+
+    /// Returns the duration of the occurrences, computed from `DTEND` or `DURATION`.
+    /// If neither is set, the occurrences have no duration.
+    pub(crate) fn get_occurrence_duration(&self) -> Duration {
+        match (self.duration, self.dt_end) {
+            (Some(duration), _) => duration,
+            (None, Some(dt_end)) => dt_end - self.dt_start,
+            (None, None) => Duration::zero(),
+        }
+    }
+
+    /// Returns an iterator over the occurrences of the set, with their start and end.
+    ///
+    /// The end is computed from `DTEND` or `DURATION`, or taken from the period
+    /// for occurrences which come from an `RDATE;VALUE=PERIOD`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nDURATION:PT1H\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let occurrence = rrule_set.occurrences().next().unwrap();
+    /// assert_eq!(occurrence.end.to_rfc3339(), "2021-01-01T10:00:00+00:00");
+    /// ```
+    #[must_use]
+    pub fn occurrences(&self) -> OccurrenceIter {
+        OccurrenceIter::new(self.into_iter(), self.get_occurrence_duration())
+    }
+
+    /// Returns an iterator over the recurrences of the set in reverse order, starting with the
+    /// last one.
+    ///
+    /// The iteration starts at [`RRuleSet::before`] if it is set, and stops at
+    /// [`RRuleSet::after`] if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `before` isn't set and a rule has neither `UNTIL` nor `COUNT`,
+    /// since the iteration can't start without a last recurrence.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// // The last 2 recurrences before the 10th of January
+    /// let dates = rrule_set
+    ///     .before(Tz::UTC.with_ymd_and_hms(2021, 1, 10, 0, 0, 0).unwrap())
+    ///     .iter_rev()
+    ///     .unwrap()
+    ///     .take(2)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(dates[0].to_rfc3339(), "2021-01-09T09:00:00+00:00");
+    /// assert_eq!(dates[1].to_rfc3339(), "2021-01-08T09:00:00+00:00");
+    /// ```
+    pub fn iter_rev(&self) -> Result<RRuleSetRevIter, RRuleError> {
+        RRuleSetRevIter::new(self)
+    }
+
     /// Returns all the recurrences of the rrule.
     ///
     /// Limit must be set in order to prevent infinite loops.
@@ -193,7 +453,7 @@ impl RRuleSet {
     pub fn all(mut self, limit: u16) -> RRuleResult {
         self.limited = true;
         collect_with_error(
-            self.into_iter(),
+            self.iter_from_after(),
             &self.after,
             &self.before,
             true,
@@ -209,7 +469,183 @@ impl RRuleSet {
     /// very long iteration times. Please read the `SECURITY.md` for more information.
     #[must_use]
     pub fn all_unchecked(self) -> Vec<DateTime<Tz>> {
-        collect_with_error(self.into_iter(), &self.after, &self.before, true, None).dates
+        collect_with_error(
+            self.iter_from_after(),
+            &self.after,
+            &self.before,
+            true,
+            None,
+        )
+        .dates
+    }
+
+    /// Returns an iterator over the recurrences of the set, starting at `dt` (inclusive).
+    ///
+    /// Instead of iterating from `dt_start`, rules without `COUNT` jump straight to the
+    /// period which contains `dt`. Rules with `COUNT` still have to count all the
+    /// recurrences before `dt`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:19900101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let from = Tz::UTC.with_ymd_and_hms(2030, 1, 1, 12, 0, 0).unwrap();
+    /// let next = rrule_set.iter_from(from).next().unwrap();
+    /// assert_eq!(next.to_rfc3339(), "2030-01-02T09:00:00+00:00");
+    /// ```
+    #[must_use]
+    pub fn iter_from(&self, dt: DateTime<Tz>) -> RRuleSetIter {
+        let mut iter = self.into_iter();
+        iter.seek(dt);
+        iter
+    }
+
+    /// Returns the first recurrence after `dt`, or at `dt` if `inclusive` is set.
+    ///
+    /// Rules without `COUNT` jump straight to `dt`, see [`RRuleSet::iter_from`].
+    /// The [`RRuleSet::after`] and [`RRuleSet::before`] filters are not applied.
+    /// Returns `None` if there is no such recurrence, or if it isn't found within the
+    /// validation limits.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let dt = Tz::UTC.with_ymd_and_hms(2021, 1, 5, 9, 0, 0).unwrap();
+    /// assert_eq!(rrule_set.next_after(dt, true), Some(dt));
+    /// assert_eq!(
+    ///     rrule_set.next_after(dt, false),
+    ///     Some(Tz::UTC.with_ymd_and_hms(2021, 1, 6, 9, 0, 0).unwrap())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn next_after(&self, dt: DateTime<Tz>, inclusive: bool) -> Option<DateTime<Tz>> {
+        let mut rrule_set = self.clone();
+        rrule_set.limited = true;
+        rrule_set.iter_from(dt).find(|date| inclusive || *date > dt)
+    }
+
+    /// Returns the last recurrence before `dt`, or at `dt` if `inclusive` is set.
+    ///
+    /// The rules are iterated in reverse from `dt`, see [`RRuleSet::iter_rev`].
+    /// The [`RRuleSet::after`] and [`RRuleSet::before`] filters are not applied.
+    /// Returns `None` if there is no such recurrence, or if it isn't found within the
+    /// validation limits.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let dt = Tz::UTC.with_ymd_and_hms(2021, 1, 5, 12, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     rrule_set.prev_before(dt, false),
+    ///     Some(Tz::UTC.with_ymd_and_hms(2021, 1, 5, 9, 0, 0).unwrap())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn prev_before(&self, dt: DateTime<Tz>, inclusive: bool) -> Option<DateTime<Tz>> {
+        let mut rrule_set = self.clone();
+        rrule_set.limited = true;
+        rrule_set.before = Some(dt);
+        rrule_set.after = None;
+        rrule_set
+            .iter_rev()
+            .ok()?
+            .find(|date| inclusive || *date < dt)
+    }
+
+    /// Checks if there is a recurrence at exactly `dt`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// assert!(rrule_set.is_occurrence(Tz::UTC.with_ymd_and_hms(2021, 1, 5, 9, 0, 0).unwrap()));
+    /// assert!(!rrule_set.is_occurrence(Tz::UTC.with_ymd_and_hms(2021, 1, 5, 10, 0, 0).unwrap()));
+    /// ```
+    #[must_use]
+    pub fn is_occurrence(&self, dt: DateTime<Tz>) -> bool {
+        self.next_after(dt, true) == Some(dt)
+    }
+
+    /// Describes the rules of the set in English, e.g. "every day at 9:00".
+    ///
+    /// The parts which can't be described are left out, they are returned by
+    /// [`RRuleSet::unsupported_text_parts`].
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_text_with(&English)
+    }
+
+    /// Describes the rules of the set with the words of `locale`.
+    #[must_use]
+    pub fn to_text_with(&self, locale: &dyn Locale) -> String {
+        text::rrule_set_to_text(self, locale)
+    }
+
+    /// Returns the parts of the set which can't be described by [`RRuleSet::to_text`],
+    /// e.g. `BYSETPOS=-1` or `EXDATE`.
+    #[must_use]
+    pub fn unsupported_text_parts(&self) -> Vec<String> {
+        text::rrule_set_unsupported_parts(self)
+    }
+
+    /// Returns an iterator which starts at `after`, if it is set.
+    fn iter_from_after(&self) -> RRuleSetIter {
+        match self.after {
+            Some(after) => self.iter_from(after),
+            None => self.into_iter(),
+        }
+    }
+
+    /// Returns all the occurrences which intersect the half-open range `[start, end)`.
+    ///
+    /// Unlike [`RRuleSet::after`] and [`RRuleSet::before`], which only look at the start of the
+    /// occurrences, this also returns occurrences which started before `start` and are still ongoing.
+    /// Occurrences without duration are returned if their start is within the range.
+    ///
+    /// Limit must be set in order to prevent infinite loops.
+    /// The max limit is `65535`. If you need more please use `occurrences` directly.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nDURATION:PT2H\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let start = Tz::UTC.with_ymd_and_hms(2021, 1, 2, 10, 0, 0).unwrap();
+    /// let end = Tz::UTC.with_ymd_and_hms(2021, 1, 3, 10, 0, 0).unwrap();
+    /// let result = rrule_set.overlapping(start, end, 10);
+    /// assert_eq!(result.occurrences.len(), 2);
+    /// assert_eq!(result.limited, false);
+    /// ```
+    #[must_use]
+    pub fn overlapping(
+        mut self,
+        start: DateTime<Tz>,
+        end: DateTime<Tz>,
+        limit: u16,
+    ) -> OccurrenceResult {
+        self.limited = true;
+        collect_overlapping(self.occurrences(), &start, &end, Some(limit))
     }
 
     fn set_from_content_lines(self, content_lines: Vec<ContentLine>) -> Result<Self, RRuleError> {
@@ -242,12 +678,38 @@ impl RRuleSet {
                         Ok(rrule_set)
                     }
                 }
-                ContentLine::ExDate(exdates) => {
+                ContentLine::ExDate(DateValues::DateTime(exdates)) => {
                     Ok(exdates.into_iter().fold(rrule_set, Self::exdate))
                 }
-                ContentLine::RDate(rdates) => {
+                ContentLine::ExDate(DateValues::Date(exdates)) => {
+                    Ok(exdates.into_iter().fold(rrule_set, Self::exdate_date))
+                }
+                ContentLine::ExDate(DateValues::Period(_)) => {
+                    Err(ParseError::PeriodNotAllowed("EXDATE".into()).into())
+                }
+                ContentLine::RDate(DateValues::DateTime(rdates)) => {
                     Ok(rdates.into_iter().fold(rrule_set, Self::rdate))
                 }
+                ContentLine::RDate(DateValues::Date(rdates)) => {
+                    Ok(rdates.into_iter().fold(rrule_set, Self::rdate_date))
+                }
+                ContentLine::RDate(DateValues::Period(rdates)) => {
+                    Ok(rdates.into_iter().fold(rrule_set, Self::rdate_period))
+                }
+                ContentLine::DtEnd(dt_end) => {
+                    if dt_end < dt_start {
+                        return Err(ValidationError::EndBeforeStart {
+                            end: dt_end.to_rfc3339(),
+                            dt_start: dt_start.to_rfc3339(),
+                        }
+                        .into());
+                    }
+                    Ok(rrule_set.dt_end(dt_end))
+                }
+                ContentLine::Duration(duration) => {
+                    validate_duration(&dt_start, duration)?;
+                    Ok(rrule_set.duration(duration))
+                }
             },
         )
     }
@@ -279,10 +741,18 @@ impl FromStr for RRuleSet {
     ///
     /// Returns [`RRuleError`], if iCalendar string contains invalid parts.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_timezones(s, &[])
+    }
+}
+
+impl RRuleSet {
+    /// Creates an [`RRuleSet`] from a string, where a `TZID` can refer to the `VTIMEZONE`s of
+    /// the document in `timezones`.
+    pub(crate) fn parse_with_timezones(s: &str, timezones: &[Tz]) -> Result<Self, RRuleError> {
         let Grammar {
             start,
             content_lines,
-        } = Grammar::from_str(s)?;
+        } = Grammar::parse_with_timezones(s, timezones)?;
 
         let start = start.ok_or(ParseError::MissingStartDate)?;
         let dtstart_is_floating = start.is_floating();
@@ -295,7 +765,60 @@ impl Display for RRuleSet {
     /// Prints a valid set of iCalendar properties which can be used to create a new [`RRuleSet`] later.
     /// You may use the generated string to create a new iCalendar component, like VEVENT.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let start_datetime = format!("DTSTART{}", datetime_to_ical_format(&self.dt_start));
+        self.write_ical(f, datetime_to_ical_format)
+    }
+}
+
+#[cfg(feature = "windows-zones")]
+impl RRuleSet {
+    /// Prints the iCalendar properties like the [`Display`] implementation, but with the Windows
+    /// timezone name in the `TZID` of `DTSTART` and `DTEND`, like `Pacific Standard Time`, for
+    /// clients such as Outlook which only understand Windows timezone names.
+    /// A timezone without a Windows name keeps its IANA name.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART;TZID=America/Los_Angeles:20210104T090000\n\
+    ///     RRULE:FREQ=WEEKLY;COUNT=3"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert!(rrule_set
+    ///     .to_windows_string()
+    ///     .starts_with("DTSTART;TZID=Pacific Standard Time:20210104T090000"));
+    /// ```
+    #[must_use]
+    pub fn to_windows_string(&self) -> String {
+        struct WindowsDisplay<'a>(&'a RRuleSet);
+
+        impl Display for WindowsDisplay<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0
+                    .write_ical(f, crate::core::datetime::datetime_to_windows_ical_format)
+            }
+        }
+
+        WindowsDisplay(self).to_string()
+    }
+}
+
+impl RRuleSet {
+    /// Writes the iCalendar properties of the set, formatting `DTSTART` and `DTEND` with
+    /// `format_datetime`.
+    fn write_ical(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        format_datetime: fn(&DateTime<Tz>) -> String,
+    ) -> std::fmt::Result {
+        let start_datetime = format!("DTSTART{}", format_datetime(&self.dt_start));
+
+        let end = match (self.dt_end, self.duration) {
+            (Some(dt_end), _) => format!("\nDTEND{}", format_datetime(&dt_end)),
+            (None, Some(duration)) => format!("\nDURATION:{}", duration_to_ical_format(duration)),
+            (None, None) => String::new(),
+        };
 
         let mut rrules = self
             .rrule
@@ -307,21 +830,26 @@ impl Display for RRuleSet {
             rrules = format!("\n{rrules}");
         }
 
-        let mut rdates = self
-            .rdate
-            .iter()
-            .map(|dt| {
-                let maybe_zulu = if dt.timezone().is_local() { "" } else { "Z" };
+        let rdates = values_to_ical("RDATE", "DATE-TIME", self.rdate.iter().map(format_datetime));
 
-                format!("{}{}", dt.format("%Y%m%dT%H%M%S"), maybe_zulu)
-            })
+        let mut rdate_dates = self
+            .rdate_date
+            .iter()
+            .map(|date| date.format("%Y%m%d").to_string())
             .collect::<Vec<_>>()
             .join(",");
-        if !rdates.is_empty() {
-            // TODO: check if original VALUE prop was DATE or PERIOD
-            rdates = format!("\nRDATE;VALUE=DATE-TIME:{rdates}");
+        if !rdate_dates.is_empty() {
+            rdate_dates = format!("\nRDATE;VALUE=DATE:{rdate_dates}");
         }
 
+        let rdate_periods = values_to_ical(
+            "RDATE",
+            "PERIOD",
+            self.rdate_period
+                .iter()
+                .map(|period| period_to_ical_format(period, format_datetime)),
+        );
+
         let mut exrules = self
             .exrule
             .iter()
@@ -332,23 +860,67 @@ impl Display for RRuleSet {
             exrules = format!("\n{exrules}");
         }
 
-        let mut exdates = self
-            .exdate
-            .iter()
-            .map(|dt| {
-                let maybe_zulu = if dt.timezone().is_local() { "" } else { "Z" };
+        let exdates = values_to_ical(
+            "EXDATE",
+            "DATE-TIME",
+            self.exdate.iter().map(format_datetime),
+        );
 
-                format!("{}{}", dt.format("%Y%m%dT%H%M%S"), maybe_zulu)
-            })
+        let mut exdate_dates = self
+            .exdate_date
+            .iter()
+            .map(|date| date.format("%Y%m%d").to_string())
             .collect::<Vec<_>>()
             .join(",");
-        if !exdates.is_empty() {
-            // TODO: check if original VALUE prop was DATE or PERIOD
-            exdates = format!("\nEXDATE;VALUE=DATE-TIME:{exdates}");
+        if !exdate_dates.is_empty() {
+            exdate_dates = format!("\nEXDATE;VALUE=DATE:{exdate_dates}");
         }
 
-        write!(f, "{start_datetime}{rrules}{rdates}{exrules}{exdates}")
+        write!(
+            f,
+            "{start_datetime}{end}{rrules}{rdates}{rdate_dates}{rdate_periods}{exrules}{exdates}{exdate_dates}"
+        )
+    }
+}
+
+/// Checks that a `DURATION` doesn't end the occurrences before they start. The end in the error
+/// is the duration if `dt_start` plus the duration is outside the range of `chrono`.
+pub(crate) fn validate_duration(
+    dt_start: &DateTime<Tz>,
+    duration: Duration,
+) -> Result<(), ValidationError> {
+    if duration < Duration::zero() {
+        return Err(ValidationError::EndBeforeStart {
+            end: dt_start
+                .checked_add_signed(duration)
+                .map_or_else(|| duration_to_ical_format(duration), |end| end.to_rfc3339()),
+            dt_start: dt_start.to_rfc3339(),
+        });
     }
+    Ok(())
+}
+
+/// Generates the `RDATE` or `EXDATE` lines of datetimes or periods, with one line per `TZID`.
+/// The values are formatted with their parameters in front, like `;TZID=Europe/Berlin:...`.
+/// UTC and local values don't need a `TZID` and share the first line.
+fn values_to_ical(
+    property: &str,
+    value_type: &str,
+    values: impl Iterator<Item = String>,
+) -> String {
+    let mut lines: Vec<(String, Vec<String>)> = vec![];
+    for formatted in values {
+        let (tzid, value) = formatted.split_at(formatted.rfind(':').unwrap_or_default());
+        match lines.iter_mut().find(|(line_tzid, _)| line_tzid == tzid) {
+            Some((_, values)) => values.push(value[1..].to_string()),
+            None => lines.push((tzid.to_string(), vec![value[1..].to_string()])),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(tzid, values)| format!("\n{property};VALUE={value_type}{tzid}:{}", values.join(",")))
+        .collect()
 }
 
 #[cfg(feature = "exrule")]
@@ -0,0 +1,552 @@
+//! A structured serde representation of [`RRule`] and [`RRuleSet`], with a field for every rule
+//! part, as an alternative to the iCalendar string they are serialized to by default.
+use std::ops::{Deref, DerefMut};
+
+use chrono::{DateTime, Duration, LocalResult, Month, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use orig_serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "json-schema")]
+use super::json_schema;
+use super::{
+    datetime::duration_to_ical_format, rruleset::validate_duration, Frequency, NWeekday, Period,
+    PeriodEnd, RRule, RRuleSet, RScale, Skip, Tz,
+};
+use crate::{
+    parser::{parse_duration, parse_timezone, ParseError},
+    RRuleError, Unvalidated, ValidationError,
+};
+
+/// A wrapper which serializes an [`RRuleSet`] or an [`RRule`] with a field for every property
+/// and rule part, instead of as an iCalendar string. This allows storing rules in a document
+/// database and querying them by `freq` or `by_weekday`.
+///
+/// Datetimes are written as their local time with the `TZID` of their timezone, which is left
+/// out for floating datetimes. Deserializing an [`RRuleSet`] validates its rules against
+/// `dt_start`, so an invalid rule is rejected with its [`ValidationError`].
+///
+/// With the `json-schema` feature, `Structured<RRule>` and `Structured<RRuleSet>` implement
+/// `schemars::JsonSchema`. The ranges of the rule parts in the schema, like `0..=23` for
+/// `by_hour`, are the ones the validator checks.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{RRuleSet, Structured};
+///
+/// let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+///     RRULE:FREQ=WEEKLY;COUNT=4;BYDAY=MO,-1FR"
+///     .parse()
+///     .unwrap();
+/// let json = serde_json::to_value(Structured(rrule_set.clone())).unwrap();
+/// assert_eq!(json["dt_start"]["tzid"], "Europe/Berlin");
+/// assert_eq!(json["rrule"][0]["freq"], "WEEKLY");
+/// assert_eq!(json["rrule"][0]["by_weekday"][1]["n"], -1);
+///
+/// let Structured(deserialized) = serde_json::from_value::<Structured<RRuleSet>>(json).unwrap();
+/// assert_eq!(deserialized, rrule_set);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Structured<T>(pub T);
+
+impl<T> Deref for Structured<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Structured<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<S> Serialize for Structured<RRule<S>> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        RRuleFields::from(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Structured<RRule<Unvalidated>> {
+    /// Deserializes an unvalidated rule, which is validated when it is built with a `dt_start`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = RRuleFields::deserialize(deserializer)?;
+        fields.into_rrule().map(Self).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl<S> schemars::JsonSchema for Structured<RRule<S>> {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        RRuleFields::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        RRuleFields::json_schema(generator)
+    }
+}
+
+impl Serialize for Structured<RRuleSet> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        RRuleSetFields::from(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Structured<RRuleSet> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = RRuleSetFields::deserialize(deserializer)?;
+        fields.into_rrule_set().map(Self).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl schemars::JsonSchema for Structured<RRuleSet> {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        RRuleSetFields::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        RRuleSetFields::json_schema(generator)
+    }
+}
+
+/// A datetime as its local time and the `TZID` of its timezone, like
+/// `{"datetime": "2025-01-06T09:00:00", "tzid": "Europe/Berlin"}`.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "DateTime")
+)]
+#[serde(crate = "orig_serde")]
+struct DateTimeFields {
+    datetime: NaiveDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tzid: Option<String>,
+}
+
+impl From<&DateTime<Tz>> for DateTimeFields {
+    fn from(dt: &DateTime<Tz>) -> Self {
+        let tz = dt.timezone();
+        Self {
+            datetime: dt.naive_local(),
+            tzid: (!tz.is_local()).then(|| tz.name().to_string()),
+        }
+    }
+}
+
+impl DateTimeFields {
+    fn into_datetime(self, property: &str) -> Result<DateTime<Tz>, ParseError> {
+        let tz = match &self.tzid {
+            Some(tzid) => parse_timezone(tzid)?,
+            None => Tz::LOCAL,
+        };
+        match tz.from_local_datetime(&self.datetime) {
+            LocalResult::Single(dt) => Ok(dt),
+            LocalResult::None => Err(ParseError::InvalidDateTimeInLocalTimezone {
+                value: self.datetime.to_string(),
+                property: property.into(),
+            }),
+            LocalResult::Ambiguous(date1, date2) => {
+                Err(ParseError::DateTimeInLocalTimezoneIsAmbiguous {
+                    value: self.datetime.to_string(),
+                    property: property.into(),
+                    date1: date1.to_rfc3339(),
+                    date2: date2.to_rfc3339(),
+                })
+            }
+        }
+    }
+}
+
+/// A [`NWeekday`], like `{"weekday": "Mon"}` or `{"n": -1, "weekday": "Fri"}`.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "NWeekday")
+)]
+#[serde(crate = "orig_serde")]
+struct NWeekdayFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_weekday_n")
+    )]
+    n: Option<i16>,
+    weekday: Weekday,
+}
+
+impl From<&NWeekday> for NWeekdayFields {
+    fn from(n_weekday: &NWeekday) -> Self {
+        match *n_weekday {
+            NWeekday::Every(weekday) => Self { n: None, weekday },
+            NWeekday::Nth(n, weekday) => Self {
+                n: Some(n),
+                weekday,
+            },
+        }
+    }
+}
+
+/// A [`Period`], which has either an `end` or a `duration`.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Period")
+)]
+#[serde(crate = "orig_serde")]
+struct PeriodFields {
+    start: DateTimeFields,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<DateTimeFields>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::duration")
+    )]
+    duration: Option<String>,
+}
+
+impl From<&Period> for PeriodFields {
+    fn from(period: &Period) -> Self {
+        let (end, duration) = match period.get_period_end() {
+            PeriodEnd::DateTime(end) => (Some(end.into()), None),
+            PeriodEnd::Duration(duration) => (None, Some(duration_to_ical_format(*duration))),
+        };
+        Self {
+            start: (&period.get_start()).into(),
+            end,
+            duration,
+        }
+    }
+}
+
+impl PeriodFields {
+    fn into_period(self) -> Result<Period, ParseError> {
+        let start = self.start.into_datetime("RDATE")?;
+        let invalid_period = |value: String| ParseError::InvalidPeriod {
+            value,
+            property: "RDATE".into(),
+        };
+        match (self.end, self.duration) {
+            (Some(end), None) => {
+                let end = end.into_datetime("RDATE")?;
+                if end < start {
+                    return Err(invalid_period(end.to_rfc3339()));
+                }
+                Ok(Period::new(start, end))
+            }
+            (None, Some(duration)) => {
+                let parsed = parse_duration(&duration)?;
+                if parsed < Duration::zero() || start.checked_add_signed(parsed).is_none() {
+                    return Err(invalid_period(duration));
+                }
+                Ok(Period::with_duration(start, parsed))
+            }
+            _ => Err(invalid_period(start.to_rfc3339())),
+        }
+    }
+}
+
+fn default_interval() -> u16 {
+    1
+}
+
+fn default_week_start() -> Weekday {
+    Weekday::Mon
+}
+
+fn is_omit(skip: &Skip) -> bool {
+    *skip == Skip::Omit
+}
+
+/// The rule parts of an [`RRule`].
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "RRule")
+)]
+#[serde(crate = "orig_serde")]
+struct RRuleFields {
+    freq: Frequency,
+    #[serde(default = "default_interval")]
+    interval: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    until: Option<DateTimeFields>,
+    #[serde(default = "default_week_start")]
+    week_start: Weekday,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_set_pos")
+    )]
+    by_set_pos: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_month")
+    )]
+    by_month: Vec<Month>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_leap_month")
+    )]
+    by_leap_month: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_month_day")
+    )]
+    by_month_day: Vec<i8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_year_day")
+    )]
+    by_year_day: Vec<i16>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_week_no")
+    )]
+    by_week_no: Vec<i8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    by_weekday: Vec<NWeekdayFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_hour")
+    )]
+    by_hour: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_minute")
+    )]
+    by_minute: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_minute")
+    )]
+    by_second: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::by_easter")
+    )]
+    by_easter: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    include_dtstart: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    local_tzid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rscale: Option<RScale>,
+    #[serde(default, skip_serializing_if = "is_omit")]
+    skip: Skip,
+}
+
+impl<S> From<&RRule<S>> for RRuleFields {
+    fn from(rrule: &RRule<S>) -> Self {
+        // A validated rule keeps its negative month days apart
+        let mut by_month_day = rrule.by_n_month_day.clone();
+        by_month_day.extend(&rrule.by_month_day);
+        Self {
+            freq: rrule.freq,
+            interval: rrule.interval,
+            count: rrule.count,
+            until: rrule.until.as_ref().map(DateTimeFields::from),
+            week_start: rrule.week_start,
+            by_set_pos: rrule.by_set_pos.clone(),
+            by_month: rrule
+                .by_month
+                .iter()
+                .filter_map(|month| Month::try_from(*month).ok())
+                .collect(),
+            by_leap_month: rrule.by_leap_month.clone(),
+            by_month_day,
+            by_year_day: rrule.by_year_day.clone(),
+            by_week_no: rrule.by_week_no.clone(),
+            by_weekday: rrule.by_weekday.iter().map(NWeekdayFields::from).collect(),
+            by_hour: rrule.by_hour.clone(),
+            by_minute: rrule.by_minute.clone(),
+            by_second: rrule.by_second.clone(),
+            by_easter: rrule.by_easter,
+            include_dtstart: rrule.include_dtstart,
+            local_tzid: rrule.local_tzid.map(|tz| tz.name().to_string()),
+            rscale: rrule.rscale,
+            skip: rrule.skip,
+        }
+    }
+}
+
+impl RRuleFields {
+    fn into_rrule(self) -> Result<RRule<Unvalidated>, ParseError> {
+        let until = self
+            .until
+            .map(|until| until.into_datetime("UNTIL"))
+            .transpose()?;
+        let local_tzid = self
+            .local_tzid
+            .map(|tzid| parse_timezone(&tzid))
+            .transpose()?;
+        Ok(RRule {
+            freq: self.freq,
+            interval: self.interval,
+            count: self.count,
+            until,
+            week_start: self.week_start,
+            by_set_pos: self.by_set_pos,
+            by_month: self
+                .by_month
+                .iter()
+                .map(|month| {
+                    u8::try_from(month.number_from_month()).expect("1-12 is within range of u8")
+                })
+                .collect(),
+            by_leap_month: self.by_leap_month,
+            by_month_day: self.by_month_day,
+            by_year_day: self.by_year_day,
+            by_week_no: self.by_week_no,
+            by_weekday: self
+                .by_weekday
+                .into_iter()
+                .map(|n_weekday| NWeekday::new(n_weekday.n, n_weekday.weekday))
+                .collect(),
+            by_hour: self.by_hour,
+            by_minute: self.by_minute,
+            by_second: self.by_second,
+            by_easter: self.by_easter,
+            include_dtstart: self.include_dtstart,
+            local_tzid,
+            rscale: self.rscale,
+            skip: self.skip,
+            ..RRule::default()
+        })
+    }
+}
+
+/// The properties of an [`RRuleSet`], which are the ones of its iCalendar string.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "json-schema",
+    derive(schemars::JsonSchema),
+    schemars(rename = "RRuleSet")
+)]
+#[serde(crate = "orig_serde")]
+struct RRuleSetFields {
+    dt_start: DateTimeFields,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dt_end: Option<DateTimeFields>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(schema_with = "json_schema::duration")
+    )]
+    duration: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rrule: Vec<RRuleFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rdate: Vec<DateTimeFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rdate_date: Vec<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rdate_period: Vec<PeriodFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exrule: Vec<RRuleFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exdate: Vec<DateTimeFields>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exdate_date: Vec<NaiveDate>,
+}
+
+impl From<&RRuleSet> for RRuleSetFields {
+    fn from(rrule_set: &RRuleSet) -> Self {
+        Self {
+            dt_start: (&rrule_set.dt_start).into(),
+            dt_end: rrule_set.dt_end.as_ref().map(DateTimeFields::from),
+            duration: rrule_set.duration.map(duration_to_ical_format),
+            rrule: rrule_set.rrule.iter().map(RRuleFields::from).collect(),
+            rdate: rrule_set.rdate.iter().map(DateTimeFields::from).collect(),
+            rdate_date: rrule_set.rdate_date.clone(),
+            rdate_period: rrule_set
+                .rdate_period
+                .iter()
+                .map(PeriodFields::from)
+                .collect(),
+            exrule: rrule_set.exrule.iter().map(RRuleFields::from).collect(),
+            exdate: rrule_set.exdate.iter().map(DateTimeFields::from).collect(),
+            exdate_date: rrule_set.exdate_date.clone(),
+        }
+    }
+}
+
+impl RRuleSetFields {
+    /// Creates the set like it is parsed from its iCalendar string, which validates the rules.
+    fn into_rrule_set(self) -> Result<RRuleSet, RRuleError> {
+        let dt_start = self.dt_start.into_datetime("DTSTART")?;
+        let dtstart_is_floating = dt_start.timezone().is_local();
+        let mut rrule_set = RRuleSet::new(dt_start);
+
+        match (self.dt_end, self.duration) {
+            (Some(_), Some(_)) => return Err(ParseError::DtEndAndDuration.into()),
+            (Some(dt_end), None) => {
+                let dt_end = dt_end.into_datetime("DTEND")?;
+                if dt_end < dt_start {
+                    return Err(ValidationError::EndBeforeStart {
+                        end: dt_end.to_rfc3339(),
+                        dt_start: dt_start.to_rfc3339(),
+                    }
+                    .into());
+                }
+                rrule_set = rrule_set.dt_end(dt_end);
+            }
+            (None, Some(duration)) => {
+                let duration = parse_duration(&duration)?;
+                validate_duration(&dt_start, duration)?;
+                rrule_set = rrule_set.duration(duration);
+            }
+            (None, None) => {}
+        }
+
+        for rrule in self.rrule {
+            let mut rrule = rrule.into_rrule()?;
+            rrule.dtstart_is_floating = dtstart_is_floating;
+            rrule_set = rrule_set.rrule(rrule.validate(dt_start)?);
+        }
+        #[cfg(feature = "exrule")]
+        for exrule in self.exrule {
+            let mut exrule = exrule.into_rrule()?;
+            exrule.dtstart_is_floating = dtstart_is_floating;
+            rrule_set = rrule_set.exrule(exrule.validate(dt_start)?);
+        }
+        #[cfg(not(feature = "exrule"))]
+        if !self.exrule.is_empty() {
+            log::warn!("Found EXRULE in input, but it will be ignored since the `exrule` feature is not enabled.");
+        }
+
+        for rdate in self.rdate {
+            rrule_set = rrule_set.rdate(rdate.into_datetime("RDATE")?);
+        }
+        for rdate in self.rdate_date {
+            rrule_set = rrule_set.rdate_date(rdate);
+        }
+        for period in self.rdate_period {
+            rrule_set = rrule_set.rdate_period(period.into_period()?);
+        }
+        for exdate in self.exdate {
+            rrule_set = rrule_set.exdate(exdate.into_datetime("EXDATE")?);
+        }
+        for exdate in self.exdate_date {
+            rrule_set = rrule_set.exdate_date(exdate);
+        }
+        Ok(rrule_set)
+    }
+}
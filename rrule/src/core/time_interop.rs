@@ -0,0 +1,219 @@
+//! Conversions from and to the datetimes of the `time` crate.
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use time::{OffsetDateTime, UtcOffset};
+
+use super::{rrule::RRule, timezone::Tz, RRuleSet, Unvalidated};
+use crate::{RRuleError, ValidationError};
+
+impl From<UtcOffset> for Tz {
+    /// Converts an offset to [`Tz::UTC`] if it is zero, or to a fixed offset otherwise.
+    fn from(offset: UtcOffset) -> Self {
+        if offset.is_utc() {
+            return Self::UTC;
+        }
+        Self::Fixed(
+            FixedOffset::east_opt(offset.whole_seconds()).expect("offsets of a day are covered"),
+        )
+    }
+}
+
+/// Converts an [`OffsetDateTime`] to a datetime with its offset as timezone, or returns `None`
+/// if it is outside the range of `chrono`, which `time` exceeds with its `large-dates` feature.
+pub(crate) fn offset_date_time_to_datetime(dt: OffsetDateTime) -> Option<DateTime<Tz>> {
+    DateTime::from_timestamp(dt.unix_timestamp(), dt.nanosecond())
+        .map(|utc| utc.with_timezone(&dt.offset().into()))
+}
+
+/// Converts an [`OffsetDateTime`] to a datetime in `tz`, where a datetime outside the range of
+/// `chrono` becomes the earliest or latest datetime.
+fn offset_date_time_to_clamped_datetime(dt: OffsetDateTime, tz: &Tz) -> DateTime<Tz> {
+    match offset_date_time_to_datetime(dt) {
+        Some(converted) => converted.with_timezone(tz),
+        None if dt.unix_timestamp() < 0 => DateTime::<Utc>::MIN_UTC.with_timezone(tz),
+        None => DateTime::<Utc>::MAX_UTC.with_timezone(tz),
+    }
+}
+
+/// Converts a datetime to an [`OffsetDateTime`] with the same offset, or returns `None` if it is
+/// outside the range of `time`.
+pub(crate) fn datetime_to_offset_date_time(dt: &DateTime<Tz>) -> Option<OffsetDateTime> {
+    let offset = UtcOffset::from_whole_seconds(dt.offset().fix().local_minus_utc()).ok()?;
+    OffsetDateTime::from_unix_timestamp(dt.timestamp())
+        .ok()?
+        .replace_nanosecond(dt.timestamp_subsec_nanos())
+        .ok()?
+        .checked_to_offset(offset)
+}
+
+impl RRule<Unvalidated> {
+    /// Validates the [`RRule`] with a `time` `dt_start` and creates an [`RRuleSet`] struct.
+    /// The recurrences keep the offset of `dt_start`, as `time` has no timezones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError::ValidationError`] in case the rrule is invalid, or if `dt_start`
+    /// is outside the range of `chrono`.
+    pub fn build_offset_date_time(self, dt_start: OffsetDateTime) -> Result<RRuleSet, RRuleError> {
+        let dt_start = offset_date_time_to_datetime(dt_start)
+            .ok_or(ValidationError::StartYearOutOfRange(dt_start.year()))?;
+        self.build(dt_start)
+    }
+}
+
+impl RRuleSet {
+    /// Only return recurrences that comes before this `time` datetime, see [`RRuleSet::before`].
+    /// A datetime outside the range of `chrono` is clamped to it.
+    #[must_use]
+    pub fn before_offset_date_time(self, dt: OffsetDateTime) -> Self {
+        let tz = self.get_dt_start().timezone();
+        self.before(offset_date_time_to_clamped_datetime(dt, &tz))
+    }
+
+    /// Only return recurrences that comes after this `time` datetime, see [`RRuleSet::after`].
+    /// A datetime outside the range of `chrono` is clamped to it.
+    #[must_use]
+    pub fn after_offset_date_time(self, dt: OffsetDateTime) -> Self {
+        let tz = self.get_dt_start().timezone();
+        self.after(offset_date_time_to_clamped_datetime(dt, &tz))
+    }
+}
+
+/// An iterator adapter which yields the datetimes of an iterator as `time::OffsetDateTime`,
+/// with the offset they have in their timezone. The iterator ends at the first datetime outside
+/// the range of `time`.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{OffsetDateTimeIterator, RRuleSet};
+///
+/// let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20250330T090000\nRRULE:FREQ=DAILY;COUNT=2"
+///     .parse()
+///     .unwrap();
+/// let dates = rrule_set.into_iter().offset_date_times().collect::<Vec<_>>();
+/// assert_eq!(dates[0].offset().whole_hours(), 2);
+/// assert_eq!(dates[1].hour(), 9);
+/// ```
+pub trait OffsetDateTimeIterator: Iterator<Item = DateTime<Tz>> + Sized {
+    /// Yields the datetimes as `time::OffsetDateTime`.
+    fn offset_date_times(self) -> OffsetDateTimeIter<Self> {
+        OffsetDateTimeIter { inner: self }
+    }
+}
+
+impl<I: Iterator<Item = DateTime<Tz>>> OffsetDateTimeIterator for I {}
+
+/// The iterator returned by [`OffsetDateTimeIterator::offset_date_times`].
+#[derive(Clone, Debug)]
+pub struct OffsetDateTimeIter<I> {
+    inner: I,
+}
+
+impl<I: Iterator<Item = DateTime<Tz>>> Iterator for OffsetDateTimeIter<I> {
+    type Item = OffsetDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        datetime_to_offset_date_time(&self.inner.next()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use time::macros::{datetime, offset};
+
+    use super::*;
+
+    #[test]
+    fn converts_offsets() {
+        assert_eq!(Tz::from(offset!(UTC)), Tz::UTC);
+        assert_eq!(
+            Tz::from(offset!(+5:30)),
+            Tz::Fixed(FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap())
+        );
+    }
+
+    #[test]
+    fn converts_datetimes() {
+        let tests = [
+            datetime!(2025-03-30 03:30:00.5 +2),
+            datetime!(1969-12-31 23:59:59.25 UTC),
+            datetime!(1600-01-01 00:00:00 -8),
+        ];
+        for dt in tests {
+            let converted = offset_date_time_to_datetime(dt).unwrap();
+            assert_eq!(converted.timestamp(), dt.unix_timestamp());
+            assert_eq!(converted.timestamp_subsec_nanos(), dt.nanosecond());
+            assert_eq!(
+                converted.offset().fix().local_minus_utc(),
+                dt.offset().whole_seconds()
+            );
+            assert_eq!(datetime_to_offset_date_time(&converted), Some(dt));
+        }
+
+        let berlin = Tz::Europe__Berlin
+            .with_ymd_and_hms(2025, 7, 1, 9, 0, 0)
+            .unwrap();
+        assert_eq!(
+            datetime_to_offset_date_time(&berlin),
+            Some(datetime!(2025-07-01 09:00:00 +2))
+        );
+    }
+
+    #[test]
+    fn handles_datetimes_outside_of_chrono_range() {
+        let far_future = datetime!(+900000-01-01 00:00:00 UTC);
+        let far_past = datetime!(-900000-01-01 00:00:00 UTC);
+        assert_eq!(offset_date_time_to_datetime(far_future), None);
+        assert_eq!(
+            "FREQ=DAILY"
+                .parse::<RRule<Unvalidated>>()
+                .unwrap()
+                .build_offset_date_time(far_future),
+            Err(ValidationError::StartYearOutOfRange(900_000).into())
+        );
+
+        let rrule_set = "FREQ=DAILY;COUNT=3"
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .build_offset_date_time(datetime!(2025-01-06 09:00:00 UTC))
+            .unwrap();
+        let count = |rrule_set: RRuleSet| rrule_set.all(10).dates.len();
+        assert_eq!(
+            count(rrule_set.clone().before_offset_date_time(far_future)),
+            3
+        );
+        assert_eq!(count(rrule_set.clone().after_offset_date_time(far_past)), 3);
+        assert_eq!(
+            count(rrule_set.clone().before_offset_date_time(far_past)),
+            0
+        );
+        assert_eq!(count(rrule_set.after_offset_date_time(far_future)), 0);
+    }
+
+    #[test]
+    fn builds_and_iterates_with_offset_date_times() {
+        let rrule_set = "FREQ=WEEKLY"
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .build_offset_date_time(datetime!(2025-01-06 09:00:00 -5))
+            .unwrap()
+            .after_offset_date_time(datetime!(2025-03-01 00:00:00 UTC))
+            .before_offset_date_time(datetime!(2025-03-20 00:00:00 UTC));
+
+        let dates = rrule_set
+            .all(10)
+            .dates
+            .into_iter()
+            .offset_date_times()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                datetime!(2025-03-03 09:00:00 -5),
+                datetime!(2025-03-10 09:00:00 -5),
+                datetime!(2025-03-17 09:00:00 -5),
+            ]
+        );
+    }
+}
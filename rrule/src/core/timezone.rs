@@ -1,6 +1,14 @@
-use chrono::Local;
+use std::sync::{Mutex, OnceLock, PoisonError};
 
-/// A wrapper around `chrono_tz::Tz` that is able to represent `Local` timezone also.
+use chrono::{FixedOffset, Local};
+
+use super::vtimezone::VTimezone;
+
+/// A wrapper around `chrono_tz::Tz` that is able to represent `Local` timezone, fixed offsets
+/// and timezones defined by a `VTIMEZONE` also.
+///
+/// The `chrono_tz::Tz` variant and the constants of the IANA timezones, like
+/// `Tz::Europe__Berlin`, need the `chrono-tz` feature, which is enabled by default.
 ///
 /// # Usage
 ///
@@ -9,16 +17,26 @@ use chrono::Local;
 ///
 /// let utc = Tz::UTC;
 /// let local = Tz::LOCAL;
+/// # #[cfg(feature = "chrono-tz")]
 /// let berlin = Tz::Europe__Berlin;
 /// // From `chrono_tz::Tz`
+/// # #[cfg(feature = "chrono-tz")]
 /// let berlin: Tz = chrono_tz::Tz::Europe__Berlin.into();
+/// // From `chrono::FixedOffset`, named like `UTC+05:30`
+/// let kolkata: Tz = chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap().into();
+/// // From a `VTIMEZONE`, see `VTimezone`
 /// ```
 #[derive(Clone, Copy)]
 pub enum Tz {
     /// Local timezone
     Local(Local),
     /// Timezone represented by `chrono_tz::Tz`
+    #[cfg(feature = "chrono-tz")]
     Tz(chrono_tz::Tz),
+    /// Timezone defined by a `VTIMEZONE` component
+    VTimezone(&'static VTimezone),
+    /// Timezone with a fixed offset from UTC, without daylight saving time
+    Fixed(FixedOffset),
 }
 
 impl Tz {
@@ -27,7 +45,12 @@ impl Tz {
     pub fn name(&self) -> &str {
         match self {
             Self::Local(_) => "Local",
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz.name(),
+            Self::VTimezone(tz) => tz.get_tzid(),
+            // Without the `chrono-tz` feature, UTC is a fixed offset
+            Self::Fixed(_) if *self == Self::UTC => "UTC",
+            Self::Fixed(offset) => fixed_offset_name(*offset),
         }
     }
 
@@ -36,14 +59,40 @@ impl Tz {
     pub fn is_local(&self) -> bool {
         match self {
             Self::Local(_) => true,
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(_) => false,
+            Self::VTimezone(_) | Self::Fixed(_) => false,
+        }
+    }
+
+    /// Windows name of timezone, like `Pacific Standard Time` for `America/Los_Angeles`,
+    /// if CLDR maps the timezone to a Windows timezone.
+    #[cfg(feature = "windows-zones")]
+    #[must_use]
+    pub fn windows_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Local(_) | Self::Fixed(_) => None,
+            // Timezones of a `TimezoneProvider` can be a `VTimezone` named by IANA also
+            _ => super::windows_zones::iana_to_windows(self.name()),
         }
     }
 
     /// Local timezone
     #[allow(non_upper_case_globals)]
     pub const LOCAL: Self = Self::Local(Local);
+}
 
+#[cfg(not(feature = "chrono-tz"))]
+impl Tz {
+    /// UTC, as a fixed offset of zero without the `chrono-tz` feature
+    pub const UTC: Self = match FixedOffset::east_opt(0) {
+        Some(offset) => Self::Fixed(offset),
+        None => unreachable!(),
+    };
+}
+
+#[cfg(feature = "chrono-tz")]
+impl Tz {
     // Duplicating all chrono_tz variants with the following program:
     //    for tz in chrono_tz::TZ_VARIANTS {
     //        let tz_name = tz.name();
@@ -1863,3 +1912,28 @@ impl Tz {
     #[allow(missing_docs)]
     pub const Zulu: Self = Self::Tz(chrono_tz::Zulu);
 }
+
+/// Returns the name of a fixed offset, like `UTC+05:30`. The names are stored for the rest of
+/// the program, like the definitions of [`VTimezone`]s, so [`Tz::name`] can borrow them.
+fn fixed_offset_name(offset: FixedOffset) -> &'static str {
+    static NAMES: OnceLock<Mutex<Vec<(FixedOffset, &'static str)>>> = OnceLock::new();
+
+    let mut names = NAMES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some((_, name)) = names.iter().find(|(stored, _)| *stored == offset) {
+        return name;
+    }
+
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    let mut name = format!("UTC{sign}{:02}:{:02}", seconds / 3600, seconds / 60 % 60);
+    if seconds % 60 != 0 {
+        name.push_str(&format!(":{:02}", seconds % 60));
+    }
+    let name: &'static str = Box::leak(name.into_boxed_str());
+    names.push((offset, name));
+    name
+}
@@ -1,12 +1,17 @@
-use chrono::{FixedOffset, Local, Offset, TimeZone, Utc};
+use chrono::{FixedOffset, Local, NaiveTime, Offset, TimeZone, Utc};
 
+use super::vtimezone::VTimezone;
 use super::Tz;
+use crate::parser::ParseError;
 
 impl PartialEq for Tz {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Local(_), Self::Local(_)) => true,
+            #[cfg(feature = "chrono-tz")]
             (Self::Tz(l0), Self::Tz(r0)) => l0 == r0,
+            (Self::VTimezone(l0), Self::VTimezone(r0)) => l0 == r0,
+            (Self::Fixed(l0), Self::Fixed(r0)) => l0 == r0,
             _ => false,
         }
     }
@@ -22,21 +27,44 @@ impl From<Local> for Tz {
 
 impl From<Utc> for Tz {
     fn from(_tz: Utc) -> Self {
-        Self::Tz(chrono_tz::UTC)
+        Self::UTC
     }
 }
 
+#[cfg(feature = "chrono-tz")]
 impl From<chrono_tz::Tz> for Tz {
     fn from(tz: chrono_tz::Tz) -> Self {
         Self::Tz(tz)
     }
 }
 
+impl From<FixedOffset> for Tz {
+    fn from(offset: FixedOffset) -> Self {
+        Self::Fixed(offset)
+    }
+}
+
+impl TryFrom<VTimezone> for Tz {
+    type Error = ParseError;
+
+    /// Stores the definition for the rest of the program, see [`VTimezone`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::TooManyTimezones`], if the stored definitions reached their limit.
+    fn try_from(tz: VTimezone) -> Result<Self, Self::Error> {
+        tz.into_static().map(Self::VTimezone)
+    }
+}
+
 impl std::fmt::Debug for Tz {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Local(tz) => tz.fmt(f),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz.fmt(f),
+            Self::VTimezone(tz) => write!(f, "{}", tz.get_tzid()),
+            Self::Fixed(offset) => offset.fmt(f),
         }
     }
 }
@@ -45,7 +73,10 @@ impl std::fmt::Display for Tz {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Local(_tz) => write!(f, "Local"),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz.fmt(f),
+            Self::VTimezone(tz) => write!(f, "{}", tz.get_tzid()),
+            Self::Fixed(_) => write!(f, "{}", self.name()),
         }
     }
 }
@@ -53,14 +84,19 @@ impl std::fmt::Display for Tz {
 #[derive(Clone, Copy)]
 pub enum RRuleOffset {
     Local(FixedOffset),
+    #[cfg(feature = "chrono-tz")]
     Tz(<chrono_tz::Tz as TimeZone>::Offset),
+    VTimezone(&'static VTimezone, FixedOffset),
+    Fixed(FixedOffset),
 }
 
 impl std::fmt::Debug for RRuleOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Local(offset) => offset.fmt(f),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(offset) => offset.fmt(f),
+            Self::VTimezone(_, offset) | Self::Fixed(offset) => offset.fmt(f),
         }
     }
 }
@@ -69,7 +105,9 @@ impl std::fmt::Display for RRuleOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Local(offset) => offset.fmt(f),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(offset) => offset.fmt(f),
+            Self::VTimezone(_, offset) | Self::Fixed(offset) => offset.fmt(f),
         }
     }
 }
@@ -78,7 +116,9 @@ impl Offset for RRuleOffset {
     fn fix(&self) -> FixedOffset {
         match self {
             Self::Local(tz) => tz.fix(),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz.fix(),
+            Self::VTimezone(_, offset) | Self::Fixed(offset) => *offset,
         }
     }
 }
@@ -89,7 +129,10 @@ impl TimeZone for Tz {
     fn from_offset(offset: &Self::Offset) -> Self {
         match offset {
             RRuleOffset::Local(offset) => Self::Local(Local::from_offset(offset)),
+            #[cfg(feature = "chrono-tz")]
             RRuleOffset::Tz(offset) => Self::Tz(chrono_tz::Tz::from_offset(offset)),
+            RRuleOffset::VTimezone(tz, _) => Self::VTimezone(tz),
+            RRuleOffset::Fixed(offset) => Self::Fixed(*offset),
         }
     }
 
@@ -102,9 +145,17 @@ impl TimeZone for Tz {
             Self::Local(tz) => tz
                 .from_local_date(local)
                 .map(|date| RRuleOffset::Local(*date.offset())),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz
                 .from_local_date(local)
                 .map(|date| RRuleOffset::Tz(*date.offset())),
+            Self::VTimezone(tz) => match local.and_hms_opt(0, 0, 0) {
+                Some(local) => tz
+                    .offsets_at_local(&local)
+                    .map(|offset| RRuleOffset::VTimezone(tz, offset)),
+                None => chrono::LocalResult::None,
+            },
+            Self::Fixed(offset) => chrono::LocalResult::Single(RRuleOffset::Fixed(*offset)),
         }
     }
 
@@ -116,9 +167,14 @@ impl TimeZone for Tz {
             Self::Local(tz) => tz
                 .from_local_datetime(local)
                 .map(|date| RRuleOffset::Local(*date.offset())),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => tz
                 .from_local_datetime(local)
                 .map(|date| RRuleOffset::Tz(*date.offset())),
+            Self::VTimezone(tz) => tz
+                .offsets_at_local(local)
+                .map(|offset| RRuleOffset::VTimezone(tz, offset)),
+            Self::Fixed(offset) => chrono::LocalResult::Single(RRuleOffset::Fixed(*offset)),
         }
     }
 
@@ -126,14 +182,22 @@ impl TimeZone for Tz {
     fn offset_from_utc_date(&self, utc: &chrono::NaiveDate) -> Self::Offset {
         match self {
             Self::Local(tz) => RRuleOffset::Local(*tz.from_utc_date(utc).offset()),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => RRuleOffset::Tz(*tz.from_utc_date(utc).offset()),
+            Self::VTimezone(tz) => {
+                RRuleOffset::VTimezone(tz, tz.offset_at_utc(&utc.and_time(NaiveTime::MIN)))
+            }
+            Self::Fixed(offset) => RRuleOffset::Fixed(*offset),
         }
     }
 
     fn offset_from_utc_datetime(&self, utc: &chrono::NaiveDateTime) -> Self::Offset {
         match self {
             Self::Local(tz) => RRuleOffset::Local(*tz.from_utc_datetime(utc).offset()),
+            #[cfg(feature = "chrono-tz")]
             Self::Tz(tz) => RRuleOffset::Tz(*tz.from_utc_datetime(utc).offset()),
+            Self::VTimezone(tz) => RRuleOffset::VTimezone(tz, tz.offset_at_utc(utc)),
+            Self::Fixed(offset) => RRuleOffset::Fixed(*offset),
         }
     }
 }
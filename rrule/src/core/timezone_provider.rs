@@ -0,0 +1,75 @@
+#[cfg(feature = "chrono-tz")]
+use std::str::FromStr;
+use std::sync::{PoisonError, RwLock};
+
+use super::timezone::Tz;
+
+/// A source of timezones, which resolves the timezone names found in `TZID` parameters.
+///
+/// [`ChronoTzProvider`] is used by default. A different provider can be installed with
+/// [`set_timezone_provider`], e.g. to use a timezone database which is loaded at runtime.
+/// Without the `chrono-tz` feature, only `UTC` is known until a provider is installed.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{ChronoTzProvider, TimezoneProvider, Tz};
+///
+/// /// Knows the timezones of `chrono_tz`, and `Office` as an alias of `Europe/Amsterdam`.
+/// struct OfficeProvider;
+///
+/// impl TimezoneProvider for OfficeProvider {
+///     fn timezone(&self, name: &str) -> Option<Tz> {
+///         match name {
+///             "Office" => Some(Tz::Europe__Amsterdam),
+///             _ => ChronoTzProvider.timezone(name),
+///         }
+///     }
+/// }
+///
+/// rrule::set_timezone_provider(OfficeProvider);
+/// let rrule_set: rrule::RRuleSet = "DTSTART;TZID=Office:20250101T090000\nRRULE:FREQ=DAILY"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(rrule_set.get_dt_start().timezone(), Tz::Europe__Amsterdam);
+/// ```
+pub trait TimezoneProvider: Send + Sync {
+    /// Returns the timezone with the given name, or `None` if the name is unknown.
+    fn timezone(&self, name: &str) -> Option<Tz>;
+}
+
+/// The default [`TimezoneProvider`], which knows the IANA timezones compiled into `chrono_tz`.
+#[cfg(feature = "chrono-tz")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChronoTzProvider;
+
+#[cfg(feature = "chrono-tz")]
+impl TimezoneProvider for ChronoTzProvider {
+    fn timezone(&self, name: &str) -> Option<Tz> {
+        chrono_tz::Tz::from_str(name).ok().map(Tz::Tz)
+    }
+}
+
+static PROVIDER: RwLock<Option<Box<dyn TimezoneProvider>>> = RwLock::new(None);
+
+/// Installs the [`TimezoneProvider`] which is used to look up timezones by name for the rest of
+/// the program, replacing the previous provider.
+pub fn set_timezone_provider(provider: impl TimezoneProvider + 'static) {
+    *PROVIDER.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(provider));
+}
+
+/// Looks up a timezone with the installed [`TimezoneProvider`], or with [`ChronoTzProvider`]
+/// if none is installed.
+pub(crate) fn lookup_timezone(name: &str) -> Option<Tz> {
+    match PROVIDER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_deref()
+    {
+        Some(provider) => provider.timezone(name),
+        #[cfg(feature = "chrono-tz")]
+        None => ChronoTzProvider.timezone(name),
+        #[cfg(not(feature = "chrono-tz"))]
+        None => (name == "UTC").then_some(Tz::UTC),
+    }
+}
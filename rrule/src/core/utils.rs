@@ -1,5 +1,5 @@
-use crate::RRuleResult;
 use crate::{iter::rrule_iter::WasLimited, Tz};
+use crate::{Occurrence, OccurrenceResult, RRuleResult};
 use std::ops::{
     Bound::{Excluded, Unbounded},
     RangeBounds,
@@ -46,6 +46,43 @@ where
     }
 }
 
+/// Helper function to collect the occurrences which intersect the range `[start, end)`.
+///
+/// The iterator has to yield the occurrences ordered by their start.
+pub(super) fn collect_overlapping<T>(
+    mut iterator: T,
+    start: &chrono::DateTime<Tz>,
+    end: &chrono::DateTime<Tz>,
+    limit: Option<u16>,
+) -> OccurrenceResult
+where
+    T: Iterator<Item = Occurrence> + WasLimited,
+{
+    let mut list = vec![];
+    let mut was_limited = false;
+    while limit.is_none() || matches!(limit, Some(limit) if usize::from(limit) > list.len()) {
+        if let Some(occurrence) = iterator.next() {
+            if occurrence.start >= *end {
+                // All following occurrences start after the range, so can stop iterating
+                break;
+            }
+            if occurrence.intersects(start, end) {
+                list.push(occurrence);
+            }
+        } else {
+            was_limited = iterator.was_limited();
+            break;
+        }
+    }
+
+    was_limited = was_limited || matches!(limit, Some(limit) if usize::from(limit) == list.len());
+
+    OccurrenceResult {
+        occurrences: list,
+        limited: was_limited,
+    }
+}
+
 /// Checks if `date` is after `end`.
 fn has_reached_the_end(
     date: &chrono::DateTime<Tz>,
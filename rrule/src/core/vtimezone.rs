@@ -0,0 +1,529 @@
+use super::rrule::{Frequency, RRule};
+use super::timezone::Tz;
+use crate::parser::{parse_components, ParseError};
+use crate::{RRuleError, Unvalidated};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDateTime, TimeZone};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// The onsets of observances with an `RRULE` are only calculated until the end of this year.
+/// Later datetimes use the offset of the last onset.
+pub(crate) const TRANSITIONS_UNTIL_YEAR: i32 = 2200;
+
+/// The stored timezones take up about this many bytes at most, so converting definitions from
+/// untrusted input can't grow the memory without bound.
+const MAX_STORED_BYTES: usize = 64 * 1024 * 1024;
+
+/// The maximum number of onsets of an observance with an `RRULE`, which is far more than a
+/// yearly rule has until [`TRANSITIONS_UNTIL_YEAR`], but keeps a hostile definition from taking
+/// up time and memory.
+const MAX_OBSERVANCE_ONSETS: usize = 10_000;
+
+/// A `STANDARD` or `DAYLIGHT` sub-component of a [`VTimezone`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Observance {
+    /// The first onset, in the local time which is observed before the onset.
+    pub dt_start: NaiveDateTime,
+    /// `TZOFFSETFROM`, the offset which is observed before the onset.
+    pub offset_from: FixedOffset,
+    /// `TZOFFSETTO`, the offset which is observed from the onset on.
+    pub offset_to: FixedOffset,
+    /// The recurrence of the onset, its `UNTIL` is given in UTC.
+    pub rrule: Option<RRule<Unvalidated>>,
+    /// Additional onsets, in the local time which is observed before the onset.
+    pub rdates: Vec<NaiveDateTime>,
+}
+
+/// A change of the offset of a [`VTimezone`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Transition {
+    /// The UTC datetime of the change.
+    at: NaiveDateTime,
+    offset_from: FixedOffset,
+    offset_to: FixedOffset,
+}
+
+/// A timezone defined by a `VTIMEZONE` component of an iCalendar document, like the
+/// definitions which Outlook and Exchange embed for zones such as `W. Europe Standard Time`.
+///
+/// The `STANDARD` and `DAYLIGHT` sub-components are expanded with their `RDATE`s and
+/// `RRULE`, until the year 2200. An `RRULE` needs to be yearly, like the rules of every real
+/// timezone. A [`VTimezone`] is used as a timezone by converting it into [`Tz`].
+///
+/// The definitions are stored for the rest of the program, which is what allows [`Tz`] to stay
+/// `Copy`. Converting the same definition again reuses the stored one, so the memory grows with
+/// the number of distinct definitions and not with the number of parsed documents. The stored
+/// definitions take up 64 MiB at most, a definition which doesn't fit anymore can't be converted.
+///
+/// `TZID` parameters resolve against the `VTIMEZONE`s of the document when it is parsed as a
+/// [`Calendar`](crate::Calendar).
+///
+/// # Usage
+///
+/// ```
+/// use chrono::TimeZone;
+/// use rrule::{Tz, VTimezone};
+///
+/// let vtimezone: VTimezone = "BEGIN:VTIMEZONE\r\n\
+///     TZID:W. Europe Standard Time\r\n\
+///     BEGIN:STANDARD\r\n\
+///     DTSTART:16010101T030000\r\n\
+///     TZOFFSETFROM:+0200\r\n\
+///     TZOFFSETTO:+0100\r\n\
+///     RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r\n\
+///     END:STANDARD\r\n\
+///     BEGIN:DAYLIGHT\r\n\
+///     DTSTART:16010101T020000\r\n\
+///     TZOFFSETFROM:+0100\r\n\
+///     TZOFFSETTO:+0200\r\n\
+///     RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\r\n\
+///     END:DAYLIGHT\r\n\
+///     END:VTIMEZONE\r\n"
+///     .parse()
+///     .unwrap();
+///
+/// let tz = Tz::try_from(vtimezone).unwrap();
+/// assert_eq!(tz.name(), "W. Europe Standard Time");
+/// let summer = tz.with_ymd_and_hms(2021, 7, 1, 9, 0, 0).unwrap();
+/// assert_eq!(summer.to_rfc3339(), "2021-07-01T09:00:00+02:00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VTimezone {
+    tzid: String,
+    observances: Vec<Observance>,
+    /// The onsets of all observances, sorted by their UTC datetime.
+    transitions: Vec<Transition>,
+}
+
+impl VTimezone {
+    /// Creates a timezone from its `TZID` and its `STANDARD` and `DAYLIGHT` sub-components.
+    pub(crate) fn new(tzid: String, observances: Vec<Observance>) -> Result<Self, RRuleError> {
+        if observances.is_empty() {
+            return Err(ParseError::MissingTimezoneObservance(tzid).into());
+        }
+
+        let mut transitions = vec![];
+        for observance in &observances {
+            for onset in observance_onsets(&tzid, observance)? {
+                transitions.push(Transition {
+                    at: onset - Duration::seconds(observance.offset_from.local_minus_utc().into()),
+                    offset_from: observance.offset_from,
+                    offset_to: observance.offset_to,
+                });
+            }
+        }
+        transitions.sort_by_key(|transition| transition.at);
+        transitions.dedup();
+
+        Ok(Self {
+            tzid,
+            observances,
+            transitions,
+        })
+    }
+
+    /// Creates a timezone from its changes of offset, given in ascending order as the UTC
+    /// datetime of the change with the offset from then on. `initial` is observed before the
+    /// first change.
+    #[cfg(any(feature = "zoneinfo", feature = "binary"))]
+    pub(crate) fn from_transitions(
+        tzid: String,
+        initial: FixedOffset,
+        changes: &[(NaiveDateTime, FixedOffset)],
+    ) -> Self {
+        let mut offset_from = initial;
+        let mut transitions = vec![];
+        for (at, offset_to) in changes {
+            transitions.push(Transition {
+                at: *at,
+                offset_from,
+                offset_to: *offset_to,
+            });
+            offset_from = *offset_to;
+        }
+        if transitions.is_empty() {
+            // A timezone needs a transition to find its offset
+            transitions.push(Transition {
+                at: NaiveDateTime::MIN,
+                offset_from: initial,
+                offset_to: initial,
+            });
+        }
+
+        Self {
+            tzid,
+            observances: vec![],
+            transitions,
+        }
+    }
+
+    /// Returns the `TZID` of the timezone.
+    #[must_use]
+    pub fn get_tzid(&self) -> &str {
+        &self.tzid
+    }
+
+    /// Returns the `STANDARD` and `DAYLIGHT` sub-components, which are empty for a timezone
+    /// created from its changes of offset.
+    #[cfg(feature = "binary")]
+    pub(crate) fn get_observances(&self) -> &[Observance] {
+        &self.observances
+    }
+
+    /// Returns the offset before the first change and the changes of offset, as they are
+    /// given to [`VTimezone::from_transitions`].
+    #[cfg(feature = "binary")]
+    pub(crate) fn get_changes(&self) -> (FixedOffset, Vec<(NaiveDateTime, FixedOffset)>) {
+        let changes = self
+            .transitions
+            .iter()
+            .map(|transition| (transition.at, transition.offset_to))
+            .collect();
+        (self.transitions[0].offset_from, changes)
+    }
+
+    /// Returns the offset which is observed at the given UTC datetime.
+    pub(crate) fn offset_at_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+        let idx = self
+            .transitions
+            .partition_point(|transition| transition.at <= *utc);
+        match idx.checked_sub(1) {
+            Some(idx) => self.transitions[idx].offset_to,
+            // Before the first onset, the offset before that onset is observed
+            None => self.transitions[0].offset_from,
+        }
+    }
+
+    /// Returns the offsets which give the local datetime, ordered by the UTC datetime they map to.
+    pub(crate) fn offsets_at_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        // An offset is less than a day, so the local datetime can only be given by the offsets
+        // which are observed within a day of it
+        let day = Duration::days(1);
+        let window_start = local.checked_sub_signed(day).unwrap_or(NaiveDateTime::MIN);
+        let window_end = local.checked_add_signed(day).unwrap_or(NaiveDateTime::MAX);
+        let first = self
+            .transitions
+            .partition_point(|transition| transition.at < window_start);
+        let last = self
+            .transitions
+            .partition_point(|transition| transition.at <= window_end);
+        let mut offsets = std::iter::once(self.offset_at_utc(&window_start))
+            .chain(
+                self.transitions[first..last]
+                    .iter()
+                    .map(|transition| transition.offset_to),
+            )
+            .collect::<Vec<_>>();
+        offsets.sort_by_key(|offset| std::cmp::Reverse(offset.local_minus_utc()));
+        offsets.dedup();
+
+        let mut valid = offsets.into_iter().filter(|offset| {
+            local
+                .checked_sub_signed(Duration::seconds(offset.local_minus_utc().into()))
+                .is_some_and(|utc| self.offset_at_utc(&utc) == *offset)
+        });
+        match (valid.next(), valid.next()) {
+            (Some(earliest), Some(latest)) => LocalResult::Ambiguous(earliest, latest),
+            (Some(offset), None) => LocalResult::Single(offset),
+            _ => LocalResult::None,
+        }
+    }
+
+    /// Stores the timezone for the rest of the program, or returns the stored timezone with the
+    /// same definition.
+    pub(crate) fn into_static(self) -> Result<&'static Self, ParseError> {
+        static STORE: OnceLock<Mutex<Store>> = OnceLock::new();
+
+        STORE
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(self, MAX_STORED_BYTES)
+    }
+
+    /// Returns about how many bytes the timezone takes up.
+    fn estimated_size(&self) -> usize {
+        let observances = self
+            .observances
+            .iter()
+            .map(|observance| {
+                size_of::<Observance>() + observance.rdates.len() * size_of::<NaiveDateTime>()
+            })
+            .sum::<usize>();
+        size_of::<Self>()
+            + self.tzid.len()
+            + observances
+            + self.transitions.len() * size_of::<Transition>()
+    }
+}
+
+/// The timezones which are stored for the rest of the program.
+#[derive(Default)]
+struct Store {
+    /// The timezones by a hash of their `TZID` and transitions, so only the timezones with the
+    /// same hash have to be compared.
+    timezones: HashMap<u64, Vec<&'static VTimezone>>,
+    /// The estimated size of the stored timezones.
+    bytes: usize,
+}
+
+impl Store {
+    /// Returns the stored timezone with the same definition, or stores the timezone if the
+    /// stored timezones take up no more than `max_bytes` with it.
+    fn insert(
+        &mut self,
+        timezone: VTimezone,
+        max_bytes: usize,
+    ) -> Result<&'static VTimezone, ParseError> {
+        let mut hasher = DefaultHasher::new();
+        timezone.tzid.hash(&mut hasher);
+        timezone.transitions.hash(&mut hasher);
+
+        let same_hash = self.timezones.entry(hasher.finish()).or_default();
+        if let Some(stored) = same_hash.iter().find(|stored| ***stored == timezone) {
+            return Ok(stored);
+        }
+        let bytes = self.bytes.saturating_add(timezone.estimated_size());
+        if bytes > max_bytes {
+            return Err(ParseError::TooManyTimezones(timezone.tzid));
+        }
+        self.bytes = bytes;
+        let timezone: &'static VTimezone = Box::leak(Box::new(timezone));
+        same_hash.push(timezone);
+        Ok(timezone)
+    }
+}
+
+/// Returns the onsets of an observance in the local time before the onset.
+fn observance_onsets(
+    tzid: &str,
+    observance: &Observance,
+) -> Result<Vec<NaiveDateTime>, RRuleError> {
+    let mut onsets = vec![observance.dt_start];
+    onsets.extend(&observance.rdates);
+
+    if let Some(rrule) = observance.rrule.clone() {
+        if rrule.freq != Frequency::Yearly {
+            return Err(ParseError::UnsupportedTimezoneObservance(tzid.into()).into());
+        }
+        // The recurrence is expanded in local time, which is represented as UTC here
+        let offset_from = Duration::seconds(observance.offset_from.local_minus_utc().into());
+        let until = rrule
+            .until
+            .map(|until| Tz::UTC.from_utc_datetime(&(until.naive_utc() + offset_from)));
+        let rrule_set =
+            RRule { until, ..rrule }.build(Tz::UTC.from_utc_datetime(&observance.dt_start))?;
+        let rrule_onsets = rrule_set
+            .into_iter()
+            .take_while(|onset: &DateTime<Tz>| onset.year() <= TRANSITIONS_UNTIL_YEAR)
+            .take(MAX_OBSERVANCE_ONSETS + 1)
+            .map(|onset| onset.naive_utc())
+            .collect::<Vec<_>>();
+        if rrule_onsets.len() > MAX_OBSERVANCE_ONSETS {
+            return Err(ParseError::UnsupportedTimezoneObservance(tzid.into()).into());
+        }
+        onsets.extend(rrule_onsets);
+    }
+    Ok(onsets)
+}
+
+impl FromStr for VTimezone {
+    type Err = RRuleError;
+
+    /// Creates a [`VTimezone`] from a `VTIMEZONE` component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the input doesn't contain exactly one valid `VTIMEZONE`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut timezones = parse_components(s)?.timezones.into_iter();
+        match (timezones.next(), timezones.next()) {
+            (Some(timezone), None) => timezone.into_vtimezone(),
+            _ => Err(ParseError::MissingProperty("VTIMEZONE".into()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Offset;
+
+    use super::*;
+
+    const NEW_YORK: &str = "BEGIN:VTIMEZONE\n\
+        TZID:America/New_York\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:19670430T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=-1SU;UNTIL=19730429T070000Z\n\
+        TZOFFSETFROM:-0500\n\
+        TZOFFSETTO:-0400\n\
+        TZNAME:EDT\n\
+        END:DAYLIGHT\n\
+        BEGIN:STANDARD\n\
+        DTSTART:19671029T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU;UNTIL=20061029T060000Z\n\
+        TZOFFSETFROM:-0400\n\
+        TZOFFSETTO:-0500\n\
+        TZNAME:EST\n\
+        END:STANDARD\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:19740106T020000\n\
+        RDATE:19750223T020000\n\
+        TZOFFSETFROM:-0500\n\
+        TZOFFSETTO:-0400\n\
+        TZNAME:EDT\n\
+        END:DAYLIGHT\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:19760425T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=-1SU;UNTIL=19860427T070000Z\n\
+        TZOFFSETFROM:-0500\n\
+        TZOFFSETTO:-0400\n\
+        TZNAME:EDT\n\
+        END:DAYLIGHT\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:19870405T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=1SU;UNTIL=20060402T070000Z\n\
+        TZOFFSETFROM:-0500\n\
+        TZOFFSETTO:-0400\n\
+        TZNAME:EDT\n\
+        END:DAYLIGHT\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:20070311T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\n\
+        TZOFFSETFROM:-0500\n\
+        TZOFFSETTO:-0400\n\
+        TZNAME:EDT\n\
+        END:DAYLIGHT\n\
+        BEGIN:STANDARD\n\
+        DTSTART:20071104T020000\n\
+        RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\n\
+        TZOFFSETFROM:-0400\n\
+        TZOFFSETTO:-0500\n\
+        TZNAME:EST\n\
+        END:STANDARD\n\
+        END:VTIMEZONE";
+
+    #[test]
+    fn matches_offsets_of_iana_timezone() {
+        let vtimezone: VTimezone = NEW_YORK.parse().unwrap();
+        let iana = chrono_tz::America::New_York;
+
+        // Every six hours from 1970 until 2040
+        let mut utc = NaiveDateTime::parse_from_str("1970-01-01 00:30", "%Y-%m-%d %H:%M").unwrap();
+        while utc.year() < 2040 {
+            assert_eq!(
+                vtimezone.offset_at_utc(&utc),
+                iana.offset_from_utc_datetime(&utc).fix(),
+                "{utc}"
+            );
+            utc += Duration::hours(6);
+        }
+    }
+
+    #[test]
+    fn resolves_local_datetimes_around_transitions() {
+        let vtimezone: VTimezone = NEW_YORK.parse().unwrap();
+        let local = |val: &str| NaiveDateTime::parse_from_str(val, "%Y-%m-%d %H:%M").unwrap();
+        let offset = |hours: i32| FixedOffset::east_opt(hours * 3600).unwrap();
+
+        assert_eq!(
+            vtimezone.offsets_at_local(&local("2021-03-14 01:30")),
+            LocalResult::Single(offset(-5))
+        );
+        assert_eq!(
+            vtimezone.offsets_at_local(&local("2021-03-14 02:30")),
+            LocalResult::None
+        );
+        assert_eq!(
+            vtimezone.offsets_at_local(&local("2021-11-07 01:30")),
+            LocalResult::Ambiguous(offset(-4), offset(-5))
+        );
+        // Before the first onset
+        assert_eq!(
+            vtimezone.offsets_at_local(&local("1960-01-01 00:00")),
+            LocalResult::Single(offset(-5))
+        );
+    }
+
+    #[test]
+    fn stores_equal_definitions_once() {
+        let first: VTimezone = NEW_YORK.parse().unwrap();
+        let second: VTimezone = NEW_YORK.parse().unwrap();
+        assert!(std::ptr::eq(
+            first.into_static().unwrap(),
+            second.into_static().unwrap()
+        ));
+    }
+
+    #[test]
+    fn stores_definitions_up_to_a_limit() {
+        let new_york: VTimezone = NEW_YORK.parse().unwrap();
+        let max_bytes = new_york.estimated_size();
+        let mut store = Store::default();
+
+        let first = store.insert(new_york.clone(), max_bytes).unwrap();
+        // An equal definition is stored already, so it doesn't count
+        let second = store.insert(new_york.clone(), max_bytes).unwrap();
+        assert!(std::ptr::eq(first, second));
+
+        let renamed = VTimezone {
+            tzid: "America/Detroit".into(),
+            ..new_york
+        };
+        assert_eq!(
+            store.insert(renamed, max_bytes),
+            Err(ParseError::TooManyTimezones("America/Detroit".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_vtimezone() {
+        let tests = [
+            (
+                "BEGIN:VTIMEZONE\nTZID:Empty\nEND:VTIMEZONE",
+                ParseError::MissingTimezoneObservance("Empty".into()),
+            ),
+            (
+                "BEGIN:VTIMEZONE\nBEGIN:STANDARD\nDTSTART:19671029T020000\n\
+                    TZOFFSETFROM:-0400\nTZOFFSETTO:-0500\nEND:STANDARD\nEND:VTIMEZONE",
+                ParseError::MissingProperty("TZID".into()),
+            ),
+            (
+                "BEGIN:VTIMEZONE\nTZID:Zone\nBEGIN:STANDARD\nDTSTART:19671029T020000\n\
+                    TZOFFSETTO:-0500\nEND:STANDARD\nEND:VTIMEZONE",
+                ParseError::MissingProperty("TZOFFSETFROM".into()),
+            ),
+            (
+                "BEGIN:VTIMEZONE\nTZID:Zone\nBEGIN:STANDARD\nDTSTART:19671029T020000\n\
+                    TZOFFSETFROM:-04\nTZOFFSETTO:-0500\nEND:STANDARD\nEND:VTIMEZONE",
+                ParseError::InvalidUtcOffset("-04".into()),
+            ),
+            (
+                "BEGIN:VEVENT\nEND:VEVENT",
+                ParseError::MissingProperty("VTIMEZONE".into()),
+            ),
+            (
+                "BEGIN:VTIMEZONE\nTZID:Zone\nBEGIN:STANDARD\nDTSTART:19000101T000000\n\
+                    TZOFFSETFROM:-0400\nTZOFFSETTO:-0500\nRRULE:FREQ=MINUTELY\n\
+                    END:STANDARD\nEND:VTIMEZONE",
+                ParseError::UnsupportedTimezoneObservance("Zone".into()),
+            ),
+            (
+                "BEGIN:VTIMEZONE\nTZID:Zone\nBEGIN:STANDARD\nDTSTART:19000101T000000\n\
+                    TZOFFSETFROM:-0400\nTZOFFSETTO:-0500\n\
+                    RRULE:FREQ=YEARLY;BYMONTH=1,2,3,4,5,6,7,8,9,10,11,12;BYHOUR=0,12;BYMINUTE=0,30\n\
+                    END:STANDARD\nEND:VTIMEZONE",
+                ParseError::UnsupportedTimezoneObservance("Zone".into()),
+            ),
+        ];
+        for (input, expected_error) in tests {
+            assert_eq!(
+                input.parse::<VTimezone>(),
+                Err(RRuleError::ParserError(expected_error))
+            );
+        }
+    }
+}
@@ -0,0 +1,717 @@
+//! Timezones loaded at runtime from TZif files, the format of the timezone database which is
+//! installed in `/usr/share/zoneinfo` on most Unix systems (RFC 8536).
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Weekday,
+};
+
+use super::{
+    timezone::Tz, timezone_provider::TimezoneProvider, vtimezone::TRANSITIONS_UNTIL_YEAR, VTimezone,
+};
+use crate::{parser::ParseError, RRuleError};
+
+/// A [`TimezoneProvider`] which loads timezones from the TZif files of a directory, like
+/// `/usr/share/zoneinfo`. A timezone is read once, when it is first looked up.
+///
+/// # Usage
+///
+/// ```no_run
+/// rrule::set_timezone_provider(rrule::ZoneinfoProvider::system());
+/// ```
+#[derive(Debug)]
+pub struct ZoneinfoProvider {
+    dir: PathBuf,
+    cache: Mutex<HashMap<String, Option<Tz>>>,
+}
+
+impl ZoneinfoProvider {
+    /// Creates a provider which reads the timezone `Europe/Berlin` from `dir/Europe/Berlin`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Creates a provider which reads the timezone database of the system in
+    /// `/usr/share/zoneinfo`.
+    #[must_use]
+    pub fn system() -> Self {
+        Self::new("/usr/share/zoneinfo")
+    }
+
+    fn load(&self, name: &str) -> Option<Tz> {
+        let data = fs::read(self.dir.join(name)).ok()?;
+        match VTimezone::from_tzif(name, &data).and_then(|vtimezone| Ok(vtimezone.try_into()?)) {
+            Ok(tz) => Some(tz),
+            Err(err) => {
+                log::warn!("Ignoring timezone file of `{name}`: {err}");
+                None
+            }
+        }
+    }
+}
+
+impl TimezoneProvider for ZoneinfoProvider {
+    fn timezone(&self, name: &str) -> Option<Tz> {
+        if !is_timezone_name(name) {
+            return None;
+        }
+        let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(timezone) = cache.get(name) {
+            return *timezone;
+        }
+        let timezone = self.load(name);
+        cache.insert(name.into(), timezone);
+        timezone
+    }
+}
+
+/// Checks if `name` can be a timezone name, which keeps `TZID`s from reading files outside of
+/// the timezone directory.
+fn is_timezone_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('/').all(|part| !part.is_empty() && part != "..")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-' | '/' | '.'))
+}
+
+impl VTimezone {
+    /// Creates a timezone from the contents of a TZif file, as found in `/usr/share/zoneinfo`.
+    /// The rule in the footer of the file is expanded until the year 2200.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if `data` isn't valid TZif data.
+    pub fn from_tzif(tzid: &str, data: &[u8]) -> Result<Self, RRuleError> {
+        let (initial, changes) =
+            parse_tzif(data).ok_or_else(|| ParseError::InvalidTzif(tzid.into()))?;
+        Ok(Self::from_transitions(tzid.into(), initial, &changes))
+    }
+}
+
+/// The counts of the header of a TZif data block.
+struct TzifHeader {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl TzifHeader {
+    /// Returns the length of the data block which follows the header.
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(taken)
+    }
+
+    /// Reads a big-endian signed integer of 4 or 8 bytes.
+    fn int(&mut self, size: usize) -> Option<i64> {
+        let bytes = self.take(size)?;
+        let unsigned = bytes
+            .iter()
+            .fold(0_u64, |value, byte| (value << 8) | u64::from(*byte));
+        // Sign extends from the size of the integer
+        let shift = 64 - 8 * size;
+        Some(((unsigned << shift) as i64) >> shift)
+    }
+
+    fn count(&mut self) -> Option<usize> {
+        usize::try_from(self.int(4)?).ok()
+    }
+
+    fn header(&mut self) -> Option<TzifHeader> {
+        if self.take(4)? != b"TZif" {
+            return None;
+        }
+        let version = self.take(1)?[0];
+        self.take(15)?;
+        Some(TzifHeader {
+            version,
+            isutcnt: self.count()?,
+            isstdcnt: self.count()?,
+            leapcnt: self.count()?,
+            timecnt: self.count()?,
+            typecnt: self.count()?,
+            charcnt: self.count()?,
+        })
+    }
+}
+
+/// Returns the initial offset and the changes of offset of TZif data.
+fn parse_tzif(data: &[u8]) -> Option<(FixedOffset, Vec<(NaiveDateTime, FixedOffset)>)> {
+    let mut reader = Reader { data };
+    let mut header = reader.header()?;
+    let mut time_size = 4;
+    if header.version >= b'2' {
+        // The version 1 block is followed by a block with 64-bit times
+        reader.take(header.block_len(time_size))?;
+        header = reader.header()?;
+        time_size = 8;
+    }
+
+    let times = (0..header.timecnt)
+        .map(|_| reader.int(time_size))
+        .collect::<Option<Vec<_>>>()?;
+    let type_indices = reader.take(header.timecnt)?;
+    let types = (0..header.typecnt)
+        .map(|_| {
+            let utoff = i32::try_from(reader.int(4)?).ok()?;
+            reader.take(2)?;
+            FixedOffset::east_opt(utoff)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    reader.take(
+        header.charcnt + header.leapcnt * (time_size + 4) + header.isstdcnt + header.isutcnt,
+    )?;
+
+    // Local time type 0 is observed before the first transition
+    let mut initial = *types.first()?;
+    let mut changes = vec![];
+    for (time, type_index) in times.into_iter().zip(type_indices) {
+        let offset = *types.get(usize::from(*type_index))?;
+        match DateTime::from_timestamp(time, 0) {
+            Some(at) => changes.push((at.naive_utc(), offset)),
+            // Transitions at the beginning of time only set the initial offset
+            None if time < 0 => initial = offset,
+            None => break,
+        }
+    }
+
+    if header.version >= b'2' {
+        let footer = std::str::from_utf8(reader.data).ok()?;
+        let footer = footer.strip_prefix('\n')?.split('\n').next()?;
+        if !footer.is_empty() {
+            let rule = parse_posix_tz(footer)?;
+            let last = changes.last().map(|(at, _)| *at);
+            let from_year = last.map_or(1900, |at| at.year());
+            changes.extend(
+                rule.changes(from_year)
+                    .into_iter()
+                    .filter(|(at, _)| last.map_or(true, |last| *at > last)),
+            );
+        }
+    }
+    Some((initial, changes))
+}
+
+/// A timezone described by a POSIX `TZ` string, like `EST5EDT,M3.2.0,M11.1.0`.
+#[derive(Debug, PartialEq)]
+struct PosixTz {
+    std: FixedOffset,
+    dst: Option<PosixDst>,
+}
+
+#[derive(Debug, PartialEq)]
+struct PosixDst {
+    offset: FixedOffset,
+    start: PosixRule,
+    end: PosixRule,
+}
+
+/// The date and local time of a change between standard and daylight saving time.
+#[derive(Debug, PartialEq)]
+struct PosixRule {
+    date: PosixDate,
+    /// Seconds since midnight, which can be negative or more than a day.
+    time: i32,
+}
+
+#[derive(Debug, PartialEq)]
+enum PosixDate {
+    /// `Jn`: the day of the year from 1 to 365, where February 29 is never counted.
+    Julian(u32),
+    /// `n`: the day of the year from 0 to 365, where February 29 is counted.
+    ZeroBased(u32),
+    /// `Mm.w.d`: weekday `d` of week `w` of month `m`, where week 5 is the last week.
+    Month {
+        month: u32,
+        week: u8,
+        weekday: Weekday,
+    },
+}
+
+impl PosixDate {
+    fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            Self::Julian(day) => {
+                let date = NaiveDate::from_yo_opt(year, day)?;
+                let is_leap_year = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                if is_leap_year && day >= 60 {
+                    date.succ_opt()
+                } else {
+                    Some(date)
+                }
+            }
+            Self::ZeroBased(day) => NaiveDate::from_yo_opt(year, day + 1),
+            Self::Month {
+                month,
+                week,
+                weekday,
+            } => NaiveDate::from_weekday_of_month_opt(year, month, weekday, week).or_else(|| {
+                // Not every month has a fifth weekday
+                (week == 5)
+                    .then(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+                    .flatten()
+            }),
+        }
+    }
+}
+
+impl PosixTz {
+    /// Returns the changes of offset from the start of `from_year` until the end of
+    /// [`TRANSITIONS_UNTIL_YEAR`].
+    fn changes(&self, from_year: i32) -> Vec<(NaiveDateTime, FixedOffset)> {
+        let Some(dst) = &self.dst else {
+            return vec![];
+        };
+        let mut changes = vec![];
+        for year in from_year..=TRANSITIONS_UNTIL_YEAR {
+            for (rule, before, after) in [
+                (&dst.start, self.std, dst.offset),
+                (&dst.end, dst.offset, self.std),
+            ] {
+                if let Some(date) = rule.date.in_year(year) {
+                    let local = date.and_time(NaiveTime::MIN) + Duration::seconds(rule.time.into());
+                    let at = local - Duration::seconds(before.local_minus_utc().into());
+                    changes.push((at, after));
+                }
+            }
+        }
+        // On the southern hemisphere, daylight saving time ends before it starts in a year
+        changes.sort_by_key(|(at, _)| *at);
+        changes
+    }
+}
+
+/// The offsets of a POSIX `TZ` string are at most a day from UTC.
+const MAX_OFFSET_HOURS: i32 = 24;
+/// The times of the rules of a POSIX `TZ` string are at most a week from midnight, see RFC 8536.
+const MAX_RULE_TIME_HOURS: i32 = 167;
+
+/// Parses a POSIX `TZ` string with the extensions of RFC 8536, like `EST5EDT,M3.2.0,M11.1.0`
+/// or `<+1030>-10:30<+11>-11,M10.1.0,M4.1.0`.
+fn parse_posix_tz(tz: &str) -> Option<PosixTz> {
+    let rest = skip_posix_name(tz)?;
+    let (std, rest) = parse_posix_time(rest, MAX_OFFSET_HOURS)?;
+    // Offsets are positive west of Greenwich
+    let std = FixedOffset::west_opt(std)?;
+    if rest.is_empty() {
+        return Some(PosixTz { std, dst: None });
+    }
+
+    let rest = skip_posix_name(rest)?;
+    let (offset, rest) = if rest.starts_with(',') {
+        (FixedOffset::east_opt(std.local_minus_utc() + 3600)?, rest)
+    } else {
+        let (offset, rest) = parse_posix_time(rest, MAX_OFFSET_HOURS)?;
+        (FixedOffset::west_opt(offset)?, rest)
+    };
+    let (start, end) = rest.strip_prefix(',')?.split_once(',')?;
+    Some(PosixTz {
+        std,
+        dst: Some(PosixDst {
+            offset,
+            start: parse_posix_rule(start)?,
+            end: parse_posix_rule(end)?,
+        }),
+    })
+}
+
+/// Skips the abbreviation of a timezone, like `EST` or `<+0530>`.
+fn skip_posix_name(tz: &str) -> Option<&str> {
+    if let Some(quoted) = tz.strip_prefix('<') {
+        let end = quoted.find('>')?;
+        return Some(&quoted[end + 1..]);
+    }
+    let len = tz
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(tz.len());
+    (len >= 3).then_some(&tz[len..])
+}
+
+/// Parses a time like `5`, `-3:30` or `25:00:00` to seconds, which is at most `max_hours`
+/// hours before or after midnight.
+fn parse_posix_time(tz: &str, max_hours: i32) -> Option<(i32, &str)> {
+    let len = tz
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | ':')))
+        .unwrap_or(tz.len());
+    let (time, rest) = tz.split_at(len);
+    let (sign, time) = match time.strip_prefix('-') {
+        Some(time) => (-1, time),
+        None => (1, time.strip_prefix('+').unwrap_or(time)),
+    };
+    let parts = time.split(':').collect::<Vec<_>>();
+    if parts.len() > 3 {
+        return None;
+    }
+    let mut seconds: i32 = 0;
+    for (part, unit) in parts.into_iter().zip([3600, 60, 1]) {
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        seconds = part
+            .parse::<i32>()
+            .ok()?
+            .checked_mul(unit)
+            .and_then(|part| seconds.checked_add(part))?;
+    }
+    (seconds <= max_hours * 3600).then_some((sign * seconds, rest))
+}
+
+/// Parses a rule like `M3.2.0`, `J60/3` or `M10.5.0/-1`.
+fn parse_posix_rule(rule: &str) -> Option<PosixRule> {
+    let (date, time) = match rule.split_once('/') {
+        Some((date, time)) => match parse_posix_time(time, MAX_RULE_TIME_HOURS)? {
+            (time, "") => (date, time),
+            _ => return None,
+        },
+        None => (rule, 2 * 3600),
+    };
+
+    let date = if let Some(day) = date.strip_prefix('J') {
+        PosixDate::Julian(day.parse().ok().filter(|day| (1..=365).contains(day))?)
+    } else if let Some(month) = date.strip_prefix('M') {
+        let mut parts = month.split('.').map(str::parse::<u8>);
+        let (Some(Ok(month)), Some(Ok(week)), Some(Ok(weekday)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        PosixDate::Month {
+            month: month.into(),
+            week,
+            // Weekday 0 is Sunday, while `Weekday` counts from Monday
+            weekday: Weekday::try_from((weekday + 6) % 7).ok()?,
+        }
+    } else {
+        PosixDate::ZeroBased(date.parse().ok().filter(|day| *day <= 365)?)
+    };
+    Some(PosixRule { date, time })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Offset, TimeZone};
+
+    use super::*;
+    use crate::core::{lookup_timezone, set_timezone_provider, ChronoTzProvider};
+
+    /// Builds version 2 TZif data with the given transitions, local time types and footer.
+    fn tzif(transitions: &[(i64, u8)], utoffs: &[i32], footer: &str) -> Vec<u8> {
+        let header = |data: &mut Vec<u8>| {
+            data.extend(b"TZif2");
+            data.extend([0; 15]);
+            for count in [0, 0, 0, transitions.len(), utoffs.len(), 0] {
+                data.extend(u32::try_from(count).unwrap().to_be_bytes());
+            }
+        };
+        let mut data = vec![];
+        header(&mut data);
+        for (time, _) in transitions {
+            data.extend(i32::try_from(*time).unwrap().to_be_bytes());
+        }
+        data.extend(transitions.iter().map(|(_, idx)| *idx));
+        for utoff in utoffs {
+            data.extend(utoff.to_be_bytes());
+            data.extend([0, 0]);
+        }
+        header(&mut data);
+        for (time, _) in transitions {
+            data.extend(time.to_be_bytes());
+        }
+        data.extend(transitions.iter().map(|(_, idx)| *idx));
+        for utoff in utoffs {
+            data.extend(utoff.to_be_bytes());
+            data.extend([0, 0]);
+        }
+        data.extend(format!("\n{footer}\n").bytes());
+        data
+    }
+
+    fn offset_at(tz: Tz, year: i32, month: u32, day: u32, hour: u32) -> i32 {
+        tz.with_ymd_and_hms(year, month, day, hour, 0, 0)
+            .unwrap()
+            .offset()
+            .fix()
+            .local_minus_utc()
+    }
+
+    #[test]
+    fn parses_tzif_data() {
+        // Local mean time until 1906-08-16, then the rule of the footer
+        let data = tzif(
+            &[(-2_000_000_000, 1)],
+            &[-17762, -18000],
+            "EST5EDT,M3.2.0,M11.1.0",
+        );
+        let tz = Tz::try_from(VTimezone::from_tzif("Test/New_York", &data).unwrap()).unwrap();
+
+        assert_eq!(tz.name(), "Test/New_York");
+        assert_eq!(offset_at(tz, 1800, 1, 1, 0), -17762);
+        assert_eq!(offset_at(tz, 2025, 1, 15, 12), -5 * 3600);
+        assert_eq!(offset_at(tz, 2025, 7, 15, 12), -4 * 3600);
+        assert_eq!(offset_at(tz, 2150, 7, 15, 12), -4 * 3600);
+        // The clocks are set forward at 2:00 on the second Sunday of March
+        assert_eq!(offset_at(tz, 2025, 3, 9, 1), -5 * 3600);
+        assert_eq!(offset_at(tz, 2025, 3, 9, 3), -4 * 3600);
+        assert!(tz.with_ymd_and_hms(2025, 3, 9, 2, 30, 0).single().is_none());
+    }
+
+    #[test]
+    fn parses_tzif_data_without_footer_rule() {
+        let data = tzif(&[], &[19800], "<+0530>-5:30");
+        let tz = Tz::try_from(VTimezone::from_tzif("Test/Kolkata", &data).unwrap()).unwrap();
+        assert_eq!(offset_at(tz, 2025, 7, 15, 12), 19800);
+    }
+
+    #[test]
+    fn rejects_invalid_tzif_data() {
+        let data = tzif(&[(0, 1)], &[3600], "CET-1CEST,M3.5.0,M10.5.0/3");
+        // The times of the footers are out of range
+        let rule_time = tzif(&[], &[3600], "CET-1CEST,M3.5.0/99999999,M10.5.0/3");
+        let offset = tzif(&[], &[3600], "CET99999999");
+        let tests = [
+            &b"TZif"[..],
+            &b"not a timezone"[..],
+            &data[..data.len() - 30],
+            // The type index of the transition doesn't exist
+            &data[..],
+            &rule_time[..],
+            &offset[..],
+        ];
+        for data in tests {
+            assert_eq!(
+                VTimezone::from_tzif("Test/Invalid", data),
+                Err(ParseError::InvalidTzif("Test/Invalid".into()).into())
+            );
+        }
+    }
+
+    #[test]
+    fn parses_posix_tz_strings() {
+        let offset = |seconds| FixedOffset::east_opt(seconds).unwrap();
+        let month = |month, week, weekday| PosixDate::Month {
+            month,
+            week,
+            weekday,
+        };
+        let tests = [
+            (
+                "EST5EDT,M3.2.0,M11.1.0",
+                PosixTz {
+                    std: offset(-5 * 3600),
+                    dst: Some(PosixDst {
+                        offset: offset(-4 * 3600),
+                        start: PosixRule {
+                            date: month(3, 2, Weekday::Sun),
+                            time: 2 * 3600,
+                        },
+                        end: PosixRule {
+                            date: month(11, 1, Weekday::Sun),
+                            time: 2 * 3600,
+                        },
+                    }),
+                },
+            ),
+            (
+                "<-02>2<-01>,M3.5.0/-1,M10.5.0/0",
+                PosixTz {
+                    std: offset(-2 * 3600),
+                    dst: Some(PosixDst {
+                        offset: offset(-3600),
+                        start: PosixRule {
+                            date: month(3, 5, Weekday::Sun),
+                            time: -3600,
+                        },
+                        end: PosixRule {
+                            date: month(10, 5, Weekday::Sun),
+                            time: 0,
+                        },
+                    }),
+                },
+            ),
+            (
+                "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0",
+                PosixTz {
+                    std: offset(10 * 3600 + 1800),
+                    dst: Some(PosixDst {
+                        offset: offset(11 * 3600),
+                        start: PosixRule {
+                            date: month(10, 1, Weekday::Sun),
+                            time: 2 * 3600,
+                        },
+                        end: PosixRule {
+                            date: month(4, 1, Weekday::Sun),
+                            time: 2 * 3600,
+                        },
+                    }),
+                },
+            ),
+            (
+                "EST5EDT,0/0,J365/25",
+                PosixTz {
+                    std: offset(-5 * 3600),
+                    dst: Some(PosixDst {
+                        offset: offset(-4 * 3600),
+                        start: PosixRule {
+                            date: PosixDate::ZeroBased(0),
+                            time: 0,
+                        },
+                        end: PosixRule {
+                            date: PosixDate::Julian(365),
+                            time: 25 * 3600,
+                        },
+                    }),
+                },
+            ),
+            (
+                "JST-9",
+                PosixTz {
+                    std: offset(9 * 3600),
+                    dst: None,
+                },
+            ),
+        ];
+        for (tz, expected) in tests {
+            assert_eq!(parse_posix_tz(tz), Some(expected), "{tz}");
+        }
+
+        for tz in [
+            "",
+            "E5",
+            "EST",
+            "EST5EDT",
+            "EST5EDT,M13.1.0,M1.1.0",
+            "EST5EDT,M3.2.7,J0",
+            "CET99999999",
+            "CET-1CEST,M3.5.0/99999999,M10.5.0/3",
+            "CET-1CEST,M3.5.0/168,M10.5.0/3",
+            "CET25",
+        ] {
+            assert_eq!(parse_posix_tz(tz), None, "{tz}");
+        }
+    }
+
+    #[test]
+    fn finds_dates_of_posix_rules() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day);
+        let last_sunday_of_march = PosixDate::Month {
+            month: 3,
+            week: 5,
+            weekday: Weekday::Sun,
+        };
+        assert_eq!(last_sunday_of_march.in_year(2025), date(2025, 3, 30));
+        assert_eq!(last_sunday_of_march.in_year(2026), date(2026, 3, 29));
+        // February 29 isn't counted by a Julian day, but it is by a zero-based day
+        assert_eq!(PosixDate::Julian(60).in_year(2024), date(2024, 3, 1));
+        assert_eq!(PosixDate::Julian(60).in_year(2025), date(2025, 3, 1));
+        assert_eq!(PosixDate::ZeroBased(59).in_year(2024), date(2024, 2, 29));
+        assert_eq!(PosixDate::ZeroBased(59).in_year(2025), date(2025, 3, 1));
+    }
+
+    #[test]
+    fn matches_chrono_tz_with_system_timezones() {
+        let provider = ZoneinfoProvider::system();
+        for name in ["America/New_York", "Europe/Dublin", "Australia/Sydney"] {
+            // Not every system has a timezone database
+            let Some(tz) = provider.timezone(name) else {
+                continue;
+            };
+            let chrono_tz = Tz::Tz(name.parse().unwrap());
+            // `chrono_tz` doesn't apply the rule of the footer after 2037
+            for year in [1950, 1990, 2025, 2037] {
+                for month in 1..=12 {
+                    assert_eq!(
+                        offset_at(tz, year, month, 15, 12),
+                        offset_at(chrono_tz, year, month, 15, 12),
+                        "{name} {year}-{month}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn looks_up_timezones_through_installed_provider() {
+        /// Knows `Test/Memory` from TZif data, and the timezones of `chrono_tz` otherwise.
+        struct MemoryProvider(Vec<u8>);
+
+        impl TimezoneProvider for MemoryProvider {
+            fn timezone(&self, name: &str) -> Option<Tz> {
+                match name {
+                    "Test/Memory" => VTimezone::from_tzif(name, &self.0)
+                        .ok()
+                        .and_then(|vtimezone| Tz::try_from(vtimezone).ok()),
+                    _ => ChronoTzProvider.timezone(name),
+                }
+            }
+        }
+
+        set_timezone_provider(MemoryProvider(tzif(&[], &[19800], "<+0530>-5:30")));
+        let tz = lookup_timezone("Test/Memory").unwrap();
+        assert_eq!(tz.name(), "Test/Memory");
+        assert_eq!(offset_at(tz, 2025, 7, 15, 12), 19800);
+        assert_eq!(lookup_timezone("Europe/Berlin"), Some(Tz::Europe__Berlin));
+
+        let rrule_set: crate::RRuleSet = "DTSTART;TZID=Test/Memory:20250101T090000\n\
+            RRULE:FREQ=DAILY;COUNT=1"
+            .parse()
+            .unwrap();
+        assert_eq!(rrule_set.get_dt_start().timezone(), tz);
+        assert_eq!(
+            rrule_set.get_dt_start().to_rfc3339(),
+            "2025-01-01T09:00:00+05:30"
+        );
+    }
+
+    #[test]
+    fn ignores_names_outside_of_timezone_directory() {
+        let provider = ZoneinfoProvider::system();
+        for name in [
+            "",
+            "../etc/passwd",
+            "/etc/passwd",
+            "Europe//Berlin",
+            "Europe\\Berlin",
+        ] {
+            assert!(!is_timezone_name(name), "{name}");
+            assert_eq!(provider.timezone(name), None);
+        }
+        assert!(is_timezone_name("America/Port-au-Prince"));
+        assert!(is_timezone_name("Etc/GMT+5"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use chrono::{Datelike, TimeZone, Timelike, Utc, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc, Weekday};
 
 use crate::{Frequency, RRule, RRuleError, Tz};
 
@@ -57,6 +57,133 @@ impl DateTimeIter {
         }
     }
 
+    /// Returns the index of the period which contains `dt`, counted in units of the
+    /// frequency from the period which contains `dt_start`.
+    ///
+    /// The index is negative if `dt` is before the period of `dt_start`.
+    /// The index doesn't take the interval into account, so the period might not be
+    /// one which is visited during iteration.
+    pub fn period_index(
+        rrule: &RRule,
+        dt_start: &chrono::DateTime<Tz>,
+        dt: &chrono::DateTime<Tz>,
+    ) -> i64 {
+        let start = dt_start.naive_local();
+        let dt = dt.with_timezone(&dt_start.timezone()).naive_local();
+        Self::naive_period_index(rrule, start, dt)
+    }
+
+    /// Same as [`Self::period_index`], for datetimes in the local time of `dt_start`.
+    pub fn naive_period_index(rrule: &RRule, start: NaiveDateTime, dt: NaiveDateTime) -> i64 {
+        match rrule.freq {
+            Frequency::Yearly => i64::from(dt.year()) - i64::from(start.year()),
+            Frequency::Monthly => {
+                (i64::from(dt.year()) - i64::from(start.year())) * 12 + i64::from(dt.month())
+                    - i64::from(start.month())
+            }
+            Frequency::Weekly => {
+                let week_start = |date: NaiveDate| {
+                    let days = (i64::from(date.weekday().num_days_from_monday())
+                        - i64::from(rrule.week_start.num_days_from_monday()))
+                    .rem_euclid(7);
+                    date - Duration::days(days)
+                };
+                (week_start(dt.date()) - week_start(start.date())).num_days() / 7
+            }
+            Frequency::Daily => (dt.date() - start.date()).num_days(),
+            Frequency::Hourly => {
+                let truncate = |dt: NaiveDateTime| dt.date().and_hms_opt(dt.hour(), 0, 0);
+                match (truncate(dt), truncate(start)) {
+                    (Some(dt), Some(start)) => (dt - start).num_hours(),
+                    _ => 0,
+                }
+            }
+            Frequency::Minutely => {
+                let truncate = |dt: NaiveDateTime| dt.date().and_hms_opt(dt.hour(), dt.minute(), 0);
+                match (truncate(dt), truncate(start)) {
+                    (Some(dt), Some(start)) => (dt - start).num_minutes(),
+                    _ => 0,
+                }
+            }
+            Frequency::Secondly => (dt - start).num_seconds(),
+        }
+    }
+
+    /// Creates the counter date of the period which is `index` periods, in units of the frequency,
+    /// after the period of `dt_start`. This is the same counter date which is reached by incrementing
+    /// from `dt_start`, if `index` is a multiple of the interval.
+    pub fn from_period_index(
+        rrule: &RRule,
+        dt_start: &chrono::DateTime<Tz>,
+        index: i64,
+    ) -> Result<Self, RRuleError> {
+        let mut counter_date = Self::from(dt_start);
+        if index == 0 {
+            return Ok(counter_date);
+        }
+
+        let out_of_range = || RRuleError::new_iter_err("The period is out of the supported range");
+        let start = dt_start.naive_local();
+        match rrule.freq {
+            Frequency::Yearly => {
+                counter_date.year =
+                    i32::try_from(i64::from(start.year()) + index).map_err(|_| out_of_range())?;
+                checks::check_year_range(counter_date.year)?;
+                counter_date.fix_day()?;
+            }
+            Frequency::Monthly => {
+                let months = i64::from(start.year()) * 12 + i64::from(start.month0()) + index;
+                counter_date.year =
+                    i32::try_from(months.div_euclid(12)).map_err(|_| out_of_range())?;
+                counter_date.month =
+                    u32::try_from(months.rem_euclid(12) + 1).expect("range 1-12 is covered by u32");
+                checks::check_year_range(counter_date.year)?;
+            }
+            Frequency::Weekly => {
+                let days = (i64::from(start.weekday().num_days_from_monday())
+                    - i64::from(rrule.week_start.num_days_from_monday()))
+                .rem_euclid(7);
+                let date = Duration::try_days(index.checked_mul(7).ok_or_else(out_of_range)?)
+                    .and_then(|duration| start.checked_add_signed(duration - Duration::days(days)))
+                    .ok_or_else(out_of_range)?;
+                counter_date = Self::from_naive(&date);
+            }
+            Frequency::Daily => {
+                let date = Duration::try_days(index)
+                    .and_then(|duration| start.checked_add_signed(duration))
+                    .ok_or_else(out_of_range)?;
+                counter_date = Self::from_naive(&date);
+            }
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => {
+                let unit = match rrule.freq {
+                    Frequency::Hourly => 60 * 60,
+                    Frequency::Minutely => 60,
+                    _ => 1,
+                };
+                let date = index
+                    .checked_mul(unit)
+                    .and_then(Duration::try_seconds)
+                    .and_then(|duration| start.checked_add_signed(duration))
+                    .ok_or_else(out_of_range)?;
+                counter_date = Self::from_naive(&date);
+            }
+        }
+        checks::check_year_range(counter_date.year)?;
+
+        Ok(counter_date)
+    }
+
+    fn from_naive(dt: &NaiveDateTime) -> Self {
+        Self {
+            year: dt.year(),
+            month: dt.month(),
+            day: dt.day(),
+            hour: dt.hour(),
+            minute: dt.minute(),
+            second: dt.second(),
+        }
+    }
+
     fn increment_yearly(&mut self, interval: u16) -> Result<(), RRuleError> {
         self.year += i32::from(interval);
         checks::check_year_range(self.year)?;
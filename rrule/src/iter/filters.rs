@@ -1,4 +1,5 @@
-use crate::{NWeekday, RRule};
+use crate::calendar::CalendarMonth;
+use crate::{NWeekday, RRule, Skip};
 
 use super::iterinfo::IterInfo;
 
@@ -21,7 +22,28 @@ pub(crate) fn is_filtered(ii: &IterInfo, current_day: usize) -> bool {
         .any(|filter| filter(ii, current_day, rrule))
 }
 
+/// The filters which still apply to a day that an invalid month day is rolled to by `SKIP`.
+/// The month and month day filters already selected the invalid month day itself.
+const ROLLED_DAY_FILTERS: [RRuleFilter; 5] = [
+    &is_filtered_by_week_number,
+    &is_filtered_by_weekday,
+    &is_filtered_by_neg_weekday,
+    &is_filtered_by_easter,
+    &is_filtered_by_year_day,
+];
+
+pub(crate) fn is_rolled_day_filtered(ii: &IterInfo, current_day: usize) -> bool {
+    let rrule = ii.rrule();
+    ROLLED_DAY_FILTERS
+        .into_iter()
+        .any(|filter| filter(ii, current_day, rrule))
+}
+
 fn is_filtered_by_month(ii: &IterInfo, current_day: usize, rrule: &RRule) -> bool {
+    if let Some(calendar_month_mask) = ii.calendar_month_mask() {
+        return is_calendar_month_filtered(&calendar_month_mask[current_day], rrule);
+    }
+
     if rrule.by_month.is_empty() {
         return false;
     }
@@ -30,6 +52,29 @@ fn is_filtered_by_month(ii: &IterInfo, current_day: usize, rrule: &RRule) -> boo
     !rrule.by_month.contains(&current_month)
 }
 
+pub(crate) fn is_calendar_month_filtered(month: &CalendarMonth, rrule: &RRule) -> bool {
+    if rrule.by_month.is_empty() && rrule.by_leap_month.is_empty() {
+        return false;
+    }
+    if month.leap {
+        return !rrule.by_leap_month.contains(&month.month);
+    }
+    if rrule.by_month.contains(&month.month) {
+        return false;
+    }
+
+    // `SKIP` replaces a leap month which the year doesn't have by the month before or
+    // after it.
+    let is_missing_leap_month = |leap_month: u8| {
+        rrule.by_leap_month.contains(&leap_month) && month.year_leap_month != Some(leap_month)
+    };
+    match rrule.skip {
+        Skip::Omit => true,
+        Skip::Backward => !is_missing_leap_month(month.month),
+        Skip::Forward => month.month == 1 || !is_missing_leap_month(month.month - 1),
+    }
+}
+
 fn is_filtered_by_week_number(ii: &IterInfo, current_day: usize, rrule: &RRule) -> bool {
     if rrule.by_week_no.is_empty() {
         return false;
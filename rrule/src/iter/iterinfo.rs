@@ -2,6 +2,7 @@ use super::counter_date::DateTimeIter;
 #[cfg(feature = "by-easter")]
 use super::easter::easter;
 use super::{monthinfo::MonthInfo, yearinfo::YearInfo};
+use crate::calendar::CalendarMonth;
 use crate::core::get_month;
 use crate::{Frequency, NWeekday, RRule, Tz};
 use chrono::{Datelike, NaiveTime, TimeZone};
@@ -32,9 +33,7 @@ impl IterInfo {
     }
 
     fn rebuild_inner(&mut self, year: i32, month: u8, skip_year_info: bool) {
-        if !skip_year_info
-            && !matches!(&self.month_info, Some(month_info) if month_info.last_year == year)
-        {
+        if !skip_year_info && self.year_info.year != year {
             self.year_info = YearInfo::new(year, &self.rrule);
         }
 
@@ -102,11 +101,24 @@ impl IterInfo {
     }
 
     pub fn month_day_mask(&self) -> &[i8] {
-        self.year_info.month_day_mask
+        match &self.year_info.calendar {
+            Some(calendar) => &calendar.month_day_mask,
+            None => self.year_info.month_day_mask,
+        }
     }
 
     pub fn neg_month_day_mask(&self) -> &[i8] {
-        self.year_info.neg_month_day_mask
+        match &self.year_info.calendar {
+            Some(calendar) => &calendar.neg_month_day_mask,
+            None => self.year_info.neg_month_day_mask,
+        }
+    }
+
+    pub fn calendar_month_mask(&self) -> Option<&[CalendarMonth]> {
+        self.year_info
+            .calendar
+            .as_ref()
+            .map(|calendar| calendar.month_mask.as_slice())
     }
 
     pub fn year_dayset(&self) -> Vec<usize> {
@@ -199,9 +211,77 @@ impl IterInfo {
         // Filter out days according to the RRule filters.
         dayset.retain(|day| !super::filters::is_filtered(self, *day));
 
+        if !self.year_info.rolled_month_days.is_empty() {
+            self.add_rolled_month_days(freq, month, &mut dayset);
+        }
+        if let Some(calendar) = &self.year_info.calendar {
+            if !calendar.rolled_days.is_empty() {
+                self.add_calendar_rolled_days(&calendar.rolled_days, freq, month, &mut dayset);
+            }
+        }
+
         dayset
     }
 
+    /// Adds the days of the current period that `SKIP` moves the invalid month days of a
+    /// non-Gregorian calendar to.
+    fn add_calendar_rolled_days(
+        &self,
+        rolled_days: &[usize],
+        freq: Frequency,
+        month: u32,
+        dayset: &mut Vec<usize>,
+    ) {
+        let period = match freq {
+            Frequency::Yearly => 0..usize::from(self.year_len()),
+            Frequency::Monthly => {
+                let month =
+                    usize::try_from(month).expect("target arch should have at least 32 bits");
+                usize::from(self.month_range()[month - 1])..usize::from(self.month_range()[month])
+            }
+            _ => return,
+        };
+        dayset.extend(rolled_days.iter().copied().filter(|day| {
+            period.contains(day) && !super::filters::is_rolled_day_filtered(self, *day)
+        }));
+
+        dayset.sort_unstable();
+        dayset.dedup();
+    }
+
+    /// Adds the days that `SKIP` moves the invalid month days of the current period to.
+    fn add_rolled_month_days(&self, freq: Frequency, month: u32, dayset: &mut Vec<usize>) {
+        let by_month = &self.rrule.by_month;
+        for &(invalid_in_month, day) in &self.year_info.rolled_month_days {
+            let in_period = match freq {
+                Frequency::Yearly => true,
+                Frequency::Monthly => u32::from(invalid_in_month) == month,
+                _ => false,
+            };
+            if !in_period
+                || !(by_month.is_empty() || by_month.contains(&invalid_in_month))
+                || super::filters::is_rolled_day_filtered(self, day)
+            {
+                continue;
+            }
+
+            // A day rolled into the next or previous month is already generated by the period
+            // of that month, if it is a valid occurrence there as well.
+            if freq == Frequency::Monthly
+                && self.rrule.interval == 1
+                && self.month_mask()[day] != invalid_in_month
+                && !super::filters::is_filtered(self, day)
+            {
+                continue;
+            }
+
+            dayset.push(day);
+        }
+
+        dayset.sort_unstable();
+        dayset.dedup();
+    }
+
     /// Gets a timeset without checking if the hour, minute and second are valid, according
     /// to the `RRule`.
     ///
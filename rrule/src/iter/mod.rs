@@ -7,17 +7,22 @@ pub(crate) mod filters;
 pub(crate) mod iterinfo;
 mod masks;
 mod monthinfo;
+mod occurrence_iter;
 mod operation_errors;
 mod pos_list;
 pub(crate) mod rrule_iter;
+mod rrule_rev_iter;
 mod rruleset_iter;
+mod rruleset_rev_iter;
 mod utils;
 mod yearinfo;
 
 use iterinfo::IterInfo;
+pub use occurrence_iter::OccurrenceIter;
 use pos_list::build_pos_list;
 pub(crate) use rrule_iter::RRuleIter;
 pub use rruleset_iter::RRuleSetIter;
+pub use rruleset_rev_iter::RRuleSetRevIter;
 
 /// Prevent loops when searching for the next event in the iterator.
 /// If after X number of iterations it still has not found an event,
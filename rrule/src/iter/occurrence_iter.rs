@@ -0,0 +1,45 @@
+use chrono::Duration;
+
+use super::rrule_iter::WasLimited;
+use super::RRuleSetIter;
+use crate::core::{add_duration_clamped, Occurrence};
+
+#[derive(Debug, Clone)]
+/// Iterator over all the occurrences in an [`RRuleSet`](crate::RRuleSet), with their start and end.
+pub struct OccurrenceIter {
+    iter: RRuleSetIter,
+    /// The duration of every occurrence which doesn't come from a period.
+    /// An end after the latest datetime of `chrono` becomes that datetime.
+    duration: Duration,
+}
+
+impl OccurrenceIter {
+    pub(crate) fn new(iter: RRuleSetIter, duration: Duration) -> Self {
+        Self { iter, duration }
+    }
+}
+
+impl Iterator for OccurrenceIter {
+    type Item = Occurrence;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_generated().map(|generated| {
+            let start = generated.date;
+            let end = generated.period.map_or_else(
+                || add_duration_clamped(&start, self.duration),
+                |period| period.get_end(),
+            );
+            Occurrence {
+                start,
+                end,
+                recurrence_id: generated.recurrence_id,
+            }
+        })
+    }
+}
+
+impl WasLimited for OccurrenceIter {
+    fn was_limited(&self) -> bool {
+        self.iter.was_limited()
+    }
+}
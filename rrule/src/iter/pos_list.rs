@@ -1,5 +1,5 @@
 use super::utils::{add_time_to_date, date_from_ordinal, pymod};
-use crate::core::Tz;
+use crate::core::{resolved_datetimes, DstPolicy, Tz};
 use chrono::NaiveTime;
 
 pub(crate) fn build_pos_list(
@@ -8,6 +8,7 @@ pub(crate) fn build_pos_list(
     timeset: &[NaiveTime],
     year_ordinal: i64,
     tz: Tz,
+    dst: DstPolicy,
 ) -> Vec<chrono::DateTime<Tz>> {
     let mut pos_list = vec![];
 
@@ -49,13 +50,10 @@ pub(crate) fn build_pos_list(
         // Create new Date + Time combination
         // Use Time from `timeset`.
         let time = timeset[time_pos];
-        let res = match add_time_to_date(tz, date, time) {
-            Some(date) => date,
-            None => continue,
-        };
-
-        if !pos_list.contains(&res) {
-            pos_list.push(res);
+        for res in resolved_datetimes(add_time_to_date(tz, date, time, dst)) {
+            if !pos_list.contains(&res) {
+                pos_list.push(res);
+            }
         }
     }
 
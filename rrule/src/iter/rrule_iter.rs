@@ -1,9 +1,9 @@
 use super::counter_date::DateTimeIter;
 use super::utils::add_time_to_date;
 use super::{build_pos_list, utils::date_from_ordinal, IterInfo, MAX_ITER_LOOP};
-use crate::core::{get_hour, get_minute, get_second};
+use crate::core::{get_hour, get_minute, get_second, resolved_datetimes, DstPolicy};
 use crate::{Frequency, RRule, Tz};
-use chrono::{Datelike, NaiveTime, TimeZone, Timelike};
+use chrono::{LocalResult, NaiveTime};
 use std::collections::VecDeque;
 
 #[derive(Debug, Clone)]
@@ -25,10 +25,17 @@ pub(crate) struct RRuleIter {
     pub(crate) limited: bool,
     /// If the iterator has been stopped by the iterator limits.
     pub(crate) was_limited: bool,
+    /// How local times in daylight saving time transitions are resolved.
+    pub(crate) dst: DstPolicy,
 }
 
 impl RRuleIter {
-    pub(crate) fn new(rrule: &RRule, dt_start: &chrono::DateTime<Tz>, limited: bool) -> Self {
+    pub(crate) fn new(
+        rrule: &RRule,
+        dt_start: &chrono::DateTime<Tz>,
+        limited: bool,
+        dst: DstPolicy,
+    ) -> Self {
         let ii = IterInfo::new(rrule, dt_start);
 
         let hour = get_hour(dt_start);
@@ -47,6 +54,7 @@ impl RRuleIter {
             count,
             limited,
             was_limited: false,
+            dst,
         };
 
         // If X-INCLUDE-DTSTART=TRUE, force include DTSTART at the beginning
@@ -56,27 +64,73 @@ impl RRuleIter {
                 // Apply LOCAL-TZID to DTSTART
                 if rrule.dtstart_is_floating {
                     // For floating datetimes, create datetime in LOCAL-TZID timezone
-                    local_tzid
-                        .with_ymd_and_hms(dt_start.year(), dt_start.month(), dt_start.day(), 
-                                          dt_start.hour(), dt_start.minute(), dt_start.second())
-                        .single()
-                        .unwrap_or(dt_start.with_timezone(&local_tzid))
+                    dst.resolve(local_tzid, dt_start.naive_local())
                 } else if dt_start.timezone() != Tz::UTC
                     && Self::should_apply_local_tzid(*dt_start, dt_start)
                 {
-                    dt_start.with_timezone(&local_tzid)
+                    LocalResult::Single(dt_start.with_timezone(&local_tzid))
                 } else {
-                    *dt_start
+                    LocalResult::Single(*dt_start)
                 }
             } else {
-                *dt_start
+                LocalResult::Single(*dt_start)
             };
-            new_iter.buffer.push_back(output_dt_start);
+            new_iter.buffer.extend(resolved_datetimes(output_dt_start));
         }
 
         new_iter
     }
 
+    /// Moves the iterator to the period which contains `dt`, without generating the periods
+    /// before it. This has to be called before the iteration starts.
+    ///
+    /// Dates of that period which are before `dt` are still returned.
+    /// Rules with `COUNT` are not moved, since all the dates before `dt` have to be counted,
+    /// and neither are rules with irregular periods.
+    pub(crate) fn seek(&mut self, dt: &chrono::DateTime<Tz>) {
+        let rrule = self.ii.rrule();
+        if rrule.count.is_some() || Self::has_irregular_periods(rrule) || rrule.interval == 0 {
+            return;
+        }
+
+        let interval = i64::from(rrule.interval);
+        let index =
+            DateTimeIter::period_index(rrule, &self.dt_start, dt).div_euclid(interval) * interval;
+        if index <= 0 {
+            return;
+        }
+        let Ok(counter_date) = DateTimeIter::from_period_index(rrule, &self.dt_start, index) else {
+            self.finished = true;
+            return;
+        };
+
+        if matches!(
+            rrule.freq,
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly
+        ) {
+            let hour = u8::try_from(counter_date.hour).expect("range 0-23 is covered by u8");
+            let minute = u8::try_from(counter_date.minute).expect("range 0-59 is covered by u8");
+            let second = u8::try_from(counter_date.second).expect("range 0-59 is covered by u8");
+            self.timeset = self.ii.get_timeset(hour, minute, second);
+        }
+        self.ii.rebuild(&counter_date);
+        self.counter_date = counter_date;
+        // Only DTSTART can be buffered at this point, which is before `dt`.
+        self.buffer.clear();
+    }
+
+    /// Checks if the periods visited by the iterator aren't a multiple of the interval apart.
+    ///
+    /// When `BYHOUR` or `BYMINUTE` skip the next period of a minutely or secondly rule,
+    /// the iterator continues from the next allowed hour or minute instead.
+    pub(crate) fn has_irregular_periods(rrule: &RRule) -> bool {
+        match rrule.freq {
+            Frequency::Minutely => !rrule.by_hour.is_empty(),
+            Frequency::Secondly => !rrule.by_hour.is_empty() || !rrule.by_minute.is_empty(),
+            _ => false,
+        }
+    }
+
     /// Attempts to add a date to the result. Returns `true` if we should
     /// terminate the iteration.
     fn try_add_datetime(
@@ -85,6 +139,7 @@ impl RRuleIter {
         count: &mut Option<u32>,
         buffer: &mut VecDeque<chrono::DateTime<Tz>>,
         dt_start: &chrono::DateTime<Tz>,
+        dst: DstPolicy,
     ) -> bool {
         if matches!(rrule.until, Some(until) if dt > until) {
             // We can break because `pos_list` is sorted and
@@ -100,39 +155,83 @@ impl RRuleIter {
                 return false;
             }
 
-            // Apply LOCAL-TZID conversion to output dates when appropriate
-            let output_dt = if let Some(local_tzid) = rrule.local_tzid {
-                // Special handling for floating datetimes
-                // If DTSTART was originally floating, treat all generated times as being in LOCAL-TZID timezone
-                if rrule.dtstart_is_floating {
-                    // Create a new datetime with the same date and time but in the LOCAL-TZID timezone
-                    local_tzid
-                        .with_ymd_and_hms(dt.year(), dt.month(), dt.day(), dt.hour(), dt.minute(), dt.second())
-                        .single()
-                        .unwrap_or(dt.with_timezone(&local_tzid))
-                } else if dt.timezone() != Tz::UTC && Self::should_apply_local_tzid(dt, dt_start) {
-                    // For floating datetimes, convert from current timezone to LOCAL-TZID
-                    dt.with_timezone(&local_tzid)
-                } else {
-                    dt
-                }
-            } else {
-                dt
-            };
-
-            buffer.push_back(output_dt);
+            for output_dt in resolved_datetimes(Self::output_datetime(dt, rrule, dt_start, dst)) {
+                buffer.push_back(output_dt);
 
-            if let Some(count) = count {
-                *count -= 1;
+                if let Some(count) = count {
+                    *count -= 1;
 
-                if *count == 0 {
-                    return true;
+                    if *count == 0 {
+                        return true;
+                    }
                 }
             }
         }
         false
     }
 
+    /// Applies the LOCAL-TZID conversion to a generated datetime when appropriate.
+    /// The local time of a floating datetime is resolved in LOCAL-TZID with `dst`.
+    pub(crate) fn output_datetime(
+        dt: chrono::DateTime<Tz>,
+        rrule: &RRule,
+        dt_start: &chrono::DateTime<Tz>,
+        dst: DstPolicy,
+    ) -> LocalResult<chrono::DateTime<Tz>> {
+        if let Some(local_tzid) = rrule.local_tzid {
+            // Special handling for floating datetimes
+            // If DTSTART was originally floating, treat all generated times as being in LOCAL-TZID timezone
+            if rrule.dtstart_is_floating {
+                // Create a new datetime with the same date and time but in the LOCAL-TZID timezone
+                dst.resolve(local_tzid, dt.naive_local())
+            } else if dt.timezone() != Tz::UTC && Self::should_apply_local_tzid(dt, dt_start) {
+                // For floating datetimes, convert from current timezone to LOCAL-TZID
+                LocalResult::Single(dt.with_timezone(&local_tzid))
+            } else {
+                LocalResult::Single(dt)
+            }
+        } else {
+            LocalResult::Single(dt)
+        }
+    }
+
+    /// Returns all the datetimes in the given `dayset` of the current period, in ascending order.
+    /// `DTSTART`, `UNTIL` and `COUNT` are not applied.
+    pub(crate) fn period_dates(
+        ii: &IterInfo,
+        dayset: &[usize],
+        timeset: &[NaiveTime],
+        tz: Tz,
+        dst: DstPolicy,
+    ) -> Vec<chrono::DateTime<Tz>> {
+        let rrule = ii.rrule();
+        if !rrule.by_set_pos.is_empty() {
+            return build_pos_list(
+                &rrule.by_set_pos,
+                dayset,
+                timeset,
+                ii.year_ordinal(),
+                tz,
+                dst,
+            );
+        }
+
+        let mut dates = vec![];
+        // Loop over `start..end`
+        for current_day in dayset {
+            let current_day = i64::try_from(*current_day)
+                .expect("We control the dayset, and we know that it will always fit within an i64");
+            let year_ordinal = ii.year_ordinal();
+            // Ordinal conversion uses UTC: if we apply local-TZ here, then
+            // just below we'll end up double-applying.
+            let date = date_from_ordinal(year_ordinal + current_day);
+            for time in timeset {
+                dates.extend(resolved_datetimes(add_time_to_date(tz, date, *time, dst)));
+            }
+        }
+        dates
+    }
+
     /// Generates a list of dates that will be added to the buffer.
     /// Returns true if finished, no more items should/can be returned.
     fn generate(&mut self) -> bool {
@@ -177,51 +276,23 @@ impl RRuleIter {
                 self.counter_date.day,
             );
 
-            let tz = self.dt_start.timezone();
-
-            if rrule.by_set_pos.is_empty() {
-                // Loop over `start..end`
-                for current_day in &dayset {
-                    let current_day = i64::try_from(*current_day).expect(
-                        "We control the dayset, and we know that it will always fit within an i64",
-                    );
-                    let year_ordinal = self.ii.year_ordinal();
-                    // Ordinal conversion uses UTC: if we apply local-TZ here, then
-                    // just below we'll end up double-applying.
-                    let date = date_from_ordinal(year_ordinal + current_day);
-                    for time in &self.timeset {
-                        let Some(dt) = add_time_to_date(tz, date, *time) else {
-                            continue;
-                        };
-                        if Self::try_add_datetime(
-                            dt,
-                            rrule,
-                            &mut self.count,
-                            &mut self.buffer,
-                            &self.dt_start,
-                        ) {
-                            return true;
-                        }
-                    }
-                }
-            } else {
-                let pos_list = build_pos_list(
-                    &rrule.by_set_pos,
-                    &dayset,
-                    &self.timeset,
-                    self.ii.year_ordinal(),
-                    self.dt_start.timezone(),
-                );
-                for dt in pos_list {
-                    if Self::try_add_datetime(
-                        dt,
-                        rrule,
-                        &mut self.count,
-                        &mut self.buffer,
-                        &self.dt_start,
-                    ) {
-                        return true;
-                    }
+            let dates = Self::period_dates(
+                &self.ii,
+                &dayset,
+                &self.timeset,
+                self.dt_start.timezone(),
+                self.dst,
+            );
+            for dt in dates {
+                if Self::try_add_datetime(
+                    dt,
+                    rrule,
+                    &mut self.count,
+                    &mut self.buffer,
+                    &self.dt_start,
+                    self.dst,
+                ) {
+                    return true;
                 }
             }
 
@@ -0,0 +1,225 @@
+use super::counter_date::DateTimeIter;
+use super::rrule_iter::{RRuleIter, WasLimited};
+use super::{IterInfo, MAX_ITER_LOOP};
+use crate::core::{get_hour, get_minute, get_second, resolved_datetimes, DstPolicy};
+use crate::{Frequency, RRule, RRuleError, Tz};
+use chrono::{NaiveDate, NaiveTime};
+
+/// Iterates over the dates of an [`RRule`] in reverse order, starting from an upper bound.
+///
+/// The periods of the rule are visited backwards, one interval at a time,
+/// and the dates within a period are built with the same masks as [`RRuleIter`].
+#[derive(Debug, Clone)]
+pub(crate) struct RRuleRevIter {
+    ii: IterInfo,
+    /// The timeset of rules with a frequency of daily or lower.
+    timeset: Vec<NaiveTime>,
+    dt_start: chrono::DateTime<Tz>,
+    /// The upper bound of the returned dates (inclusive).
+    bound: Option<chrono::DateTime<Tz>>,
+    /// The index of the period that will be generated next, see [`DateTimeIter::period_index`].
+    index: i64,
+    /// Buffer of datetimes not yet yielded, in ascending order.
+    buffer: Vec<chrono::DateTime<Tz>>,
+    /// Indicate of iterator should not return more items.
+    finished: bool,
+    /// If the iterator should be using iterator limits.
+    limited: bool,
+    /// If the iterator has been stopped by the iterator limits.
+    was_limited: bool,
+    /// How local times in daylight saving time transitions are resolved.
+    dst: DstPolicy,
+}
+
+impl RRuleRevIter {
+    /// Creates a reverse iterator over the dates of `rrule` which are before or at `bound`.
+    ///
+    /// Returns an error if neither `bound`, `UNTIL` nor `COUNT` limit the rule.
+    pub(crate) fn new(
+        rrule: &RRule,
+        dt_start: &chrono::DateTime<Tz>,
+        bound: Option<chrono::DateTime<Tz>>,
+        limited: bool,
+        dst: DstPolicy,
+    ) -> Result<Self, RRuleError> {
+        let ii = IterInfo::new(rrule, dt_start);
+        let mut rev_iter = Self {
+            timeset: ii.get_timeset(
+                get_hour(dt_start),
+                get_minute(dt_start),
+                get_second(dt_start),
+            ),
+            ii,
+            dt_start: *dt_start,
+            bound,
+            index: -1,
+            buffer: vec![],
+            finished: false,
+            limited,
+            was_limited: false,
+            dst,
+        };
+
+        let upper = match (bound, rrule.until) {
+            (Some(bound), Some(until)) => Some(bound.min(until)),
+            (bound, until) => bound.or(until),
+        };
+        if upper.is_none() && rrule.count.is_none() {
+            return Err(RRuleError::new_iter_err(
+                "Reverse iteration requires `UNTIL`, `COUNT` or an upper bound",
+            ));
+        }
+
+        if rrule.count.is_some() || RRuleIter::has_irregular_periods(rrule) {
+            // The dates can only be known by iterating from the start.
+            let mut iter = RRuleIter::new(rrule, dt_start, limited, dst);
+            rev_iter.buffer = iter
+                .by_ref()
+                .take_while(|dt| upper.map_or(true, |upper| *dt <= upper))
+                .collect();
+            rev_iter.was_limited = iter.was_limited();
+            rev_iter.finished = true;
+        } else if let Some(upper) = upper.filter(|_| rrule.interval > 0) {
+            let interval = i64::from(rrule.interval);
+            rev_iter.index =
+                DateTimeIter::period_index(rrule, dt_start, &upper).div_euclid(interval) * interval;
+        }
+
+        Ok(rev_iter)
+    }
+
+    /// Generates the dates of the previous periods until at least one date is found.
+    fn generate(&mut self) {
+        let mut loop_counter: u32 = 0;
+        while self.buffer.is_empty() && !self.finished {
+            if self.index < 0 {
+                self.finished = true;
+                let rrule = self.ii.rrule();
+                if rrule.include_dtstart == Some(true) {
+                    let dt_start =
+                        RRuleIter::output_datetime(self.dt_start, rrule, &self.dt_start, self.dst);
+                    for dt_start in resolved_datetimes(dt_start) {
+                        if self.bound.map_or(true, |bound| dt_start <= bound) {
+                            self.buffer.push(dt_start);
+                        }
+                    }
+                }
+                return;
+            }
+
+            // Prevent infinite loops
+            if self.limited {
+                loop_counter += 1;
+                if loop_counter >= MAX_ITER_LOOP {
+                    self.finished = true;
+                    self.was_limited = true;
+                    log::warn!(
+                        "Reached max loop counter (`{}`). \
+                    See 'validator limits' in docs for more info.",
+                        MAX_ITER_LOOP
+                    );
+                    return;
+                }
+            }
+
+            let Ok(counter_date) =
+                DateTimeIter::from_period_index(self.ii.rrule(), &self.dt_start, self.index)
+            else {
+                self.finished = true;
+                return;
+            };
+            self.ii.rebuild(&counter_date);
+            let rrule = self.ii.rrule();
+
+            let dayset = self.ii.get_dayset(
+                rrule.freq,
+                counter_date.year,
+                counter_date.month,
+                counter_date.day,
+            );
+
+            let is_sub_daily = matches!(
+                rrule.freq,
+                Frequency::Hourly | Frequency::Minutely | Frequency::Secondly
+            );
+            if is_sub_daily && dayset.is_empty() {
+                // None of the periods of this day can have any dates, jump to the previous day.
+                self.index = self.last_index_before_day(&counter_date);
+                continue;
+            }
+
+            let timeset = if is_sub_daily {
+                let hour = u8::try_from(counter_date.hour).expect("range 0-23 is covered by u8");
+                let minute =
+                    u8::try_from(counter_date.minute).expect("range 0-59 is covered by u8");
+                let second =
+                    u8::try_from(counter_date.second).expect("range 0-59 is covered by u8");
+                self.ii.get_timeset(hour, minute, second)
+            } else {
+                self.timeset.clone()
+            };
+
+            let dates = RRuleIter::period_dates(
+                &self.ii,
+                &dayset,
+                &timeset,
+                self.dt_start.timezone(),
+                self.dst,
+            );
+            for dt in dates {
+                if dt < self.dt_start || matches!(rrule.until, Some(until) if dt > until) {
+                    continue;
+                }
+                if dt == self.dt_start && rrule.include_dtstart == Some(true) {
+                    // DTSTART is added when the first period is reached.
+                    continue;
+                }
+                let output_dt = RRuleIter::output_datetime(dt, rrule, &self.dt_start, self.dst);
+                for output_dt in resolved_datetimes(output_dt) {
+                    if matches!(self.bound, Some(bound) if output_dt > bound) {
+                        continue;
+                    }
+                    self.buffer.push(output_dt);
+                }
+            }
+
+            self.index -= i64::from(rrule.interval);
+        }
+    }
+
+    /// Returns the index of the last period, which is visited during iteration,
+    /// before the day of `counter_date`.
+    fn last_index_before_day(&self, counter_date: &DateTimeIter) -> i64 {
+        let rrule = self.ii.rrule();
+        let day_start =
+            NaiveDate::from_ymd_opt(counter_date.year, counter_date.month, counter_date.day)
+                .and_then(|date| date.and_hms_opt(0, 0, 0));
+        let Some(day_start) = day_start else {
+            return -1;
+        };
+        let index = DateTimeIter::naive_period_index(
+            rrule,
+            self.dt_start.naive_local(),
+            day_start - chrono::Duration::seconds(1),
+        );
+        let interval = i64::from(rrule.interval);
+        index.div_euclid(interval) * interval
+    }
+}
+
+impl Iterator for RRuleRevIter {
+    type Item = chrono::DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            self.generate();
+        }
+        self.buffer.pop()
+    }
+}
+
+impl WasLimited for RRuleRevIter {
+    fn was_limited(&self) -> bool {
+        self.was_limited
+    }
+}
@@ -1,13 +1,50 @@
-use chrono::DateTime;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime};
 
 use super::rrule_iter::WasLimited;
+use super::utils::add_time_to_date;
 use super::{rrule_iter::RRuleIter, MAX_ITER_LOOP};
-use crate::RRuleSet;
+use crate::core::resolved_datetimes;
+use crate::{Period, RRuleSet, RecurrenceOverride, RecurrenceRange};
 use crate::{RRuleError, Tz};
-use std::collections::BTreeSet;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 use std::str::FromStr;
 use std::{collections::HashMap, iter::Iterator};
 
+/// An occurrence with the period it was generated from, if any.
+pub(super) type DateWithPeriod = (DateTime<Tz>, Option<Period>);
+
+/// An occurrence after the overrides of the set have been applied.
+#[derive(Debug, Clone)]
+pub(crate) struct GeneratedDate {
+    /// The start of the occurrence.
+    pub date: DateTime<Tz>,
+    /// The start of the occurrence as generated by the rules, used as its `RECURRENCE-ID`.
+    pub recurrence_id: DateTime<Tz>,
+    /// The period the occurrence was given by, if any.
+    pub period: Option<Period>,
+}
+
+impl PartialEq for GeneratedDate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for GeneratedDate {}
+
+impl PartialOrd for GeneratedDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GeneratedDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.date, self.recurrence_id).cmp(&(other.date, other.recurrence_id))
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Iterator over all the dates in an [`RRuleSet`].
 pub struct RRuleSetIter {
@@ -16,25 +53,40 @@ pub struct RRuleSetIter {
     rrule_iters: Vec<RRuleIter>,
     exrules: Vec<RRuleIter>,
     exdates: BTreeSet<i64>,
-    /// Sorted additional dates in descending order
-    rdates: Vec<DateTime<Tz>>,
+    /// Calendar days on which all occurrences are excluded.
+    exdate_dates: BTreeSet<NaiveDate>,
+    /// Sorted additional dates in descending order, with the period they were given by, if any.
+    rdates: Vec<DateWithPeriod>,
+    /// Overrides keyed by the original start of the occurrence.
+    overrides: BTreeMap<DateTime<Tz>, RecurrenceOverride>,
+    /// The earliest an override moves an occurrence, never positive.
+    min_shift: Duration,
+    /// Occurrences with overrides applied, waiting until no earlier occurrence can follow.
+    moved: BinaryHeap<Reverse<GeneratedDate>>,
+    /// The next occurrence generated by the rules, before applying the overrides.
+    upcoming: Option<DateWithPeriod>,
+    /// Set when the rules are done generating occurrences, or the rest is cancelled.
+    exhausted: bool,
+    /// Occurrences before this are skipped, set when seeking.
+    from: Option<DateTime<Tz>>,
     was_limited: bool,
 }
 
 impl RRuleSetIter {
     fn generate_date(
-        dates: &mut Vec<DateTime<Tz>>,
+        dates: &mut Vec<DateWithPeriod>,
         exrules: &mut [RRuleIter],
         exdates: &mut BTreeSet<i64>,
+        exdate_dates: &BTreeSet<NaiveDate>,
         limited: bool,
-    ) -> (Option<DateTime<Tz>>, bool) {
+    ) -> (Option<DateWithPeriod>, bool) {
         if dates.is_empty() {
             return (None, false);
         }
 
         let mut date = dates.remove(dates.len() - 1);
         let mut loop_counter: u32 = 0;
-        while Self::is_date_excluded(&date, exrules, exdates) {
+        while Self::is_date_excluded(&date.0, exrules, exdates, exdate_dates) {
             if dates.is_empty() {
                 return (None, false);
             }
@@ -60,6 +112,7 @@ impl RRuleSetIter {
         rrule_iter: &mut RRuleIter,
         exrules: &mut [RRuleIter],
         exdates: &mut BTreeSet<i64>,
+        exdate_dates: &BTreeSet<NaiveDate>,
         limited: bool,
     ) -> (Option<DateTime<Tz>>, bool) {
         let mut date = match rrule_iter.next() {
@@ -67,7 +120,7 @@ impl RRuleSetIter {
             None => return (None, false),
         };
         let mut loop_counter: u32 = 0;
-        while Self::is_date_excluded(&date, exrules, exdates) {
+        while Self::is_date_excluded(&date, exrules, exdates, exdate_dates) {
             // Prevent infinite loops
             if limited {
                 loop_counter += 1;
@@ -94,6 +147,7 @@ impl RRuleSetIter {
         date: &DateTime<Tz>,
         exrules: &mut [RRuleIter],
         exdates: &mut BTreeSet<i64>,
+        exdate_dates: &BTreeSet<NaiveDate>,
     ) -> bool {
         for exrule in exrules {
             for exdate in exrule {
@@ -104,14 +158,123 @@ impl RRuleSetIter {
             }
         }
 
-        exdates.contains(&date.timestamp())
+        exdates.contains(&date.timestamp()) || exdate_dates.contains(&date.date_naive())
     }
 }
 
-impl Iterator for RRuleSetIter {
-    type Item = DateTime<Tz>;
+impl RRuleSetIter {
+    /// Returns the next occurrence together with the [`Period`] it was generated from.
+    ///
+    /// The period is only set for occurrences which come from an `RDATE;VALUE=PERIOD` or
+    /// which were moved by an override with its own duration, in which case the occurrence is
+    /// the start of the period.
+    pub fn next_with_period(&mut self) -> Option<(DateTime<Tz>, Option<Period>)> {
+        self.next_generated()
+            .map(|generated| (generated.date, generated.period))
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Skips all the occurrences before `dt`. This has to be called before the iteration starts.
+    ///
+    /// The rules jump straight to the period which contains `dt` when possible.
+    pub(crate) fn seek(&mut self, dt: DateTime<Tz>) {
+        // An occurrence can be moved after `dt` by an override, so the original occurrences
+        // are needed from the latest such move.
+        let from = dt - override_shifts(&self.overrides).fold(Duration::zero(), Duration::max);
+        for rrule_iter in self.rrule_iters.iter_mut().chain(&mut self.exrules) {
+            rrule_iter.seek(&from);
+        }
+        self.rdates.retain(|(date, _)| *date >= from);
+        self.from = Some(dt);
+    }
+
+    /// Returns the next occurrence with the overrides applied.
+    pub(crate) fn next_generated(&mut self) -> Option<GeneratedDate> {
+        loop {
+            let generated = self.next_moved()?;
+            if !matches!(self.from, Some(from) if generated.date < from) {
+                return Some(generated);
+            }
+        }
+    }
+
+    /// Returns the next occurrence with the overrides applied, including the ones before `from`.
+    ///
+    /// Moved occurrences are buffered until every occurrence which is still to be generated
+    /// is known to come after them, so that the dates stay sorted.
+    fn next_moved(&mut self) -> Option<GeneratedDate> {
+        if self.overrides.is_empty() {
+            return self.next_original().map(|(date, period)| GeneratedDate {
+                date,
+                recurrence_id: date,
+                period,
+            });
+        }
+
+        loop {
+            if self.upcoming.is_none() && !self.exhausted {
+                self.upcoming = self.next_original();
+                self.exhausted = self.upcoming.is_none();
+            }
+
+            match (self.moved.peek(), self.upcoming) {
+                (Some(Reverse(moved)), Some((upcoming, _)))
+                    if moved.date <= upcoming + self.min_shift =>
+                {
+                    return self.moved.pop().map(|Reverse(moved)| moved);
+                }
+                (Some(_), None) => return self.moved.pop().map(|Reverse(moved)| moved),
+                (None, None) => return None,
+                (_, Some(upcoming)) => {
+                    self.upcoming = None;
+                    if let Some(generated) = self.apply_overrides(upcoming) {
+                        self.moved.push(Reverse(generated));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies the override of the occurrence, or the last `THISANDFUTURE` override before it.
+    /// Returns `None` if the occurrence is cancelled.
+    fn apply_overrides(&mut self, (date, period): DateWithPeriod) -> Option<GeneratedDate> {
+        let applied_override = self.overrides.get_key_value(&date).or_else(|| {
+            self.overrides
+                .range(..date)
+                .rev()
+                .find(|(_, ov)| ov.get_range() == RecurrenceRange::ThisAndFuture)
+        });
+        let Some((recurrence_id, recurrence_override)) = applied_override else {
+            return Some(GeneratedDate {
+                date,
+                recurrence_id: date,
+                period,
+            });
+        };
+
+        let Some(start) = recurrence_override.get_start() else {
+            if recurrence_override.get_range() == RecurrenceRange::ThisAndFuture {
+                // All following occurrences are cancelled as well.
+                self.exhausted = true;
+            }
+            return None;
+        };
+
+        let shift = *start - *recurrence_id;
+        let new_date = date + shift;
+        let period = match recurrence_override.get_duration() {
+            Some(duration) => Some(Period::with_duration(new_date, *duration)),
+            None => period.map(|period| Period::with_duration(new_date, period.get_duration())),
+        };
+
+        Some(GeneratedDate {
+            date: new_date,
+            recurrence_id: date,
+            period,
+        })
+    }
+
+    /// Returns the next occurrence as generated by the rules, before applying the overrides.
+    fn next_original(&mut self) -> Option<DateWithPeriod> {
         let mut next_date: Option<(usize, DateTime<Tz>)> = None;
 
         // If there already was an error, return the error again.
@@ -129,6 +292,7 @@ impl Iterator for RRuleSetIter {
                     rrule_iter,
                     &mut self.exrules,
                     &mut self.exdates,
+                    &self.exdate_dates,
                     self.limited,
                 );
 
@@ -163,6 +327,7 @@ impl Iterator for RRuleSetIter {
             &mut self.rdates,
             &mut self.exrules,
             &mut self.exdates,
+            &self.exdate_dates,
             self.limited,
         );
         if was_limited {
@@ -174,7 +339,7 @@ impl Iterator for RRuleSetIter {
             Some(first_rdate) => {
                 let next_date = match next_date {
                     Some(next_date) => {
-                        if next_date.1 >= first_rdate {
+                        if next_date.1 >= first_rdate.0 {
                             // Add previous date to its rrule queue
                             self.queue.insert(next_date.0, next_date.1);
 
@@ -183,51 +348,134 @@ impl Iterator for RRuleSetIter {
                             // add rdate back
                             self.rdates.push(first_rdate);
 
-                            next_date.1
+                            (next_date.1, None)
                         }
                     }
                     None => first_rdate,
                 };
                 Some(next_date)
             }
-            None => next_date.map(|d| d.1),
+            None => next_date.map(|d| (d.1, None)),
         }
     }
 }
 
+impl Iterator for RRuleSetIter {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_period().map(|(date, _)| date)
+    }
+}
+
 impl IntoIterator for &RRuleSet {
     type Item = DateTime<Tz>;
 
     type IntoIter = RRuleSetIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        // Sort in decreasing order
-        let mut rdates_sorted = self.rdate.clone();
-        rdates_sorted
-            .sort_by(|d1, d2| d2.partial_cmp(d1).expect("Could not order dates correctly"));
+        RRuleSetIter::new(self, effective_overrides(self))
+    }
+}
 
-        let limited = self.limited;
+impl RRuleSetIter {
+    fn new(rrule_set: &RRuleSet, overrides: BTreeMap<DateTime<Tz>, RecurrenceOverride>) -> Self {
+        let limited = rrule_set.limited;
 
-        RRuleSetIter {
+        Self {
             queue: HashMap::new(),
             limited,
-            rrule_iters: self
+            rrule_iters: rrule_set
                 .rrule
                 .iter()
-                .map(|rrule| rrule.iter_with_ctx(self.dt_start, limited))
+                .map(|rrule| rrule.iter_with_ctx(rrule_set.dt_start, limited, rrule_set.dst))
                 .collect(),
-            rdates: rdates_sorted,
-            exrules: self
+            rdates: sorted_rdates(rrule_set),
+            exrules: rrule_set
                 .exrule
                 .iter()
-                .map(|exrule| exrule.iter_with_ctx(self.dt_start, limited))
+                .map(|exrule| exrule.iter_with_ctx(rrule_set.dt_start, limited, rrule_set.dst))
                 .collect(),
-            exdates: self.exdate.iter().map(DateTime::timestamp).collect(),
+            exdates: rrule_set.exdate.iter().map(DateTime::timestamp).collect(),
+            exdate_dates: rrule_set.exdate_date.iter().copied().collect(),
+            min_shift: override_shifts(&overrides).fold(Duration::zero(), Duration::min),
+            overrides,
+            moved: BinaryHeap::new(),
+            upcoming: None,
+            exhausted: false,
+            from: None,
             was_limited: false,
         }
     }
 }
 
+/// Returns all the additional dates of the set in decreasing order, with the period
+/// they were given by, if any.
+pub(super) fn sorted_rdates(rrule_set: &RRuleSet) -> Vec<DateWithPeriod> {
+    // Date-valued rdates occur at the start of the day in the timezone of `dt_start`.
+    let tz = rrule_set.dt_start.timezone();
+    let rdate_dates = rrule_set.rdate_date.iter().flat_map(|date| {
+        resolved_datetimes(add_time_to_date(tz, *date, NaiveTime::MIN, rrule_set.dst))
+    });
+    let rdate_periods = rrule_set
+        .rdate_period
+        .iter()
+        .map(|period| (period.get_start(), Some(*period)));
+
+    // Sort in decreasing order
+    let mut rdates_sorted = rrule_set
+        .rdate
+        .iter()
+        .copied()
+        .chain(rdate_dates)
+        .map(|date| (date, None))
+        .chain(rdate_periods)
+        .collect::<Vec<_>>();
+    rdates_sorted.sort_by(|d1, d2| {
+        d2.0.partial_cmp(&d1.0)
+            .expect("Could not order dates correctly")
+    });
+    rdates_sorted
+}
+
+/// Returns the overrides of the set whose `recurrence_id` is an occurrence generated by the
+/// rules and additional dates. The others have no effect, not even on the following
+/// occurrences of a `THISANDFUTURE` override.
+pub(super) fn effective_overrides(
+    rrule_set: &RRuleSet,
+) -> BTreeMap<DateTime<Tz>, RecurrenceOverride> {
+    let Some(first) = rrule_set.overrides.keys().next() else {
+        return BTreeMap::new();
+    };
+    let mut originals = RRuleSetIter::new(rrule_set, BTreeMap::new());
+    originals.seek(*first);
+    let mut original: Option<DateTime<Tz>> = None;
+    rrule_set
+        .overrides
+        .iter()
+        .filter(|(recurrence_id, _)| {
+            // The overrides are sorted, so the originals only have to be generated once.
+            while original.map_or(true, |date| date < **recurrence_id) {
+                match originals.next_original() {
+                    Some((date, _)) => original = Some(date),
+                    None => return false,
+                }
+            }
+            original == Some(**recurrence_id)
+        })
+        .map(|(recurrence_id, ov)| (*recurrence_id, *ov))
+        .collect()
+}
+
+/// Returns how much each override moves its occurrence.
+pub(super) fn override_shifts(
+    overrides: &BTreeMap<DateTime<Tz>, RecurrenceOverride>,
+) -> impl Iterator<Item = Duration> + '_ {
+    overrides
+        .iter()
+        .filter_map(|(recurrence_id, ov)| ov.get_start().map(|start| *start - *recurrence_id))
+}
+
 impl WasLimited for RRuleSetIter {
     fn was_limited(&self) -> bool {
         self.was_limited
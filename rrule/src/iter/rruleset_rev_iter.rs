@@ -0,0 +1,273 @@
+use chrono::{DateTime, Duration, NaiveDate};
+
+use super::rrule_iter::WasLimited;
+use super::rrule_rev_iter::RRuleRevIter;
+use super::rruleset_iter::{
+    effective_overrides, override_shifts, sorted_rdates, DateWithPeriod, GeneratedDate,
+};
+use super::MAX_ITER_LOOP;
+use crate::{Period, RRuleError, RRuleSet, RecurrenceOverride, RecurrenceRange, Tz};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+#[derive(Debug, Clone)]
+/// Iterator over all the dates in an [`RRuleSet`] in reverse order, from the last to the first.
+///
+/// Created by [`RRuleSet::iter_rev`].
+pub struct RRuleSetRevIter {
+    limited: bool,
+    rrule_iters: Vec<RRuleRevIter>,
+    /// The next date of every rule, followed by the next additional date.
+    heads: Vec<Option<DateWithPeriod>>,
+    exrules: Vec<RRuleRevIter>,
+    exdates: BTreeSet<i64>,
+    /// Calendar days on which all occurrences are excluded.
+    exdate_dates: BTreeSet<NaiveDate>,
+    /// Sorted additional dates in ascending order, with the period they were given by, if any.
+    rdates: Vec<DateWithPeriod>,
+    /// Overrides keyed by the original start of the occurrence.
+    overrides: BTreeMap<DateTime<Tz>, RecurrenceOverride>,
+    /// The latest an override moves an occurrence, never negative.
+    max_shift: Duration,
+    /// Occurrences with overrides applied, waiting until no later occurrence can follow.
+    moved: BinaryHeap<GeneratedDate>,
+    /// The next occurrence generated by the rules, before applying the overrides.
+    upcoming: Option<DateWithPeriod>,
+    /// Set when the rules are done generating occurrences.
+    exhausted: bool,
+    before: Option<DateTime<Tz>>,
+    after: Option<DateTime<Tz>>,
+    was_limited: bool,
+}
+
+impl RRuleSetRevIter {
+    pub(crate) fn new(rrule_set: &RRuleSet) -> Result<Self, RRuleError> {
+        let limited = rrule_set.limited;
+        // An occurrence can be moved before `before` by an override, so the rules have
+        // to generate the original occurrences up to the earliest such move.
+        let bound = rrule_set.before.map(|before| {
+            before - override_shifts(&rrule_set.overrides).fold(Duration::zero(), Duration::min)
+        });
+        let rev_iters = |rrules: &[crate::RRule]| {
+            rrules
+                .iter()
+                .map(|rrule| {
+                    RRuleRevIter::new(rrule, &rrule_set.dt_start, bound, limited, rrule_set.dst)
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let rrule_iters = rev_iters(&rrule_set.rrule)?;
+
+        let mut rdates = sorted_rdates(rrule_set);
+        rdates.reverse();
+        let overrides = effective_overrides(rrule_set);
+
+        Ok(Self {
+            limited,
+            heads: vec![None; rrule_iters.len() + 1],
+            rrule_iters,
+            exrules: rev_iters(&rrule_set.exrule)?,
+            exdates: rrule_set.exdate.iter().map(DateTime::timestamp).collect(),
+            exdate_dates: rrule_set.exdate_date.iter().copied().collect(),
+            rdates,
+            max_shift: override_shifts(&overrides).fold(Duration::zero(), Duration::max),
+            overrides,
+            moved: BinaryHeap::new(),
+            upcoming: None,
+            exhausted: false,
+            before: rrule_set.before,
+            after: rrule_set.after,
+            was_limited: false,
+        })
+    }
+
+    /// Returns the previous date of `dates` which isn't excluded, and whether the
+    /// iterator limits were reached.
+    fn generate<I>(
+        dates: I,
+        exrules: &mut [RRuleRevIter],
+        exdates: &mut BTreeSet<i64>,
+        exdate_dates: &BTreeSet<NaiveDate>,
+        limited: bool,
+    ) -> (Option<DateWithPeriod>, bool)
+    where
+        I: Iterator<Item = DateWithPeriod>,
+    {
+        let mut loop_counter: u32 = 0;
+        for date in dates {
+            if !Self::is_date_excluded(&date.0, exrules, exdates, exdate_dates) {
+                return (Some(date), false);
+            }
+            // Prevent infinite loops
+            if limited {
+                loop_counter += 1;
+                if loop_counter >= MAX_ITER_LOOP {
+                    log::warn!(
+                        "Reached max loop counter (`{}`). \
+                    See 'validator limits' in docs for more info.",
+                        MAX_ITER_LOOP
+                    );
+                    return (None, true);
+                }
+            }
+        }
+        (None, false)
+    }
+
+    fn is_date_excluded(
+        date: &DateTime<Tz>,
+        exrules: &mut [RRuleRevIter],
+        exdates: &mut BTreeSet<i64>,
+        exdate_dates: &BTreeSet<NaiveDate>,
+    ) -> bool {
+        for exrule in exrules {
+            for exdate in exrule {
+                exdates.insert(exdate.timestamp());
+                if exdate < *date {
+                    break;
+                }
+            }
+        }
+
+        exdates.contains(&date.timestamp()) || exdate_dates.contains(&date.date_naive())
+    }
+
+    /// Returns the previous occurrence as generated by the rules, before applying the overrides.
+    fn next_original(&mut self) -> Option<DateWithPeriod> {
+        // If there already was an error, return the error again.
+        if self.was_limited {
+            return None;
+        }
+
+        for (i, head) in self.heads.iter_mut().enumerate() {
+            if head.is_some() {
+                continue;
+            }
+            let (date, was_limited) = match self.rrule_iters.get_mut(i) {
+                Some(rrule_iter) => {
+                    let (date, was_limited) = Self::generate(
+                        rrule_iter.by_ref().map(|date| (date, None)),
+                        &mut self.exrules,
+                        &mut self.exdates,
+                        &self.exdate_dates,
+                        self.limited,
+                    );
+                    (date, was_limited || rrule_iter.was_limited())
+                }
+                None => Self::generate(
+                    std::iter::from_fn(|| self.rdates.pop()),
+                    &mut self.exrules,
+                    &mut self.exdates,
+                    &self.exdate_dates,
+                    self.limited,
+                ),
+            };
+            if was_limited {
+                self.was_limited = true;
+                return None;
+            }
+            *head = date;
+        }
+
+        // Take the latest date, preferring the rules over the additional dates.
+        let (latest, _) = self
+            .heads
+            .iter()
+            .enumerate()
+            .filter_map(|(i, head)| head.map(|(date, _)| (i, date)))
+            .max_by_key(|(i, date)| (*date, Reverse(*i)))?;
+        self.heads[latest].take()
+    }
+
+    /// Applies the override of the occurrence, or the last `THISANDFUTURE` override before it.
+    /// Returns `None` if the occurrence is cancelled.
+    fn apply_overrides(&self, (date, period): DateWithPeriod) -> Option<GeneratedDate> {
+        let cancelled_before = self
+            .overrides
+            .range(..date)
+            .any(|(_, ov)| ov.is_cancelled() && ov.get_range() == RecurrenceRange::ThisAndFuture);
+        if cancelled_before {
+            return None;
+        }
+
+        let applied_override = self.overrides.get_key_value(&date).or_else(|| {
+            self.overrides
+                .range(..date)
+                .rev()
+                .find(|(_, ov)| ov.get_range() == RecurrenceRange::ThisAndFuture)
+        });
+        let Some((recurrence_id, recurrence_override)) = applied_override else {
+            return Some(GeneratedDate {
+                date,
+                recurrence_id: date,
+                period,
+            });
+        };
+
+        let shift = *recurrence_override.get_start()? - *recurrence_id;
+        let new_date = date + shift;
+        let period = match recurrence_override.get_duration() {
+            Some(duration) => Some(Period::with_duration(new_date, *duration)),
+            None => period.map(|period| Period::with_duration(new_date, period.get_duration())),
+        };
+
+        Some(GeneratedDate {
+            date: new_date,
+            recurrence_id: date,
+            period,
+        })
+    }
+
+    /// Returns the previous occurrence with the overrides applied.
+    ///
+    /// Moved occurrences are buffered until every occurrence which is still to be generated
+    /// is known to come before them, so that the dates stay sorted.
+    fn next_generated(&mut self) -> Option<GeneratedDate> {
+        loop {
+            if self.upcoming.is_none() && !self.exhausted {
+                self.upcoming = self.next_original();
+                self.exhausted = self.upcoming.is_none();
+            }
+
+            match (self.moved.peek(), self.upcoming) {
+                (Some(moved), Some((upcoming, _))) if moved.date >= upcoming + self.max_shift => {
+                    return self.moved.pop();
+                }
+                (Some(_), None) => return self.moved.pop(),
+                (None, None) => return None,
+                (_, Some(upcoming)) => {
+                    self.upcoming = None;
+                    if let Some(generated) = self.apply_overrides(upcoming) {
+                        self.moved.push(generated);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for RRuleSetRevIter {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(generated) = self.next_generated() {
+            if matches!(self.before, Some(before) if generated.date > before) {
+                continue;
+            }
+            if matches!(self.after, Some(after) if generated.date < after) {
+                // All the following dates are before `after` as well.
+                self.exhausted = true;
+                self.moved.clear();
+                return None;
+            }
+            return Some(generated.date);
+        }
+        None
+    }
+}
+
+impl WasLimited for RRuleSetRevIter {
+    fn was_limited(&self) -> bool {
+        self.was_limited
+    }
+}
@@ -1,7 +1,7 @@
 use std::ops;
 
-use crate::core::{duration_from_midnight, Tz};
-use chrono::{NaiveDate, NaiveTime, Utc};
+use crate::core::{DstPolicy, Tz};
+use chrono::{LocalResult, NaiveDate, NaiveTime, Utc};
 
 const DAY_SECS: i64 = 24 * 60 * 60;
 
@@ -72,19 +72,15 @@ where
     }
 }
 
+/// Returns the datetimes of the `time` on `date` in `tz`. A time in a daylight saving time
+/// transition is resolved with `dst`, which can give no or two datetimes.
 pub(crate) fn add_time_to_date(
     tz: Tz,
     date: NaiveDate,
     time: NaiveTime,
-) -> Option<chrono::DateTime<Tz>> {
-    if let Some(dt) = date.and_time(time).and_local_timezone(tz).single() {
-        return Some(dt);
-    }
-    // If the day is a daylight saving time, the above code might not work, and we
-    // can try to get a valid datetime by adding the `time` as a duration instead.
-    let dt = date.and_hms_opt(0, 0, 0)?.and_local_timezone(tz).single()?;
-    let day_duration = duration_from_midnight(time);
-    dt.checked_add_signed(day_duration)
+    dst: DstPolicy,
+) -> LocalResult<chrono::DateTime<Tz>> {
+    dst.resolve(tz, date.and_time(time))
 }
 
 #[cfg(test)]
@@ -190,8 +186,8 @@ mod test {
         ];
 
         for (tz, date, time, expected_output) in tests {
-            let res = add_time_to_date(tz, date, time);
-            assert_eq!(res, expected_output);
+            let res = add_time_to_date(tz, date, time, DstPolicy::default());
+            assert_eq!(res.single(), expected_output);
         }
     }
 }
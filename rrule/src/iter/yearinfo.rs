@@ -2,7 +2,8 @@ use super::{
     masks::MASKS,
     utils::{days_since_unix_epoch, get_year_len, pymod},
 };
-use crate::RRule;
+use crate::calendar::{self, CalendarMonth};
+use crate::{Frequency, RRule, RScale, Skip};
 use chrono::{Datelike, TimeZone, Utc};
 
 #[derive(Debug)]
@@ -34,6 +35,127 @@ fn base_year_masks(year_weekday: u16, year_len: u16) -> BaseMasks {
     }
 }
 
+/// Finds the months of the year in which `BYMONTHDAY` values don't exist, like the 31st
+/// of February, and the day the `SKIP` rule part moves them to.
+fn rolled_month_days(rrule: &RRule, month_range: &[u16]) -> Vec<(u8, usize)> {
+    if rrule.skip == Skip::Omit || !matches!(rrule.freq, Frequency::Yearly | Frequency::Monthly) {
+        return vec![];
+    }
+
+    let mut rolled_month_days = vec![];
+    for month in 1..=12 {
+        let first = usize::from(month_range[usize::from(month) - 1]);
+        let next_first = usize::from(month_range[usize::from(month)]);
+        let month_len = next_first - first;
+
+        // E.g. the 31st is moved to the last day of the month or the first day of the next one.
+        if rrule
+            .by_month_day
+            .iter()
+            .any(|day| usize::from(day.unsigned_abs()) > month_len)
+        {
+            let day = if rrule.skip == Skip::Backward {
+                next_first - 1
+            } else {
+                next_first
+            };
+            rolled_month_days.push((month, day));
+        }
+
+        // E.g. the 31st to last day is moved to the last day of the previous month or the
+        // first day of the month.
+        if rrule
+            .by_n_month_day
+            .iter()
+            .any(|day| usize::from(day.unsigned_abs()) > month_len)
+        {
+            let day = if rrule.skip == Skip::Backward {
+                first.checked_sub(1)
+            } else {
+                Some(first)
+            };
+            rolled_month_days.extend(day.map(|day| (month, day)));
+        }
+    }
+
+    rolled_month_days
+}
+
+/// The masks of a non-Gregorian `RSCALE`, which replace the Gregorian month masks.
+#[derive(Debug, Clone)]
+pub(crate) struct CalendarMasks {
+    /// The calendar month of each day
+    pub month_mask: Vec<CalendarMonth>,
+    pub month_day_mask: Vec<i8>,
+    pub neg_month_day_mask: Vec<i8>,
+    /// Days that month days which don't exist in their calendar month are rolled to
+    /// by `SKIP`.
+    pub rolled_days: Vec<usize>,
+}
+
+impl CalendarMasks {
+    fn new(rscale: RScale, first_year_day: i32, year_len: u16, rrule: &RRule) -> Self {
+        // Like the Gregorian masks, these include the first week of the next year.
+        let next_day = first_year_day + i32::from(year_len) + 7;
+        let index = |day: i32| usize::try_from(day - first_year_day).ok();
+
+        // Start a day early to find the month which can roll onto the first day.
+        let months = calendar::months(rscale, first_year_day - 1, next_day);
+        let mut masks = Self {
+            month_mask: vec![],
+            month_day_mask: vec![],
+            neg_month_day_mask: vec![],
+            rolled_days: vec![],
+        };
+        for month in &months {
+            let len = i8::try_from(month.len).expect("a month has at most 31 days");
+            for day in month.first_day.max(first_year_day)..month.next_first_day().min(next_day) {
+                let month_day =
+                    i8::try_from(month.month_day(day)).expect("a month has at most 31 days");
+                masks.month_mask.push(*month);
+                masks.month_day_mask.push(month_day);
+                masks.neg_month_day_mask.push(month_day - len - 1);
+            }
+        }
+
+        if rrule.skip == Skip::Omit || !matches!(rrule.freq, Frequency::Yearly | Frequency::Monthly)
+        {
+            return masks;
+        }
+        for month in &months {
+            if super::filters::is_calendar_month_filtered(month, rrule) {
+                continue;
+            }
+            let len = month.len;
+            if rrule
+                .by_month_day
+                .iter()
+                .any(|day| day.unsigned_abs() > len)
+            {
+                let day = if rrule.skip == Skip::Backward {
+                    month.next_first_day() - 1
+                } else {
+                    month.next_first_day()
+                };
+                masks.rolled_days.extend(index(day));
+            }
+            if rrule
+                .by_n_month_day
+                .iter()
+                .any(|day| day.unsigned_abs() > len)
+            {
+                let day = if rrule.skip == Skip::Backward {
+                    month.first_day - 1
+                } else {
+                    month.first_day
+                };
+                masks.rolled_days.extend(index(day));
+            }
+        }
+        masks
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct YearInfo {
     /// The year
@@ -51,6 +173,11 @@ pub(crate) struct YearInfo {
     pub weekday_mask: &'static [u32],
     /// Week number mask
     pub week_no_mask: Option<Vec<u8>>,
+    /// Days that month days which don't exist are rolled to by `SKIP`,
+    /// together with the month in which they don't exist.
+    pub rolled_month_days: Vec<(u8, usize)>,
+    /// The month masks of a non-Gregorian `RSCALE`
+    pub calendar: Option<CalendarMasks>,
 }
 
 impl YearInfo {
@@ -65,6 +192,20 @@ impl YearInfo {
             .expect("num_days_from_monday is between 0 and 6 which is covered by u16");
 
         let base_masks = base_year_masks(year_start_weekday, year_len);
+        let calendar = match rrule.rscale {
+            Some(rscale) if rscale != RScale::Gregorian => Some(CalendarMasks::new(
+                rscale,
+                first_year_day.num_days_from_ce(),
+                year_len,
+                rrule,
+            )),
+            _ => None,
+        };
+        let rolled_month_days = if calendar.is_some() {
+            vec![]
+        } else {
+            rolled_month_days(rrule, base_masks.month_range)
+        };
 
         let mut result = Self {
             year,
@@ -72,6 +213,8 @@ impl YearInfo {
             next_year_len,
             year_ordinal,
             week_no_mask: None,
+            rolled_month_days,
+            calendar,
             month_mask: base_masks.month_mask,
             month_day_mask: base_masks.month_day_mask,
             neg_month_day_mask: base_masks.neg_month_day_mask,
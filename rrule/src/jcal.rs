@@ -0,0 +1,211 @@
+//! The jCal representation of recurrences, as defined in [RFC 7265](https://datatracker.ietf.org/doc/html/rfc7265).
+//!
+//! jCal maps the iCalendar text format to JSON one to one, so the conversions go through the
+//! properties of the text form, see [`crate::property`]. Both forms therefore round-trip in the
+//! same way.
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    parser::ParseError,
+    property::{
+        check_recur_parts, recur_from_parts, recur_parts, rrule_set_from_properties,
+        rrule_set_to_properties, Property, PropertyValues,
+    },
+    RRule, RRuleError, RRuleSet, Unvalidated,
+};
+
+impl<S> RRule<S> {
+    /// Converts the rule to a jCal `recur` value, like `{"freq": "WEEKLY", "byday": ["MO", "FR"]}`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{RRule, Unvalidated};
+    ///
+    /// let rrule: RRule<Unvalidated> = "FREQ=WEEKLY;COUNT=4;BYDAY=MO,FR".parse().unwrap();
+    /// assert_eq!(
+    ///     rrule.to_jcal(),
+    ///     serde_json::json!({"freq": "WEEKLY", "count": 4, "byday": ["MO", "FR"]})
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_jcal(&self) -> Value {
+        recur_to_jcal(&recur_parts(&self.to_string()))
+    }
+}
+
+impl RRule<Unvalidated> {
+    /// Creates a rule from a jCal `recur` value, see [`RRule::to_jcal`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the value isn't a valid `recur` value.
+    pub fn from_jcal(value: &Value) -> Result<Self, RRuleError> {
+        let parts = recur_from_jcal(value)?;
+        check_recur_parts(&parts)?;
+        Self::from_str(&recur_from_parts(&parts))
+    }
+}
+
+impl RRuleSet {
+    /// Converts the set to a list of jCal properties, like
+    /// `[["dtstart", {"tzid": "Europe/Berlin"}, "date-time", "2025-01-01T09:00:00"], ["rrule", {}, "recur", {"freq": "DAILY"}]]`.
+    ///
+    /// The dates of `RDATE` and `EXDATE` become `date`, `date-time` or `period` values.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20250101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+    ///     .parse()
+    ///     .unwrap();
+    /// let jcal = rrule_set.to_jcal();
+    /// assert_eq!(
+    ///     jcal,
+    ///     serde_json::json!([
+    ///         ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+    ///         [
+    ///             "rrule",
+    ///             {},
+    ///             "recur",
+    ///             {"freq": "DAILY", "count": 3, "byhour": 9, "byminute": 0, "bysecond": 0},
+    ///         ],
+    ///     ])
+    /// );
+    /// assert_eq!(RRuleSet::from_jcal(&jcal).unwrap(), rrule_set);
+    /// ```
+    #[must_use]
+    pub fn to_jcal(&self) -> Value {
+        Value::Array(
+            rrule_set_to_properties(self)
+                .iter()
+                .map(property_to_jcal)
+                .collect(),
+        )
+    }
+
+    /// Creates a set from a list of jCal properties, see [`RRuleSet::to_jcal`]. Properties which
+    /// don't describe recurrences, like the `summary` of an event, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the value isn't a list of jCal properties or if the properties
+    /// don't describe a valid [`RRuleSet`].
+    pub fn from_jcal(value: &Value) -> Result<Self, RRuleError> {
+        let properties = value.as_array().ok_or_else(|| invalid(value))?;
+        let properties = properties
+            .iter()
+            .map(property_from_jcal)
+            .collect::<Result<Vec<_>, _>>()?;
+        rrule_set_from_properties(&properties)
+    }
+}
+
+fn invalid(value: &Value) -> ParseError {
+    ParseError::InvalidJCal(value.to_string())
+}
+
+fn property_to_jcal(property: &Property) -> Value {
+    let parameters = property
+        .parameters
+        .iter()
+        .map(|(parameter, value)| (parameter.clone(), Value::String(value.clone())))
+        .collect::<Map<_, _>>();
+    let mut jcal = vec![
+        Value::String(property.name.clone()),
+        Value::Object(parameters),
+        Value::String(property.value_type.clone()),
+    ];
+    match &property.values {
+        PropertyValues::Recur(parts) => jcal.push(recur_to_jcal(parts)),
+        PropertyValues::Text(values) => jcal.extend(values.iter().cloned().map(Value::String)),
+    }
+    Value::Array(jcal)
+}
+
+fn property_from_jcal(property: &Value) -> Result<Property, ParseError> {
+    let parts = property.as_array().ok_or_else(|| invalid(property))?;
+    let (
+        Some(Value::String(name)),
+        Some(Value::Object(parameters)),
+        Some(Value::String(value_type)),
+    ) = (parts.first(), parts.get(1), parts.get(2))
+    else {
+        return Err(invalid(property));
+    };
+
+    let parameters = parameters
+        .iter()
+        .map(|(parameter, value)| match value {
+            Value::String(value) => Ok((parameter.to_lowercase(), value.clone())),
+            _ => Err(invalid(property)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let values = match (value_type.as_str(), &parts[3..]) {
+        ("recur", [recur]) => PropertyValues::Recur(recur_from_jcal(recur)?),
+        ("date" | "date-time" | "duration" | "period", values) if !values.is_empty() => {
+            let values = values
+                .iter()
+                .map(|value| value.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| invalid(property))?;
+            PropertyValues::Text(values)
+        }
+        _ => return Err(invalid(property)),
+    };
+    Ok(Property {
+        name: name.to_lowercase(),
+        parameters,
+        value_type: value_type.clone(),
+        values,
+    })
+}
+
+/// Converts the parts of a `RECUR` value to a jCal object. Parts with one value become a single
+/// value, parts with more values become an array, and numbers become numbers.
+fn recur_to_jcal(parts: &[(String, Vec<String>)]) -> Value {
+    let mut object = Map::new();
+    for (key, values) in parts {
+        let mut values = values
+            .iter()
+            .map(|value| match i64::from_str(value) {
+                Ok(number) if key != "until" => number.into(),
+                _ => Value::String(value.clone()),
+            })
+            .collect::<Vec<_>>();
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            Value::Array(values)
+        };
+        object.insert(key.clone(), value);
+    }
+    Value::Object(object)
+}
+
+/// Converts a jCal `recur` object to the parts of a `RECUR` value.
+fn recur_from_jcal(recur: &Value) -> Result<Vec<(String, Vec<String>)>, ParseError> {
+    let object = recur.as_object().ok_or_else(|| invalid(recur))?;
+    let value_to_string = |value: &Value| match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        _ => Err(invalid(recur)),
+    };
+
+    let mut parts = vec![];
+    for (key, value) in object {
+        let values = match value {
+            Value::Array(values) => values
+                .iter()
+                .map(value_to_string)
+                .collect::<Result<Vec<_>, _>>()?,
+            value => vec![value_to_string(value)?],
+        };
+        parts.push((key.to_lowercase(), values));
+    }
+    Ok(parts)
+}
@@ -2,6 +2,8 @@
 //!
 //! This crate provides [`RRuleSet`] for working with recurrence rules. It has a collection of `DTSTART`, `RRULE`s, `EXRULE`s, `RDATE`s and `EXDATE`s. Both the `RRULE` and `EXRULE`
 //! properties are represented by the [`RRule`] type and the `DTSTART`, `RDATE` and `EXDATE` properties are represented by the [`chrono::DateTime<Tz>`].
+//! `RDATE`s and `EXDATE`s with the `VALUE=DATE` parameter are represented by [`chrono::NaiveDate`], an `EXDATE` of that kind
+//! excludes every occurrence on that calendar day. `RDATE`s with the `VALUE=PERIOD` parameter are represented by [`Period`].
 //!
 //! # Building `RRule` and `RRuleSet`
 //! [`RRuleSet`] and [`RRule`] both implements the [`std::str::FromStr`] trait so that it can be parsed and built from a string representation.
@@ -93,15 +95,48 @@
 #![warn(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+#[cfg(feature = "binary")]
+mod binary;
+mod calendar;
 mod core;
 mod error;
 mod iter;
+#[cfg(feature = "jcal")]
+mod jcal;
 mod parser;
+#[cfg(any(feature = "jcal", feature = "xcal"))]
+mod property;
 mod tests;
+mod text;
 mod validator;
+#[cfg(feature = "xcal")]
+mod xcal;
 
-pub use crate::core::{Frequency, NWeekday, RRule, RRuleResult, RRuleSet, Tz};
+#[cfg(feature = "chrono-tz")]
+pub use crate::core::ChronoTzProvider;
+#[cfg(feature = "serde")]
+pub use crate::core::Structured;
+#[cfg(feature = "zoneinfo")]
+pub use crate::core::ZoneinfoProvider;
+pub use crate::core::{
+    set_timezone_provider, Calendar, CalendarComponent, ComponentKind, DstGap, DstOverlap,
+    Frequency, NWeekday, Occurrence, OccurrenceResult, Period, PeriodEnd, RRule, RRuleResult,
+    RRuleSet, RScale, RecurrenceOverride, RecurrenceRange, Skip, TimezoneProvider, Tz, VTimezone,
+};
+#[cfg(feature = "time")]
+pub use crate::core::{OffsetDateTimeIter, OffsetDateTimeIterator};
 pub use crate::core::{Unvalidated, Validated};
+#[cfg(feature = "jiff")]
+pub use crate::core::{ZonedIter, ZonedIterator};
 pub use chrono::Weekday;
 pub use error::{ParseError, RRuleError, ValidationError};
-pub use iter::RRuleSetIter;
+pub use iter::{OccurrenceIter, RRuleSetIter, RRuleSetRevIter};
+#[cfg(feature = "locale-nl")]
+pub use text::Dutch;
+#[cfg(feature = "locale-fr")]
+pub use text::French;
+#[cfg(feature = "locale-de")]
+pub use text::German;
+#[cfg(feature = "locale-ja")]
+pub use text::Japanese;
+pub use text::{English, Locale, TextPart};
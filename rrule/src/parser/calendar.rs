@@ -0,0 +1,434 @@
+//! Splits an iCalendar document into the `VEVENT` and `VTODO` components which
+//! describe a recurrence set, and the `VTIMEZONE`s they can refer to.
+
+use chrono::{DateTime, NaiveDateTime};
+
+use super::{
+    content_line::{ContentLineCaptures, StartDateContentLine},
+    datetime::{datestring_to_date, parse_utc_offset},
+    utils::{find_unquoted, split_unquoted, unfold_lines},
+    ParseError,
+};
+use crate::core::Observance;
+use crate::{ComponentKind, RRule, RRuleError, RecurrenceRange, Tz, VTimezone};
+
+/// The properties which are passed on to the parser of an [`crate::RRuleSet`].
+const RECURRENCE_PROPERTIES: [&str; 7] = [
+    "DTSTART", "RRULE", "EXRULE", "RDATE", "EXDATE", "DTEND", "DURATION",
+];
+
+/// The components of an iCalendar document which are used for recurrences.
+#[derive(Debug, PartialEq, Default)]
+pub(crate) struct CalendarLines {
+    pub components: Vec<ComponentLines>,
+    pub timezones: Vec<TimezoneLines>,
+}
+
+/// A `VEVENT` or `VTODO` component of an iCalendar document.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ComponentLines {
+    pub kind: ComponentKind,
+    pub uid: Option<String>,
+    /// The `RECURRENCE-ID` line, if this component overrides an occurrence of another component.
+    pub recurrence_id: Option<String>,
+    /// If the component has `STATUS:CANCELLED`.
+    pub cancelled: bool,
+    /// The unfolded lines of the recurrence properties, which can be parsed into an [`crate::RRuleSet`].
+    /// The `DUE` of a `VTODO` is given as `DTEND`.
+    pub lines: Vec<String>,
+}
+
+impl ComponentLines {
+    fn new(kind: ComponentKind) -> Self {
+        Self {
+            kind,
+            uid: None,
+            recurrence_id: None,
+            cancelled: false,
+            lines: vec![],
+        }
+    }
+
+    fn add_property(&mut self, line: &str) -> Result<(), ParseError> {
+        let name = property_name(line).to_uppercase();
+        match &name[..] {
+            "UID" => self.uid = Some(property_value(line)?.into()),
+            "STATUS" => self.cancelled = property_value(line)?.eq_ignore_ascii_case("CANCELLED"),
+            "RECURRENCE-ID" => self.recurrence_id = Some(line.into()),
+            "DUE" if self.kind == ComponentKind::Todo => {
+                self.lines.push(format!("DTEND{}", &line[name.len()..]));
+            }
+            name if RECURRENCE_PROPERTIES.contains(&name) => self.lines.push(line.into()),
+            // Other properties, like `SUMMARY`, don't change the occurrences
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// A `VTIMEZONE` component of an iCalendar document.
+#[derive(Debug, PartialEq, Default)]
+pub(crate) struct TimezoneLines {
+    pub tzid: Option<String>,
+    /// The unfolded lines of every `STANDARD` and `DAYLIGHT` sub-component.
+    pub observances: Vec<Vec<String>>,
+}
+
+impl TimezoneLines {
+    /// Parses the sub-components and creates a [`VTimezone`].
+    pub(crate) fn into_vtimezone(self) -> Result<VTimezone, RRuleError> {
+        let tzid = self
+            .tzid
+            .ok_or_else(|| ParseError::MissingProperty("TZID".into()))?;
+        let observances = self
+            .observances
+            .iter()
+            .map(|lines| parse_observance(lines))
+            .collect::<Result<Vec<_>, _>>()?;
+        VTimezone::new(tzid, observances)
+    }
+}
+
+/// The component which is being parsed.
+enum OpenComponent {
+    Component(ComponentLines),
+    Timezone(TimezoneLines),
+}
+
+/// Returns the `VEVENT`, `VTODO` and `VTIMEZONE` components of an iCalendar document in the
+/// order they are found. Nested components, like a `VALARM`, and all other components are skipped.
+pub(crate) fn parse_components(s: &str) -> Result<CalendarLines, ParseError> {
+    let mut calendar = CalendarLines::default();
+    // The names of the components which contain the current line
+    let mut open_components: Vec<String> = vec![];
+    // The component which is being parsed, with its depth in `open_components`
+    let mut current: Option<(OpenComponent, usize)> = None;
+
+    for line in unfold_lines(s) {
+        let name = property_name(&line);
+        let is_begin = name.eq_ignore_ascii_case("BEGIN");
+        if !is_begin && !name.eq_ignore_ascii_case("END") {
+            match current.as_mut() {
+                // Only the properties of the component itself, and not of its nested components
+                Some((OpenComponent::Component(component), depth))
+                    if *depth == open_components.len() =>
+                {
+                    component.add_property(&line)?;
+                }
+                Some((OpenComponent::Timezone(timezone), depth))
+                    if *depth == open_components.len() && name.eq_ignore_ascii_case("TZID") =>
+                {
+                    timezone.tzid = Some(property_value(&line)?.into());
+                }
+                Some((OpenComponent::Timezone(timezone), depth))
+                    if *depth + 1 == open_components.len()
+                        && matches!(
+                            open_components.last().map(String::as_str),
+                            Some("STANDARD" | "DAYLIGHT")
+                        ) =>
+                {
+                    if let Some(observance) = timezone.observances.last_mut() {
+                        observance.push(line);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let component_name = line[name.len()..]
+            .trim_start_matches(':')
+            .trim()
+            .to_uppercase();
+        if is_begin {
+            match current.as_mut() {
+                None => {
+                    current = match &component_name[..] {
+                        "VEVENT" => Some(OpenComponent::Component(ComponentLines::new(
+                            ComponentKind::Event,
+                        ))),
+                        "VTODO" => Some(OpenComponent::Component(ComponentLines::new(
+                            ComponentKind::Todo,
+                        ))),
+                        "VTIMEZONE" => Some(OpenComponent::Timezone(TimezoneLines::default())),
+                        _ => None,
+                    }
+                    .map(|component| (component, open_components.len() + 1));
+                }
+                Some((OpenComponent::Timezone(timezone), depth))
+                    if *depth == open_components.len()
+                        && matches!(&component_name[..], "STANDARD" | "DAYLIGHT") =>
+                {
+                    timezone.observances.push(vec![]);
+                }
+                Some(_) => {}
+            }
+            open_components.push(component_name);
+            continue;
+        }
+
+        if current
+            .as_ref()
+            .is_some_and(|(_, depth)| *depth == open_components.len())
+        {
+            match current.take() {
+                Some((OpenComponent::Component(component), _)) => {
+                    calendar.components.push(component);
+                }
+                Some((OpenComponent::Timezone(timezone), _)) => calendar.timezones.push(timezone),
+                None => {}
+            }
+        }
+        match open_components.pop() {
+            Some(open) if open == component_name => {}
+            Some(open) => return Err(ParseError::UnterminatedComponent(open)),
+            None => return Err(ParseError::UnexpectedComponentEnd(component_name)),
+        }
+    }
+
+    match open_components.pop() {
+        Some(open) => Err(ParseError::UnterminatedComponent(open)),
+        None => Ok(calendar),
+    }
+}
+
+/// The name of a content line, the part before the parameters and value.
+fn property_name(line: &str) -> &str {
+    line.find([';', ':'])
+        .map_or(line, |name_end| &line[..name_end])
+}
+
+/// The value of a content line, the part after the name and parameters.
+fn property_value(line: &str) -> Result<&str, ParseError> {
+    Ok(find_unquoted(line, ':')?.map_or("", |idx| &line[idx + 1..]))
+}
+
+/// Parses the lines of a `STANDARD` or `DAYLIGHT` sub-component of a `VTIMEZONE`.
+fn parse_observance(lines: &[String]) -> Result<Observance, ParseError> {
+    // The onsets are given in the local time before the onset, represented as UTC
+    let local_datetime = |val: &str, property: &str| {
+        datestring_to_date(val, Some(Tz::UTC), property).map(|datetime| datetime.naive_utc())
+    };
+
+    let mut dt_start = None;
+    let mut offset_from = None;
+    let mut offset_to = None;
+    let mut rrule = None;
+    let mut rdates: Vec<NaiveDateTime> = vec![];
+    for line in lines {
+        let value = property_value(line)?;
+        match &property_name(line).to_uppercase()[..] {
+            "DTSTART" => dt_start = Some(local_datetime(value, "DTSTART")?),
+            "TZOFFSETFROM" => offset_from = Some(parse_utc_offset(value)?),
+            "TZOFFSETTO" => offset_to = Some(parse_utc_offset(value)?),
+            "RRULE" => rrule = Some(RRule::try_from(ContentLineCaptures::new(line)?)?),
+            "RDATE" => {
+                for val in value.split(',').filter(|val| !val.is_empty()) {
+                    rdates.push(local_datetime(val, "RDATE")?);
+                }
+            }
+            // Like `TZNAME` and `COMMENT`
+            _ => {}
+        }
+    }
+
+    let missing = |property: &str| ParseError::MissingProperty(property.into());
+    Ok(Observance {
+        dt_start: dt_start.ok_or_else(|| missing("DTSTART"))?,
+        offset_from: offset_from.ok_or_else(|| missing("TZOFFSETFROM"))?,
+        offset_to: offset_to.ok_or_else(|| missing("TZOFFSETTO"))?,
+        rrule,
+        rdates,
+    })
+}
+
+/// Parses a `RECURRENCE-ID` line, which has the same form as `DTSTART` with an optional
+/// `RANGE` parameter. A `TZID` can refer to the `VTIMEZONE`s of the document in `timezones`.
+pub(crate) fn parse_recurrence_id(
+    line: &str,
+    timezones: &[Tz],
+) -> Result<(DateTime<Tz>, RecurrenceRange), ParseError> {
+    let parts = ContentLineCaptures::new(line)?;
+
+    let mut range = RecurrenceRange::ThisInstance;
+    let mut parameters = vec![];
+    for parameter in split_unquoted(parts.parameters.unwrap_or_default(), ';') {
+        match parameter.split_once('=') {
+            Some((name, value)) if name.eq_ignore_ascii_case("RANGE") => {
+                if !value.eq_ignore_ascii_case("THISANDFUTURE") {
+                    return Err(ParseError::InvalidRecurrenceRange(value.into()));
+                }
+                range = RecurrenceRange::ThisAndFuture;
+            }
+            _ => parameters.push(parameter),
+        }
+    }
+    let parameters = parameters.join(";");
+
+    let start = StartDateContentLine::try_from_with_local_tzid(
+        &ContentLineCaptures {
+            parameters: Some(&parameters),
+            ..parts
+        },
+        None,
+        timezones,
+    )?;
+    Ok((start.datetime, range))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parses_components_of_calendar() {
+        let input = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:event-1\r\n\
+            SUMMARY:Standup\r\n\
+            DTSTART:20210101T090000Z\r\n\
+            RRULE:FREQ=DAILY;\r\n COUNT=3\r\n\
+            BEGIN:VALARM\r\n\
+            TRIGGER:-PT15M\r\n\
+            DURATION:PT5M\r\n\
+            END:VALARM\r\n\
+            DURATION:PT15M\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VTODO\r\n\
+            UID:todo-1\r\n\
+            DTSTART;VALUE=DATE:20210104\r\n\
+            DUE;VALUE=DATE:20210105\r\n\
+            END:VTODO\r\n\
+            BEGIN:VJOURNAL\r\n\
+            DTSTART:20210101T090000Z\r\n\
+            END:VJOURNAL\r\n\
+            END:VCALENDAR\r\n";
+
+        let components = parse_components(input).map(|calendar| calendar.components);
+        assert_eq!(
+            components,
+            Ok(vec![
+                ComponentLines {
+                    kind: ComponentKind::Event,
+                    uid: Some("event-1".into()),
+                    recurrence_id: None,
+                    cancelled: false,
+                    lines: vec![
+                        "DTSTART:20210101T090000Z".into(),
+                        "RRULE:FREQ=DAILY;COUNT=3".into(),
+                        "DURATION:PT15M".into(),
+                    ],
+                },
+                ComponentLines {
+                    kind: ComponentKind::Todo,
+                    uid: Some("todo-1".into()),
+                    recurrence_id: None,
+                    cancelled: false,
+                    lines: vec![
+                        "DTSTART;VALUE=DATE:20210104".into(),
+                        "DTEND;VALUE=DATE:20210105".into(),
+                    ],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_timezones_of_calendar() {
+        let input = "BEGIN:VCALENDAR\n\
+            BEGIN:VTIMEZONE\n\
+            TZID:Custom\n\
+            X-LIC-LOCATION:Custom\n\
+            BEGIN:STANDARD\n\
+            DTSTART:16010101T030000\n\
+            TZOFFSETFROM:+0200\n\
+            TZOFFSETTO:+0100\n\
+            END:STANDARD\n\
+            BEGIN:X-UNKNOWN\n\
+            TZOFFSETTO:+0300\n\
+            END:X-UNKNOWN\n\
+            BEGIN:DAYLIGHT\n\
+            DTSTART:16010101T020000\n\
+            TZOFFSETFROM:+0100\n\
+            TZOFFSETTO:+0200\n\
+            END:DAYLIGHT\n\
+            END:VTIMEZONE\n\
+            END:VCALENDAR";
+
+        let timezones = parse_components(input).map(|calendar| calendar.timezones);
+        assert_eq!(
+            timezones,
+            Ok(vec![TimezoneLines {
+                tzid: Some("Custom".into()),
+                observances: vec![
+                    vec![
+                        "DTSTART:16010101T030000".into(),
+                        "TZOFFSETFROM:+0200".into(),
+                        "TZOFFSETTO:+0100".into(),
+                    ],
+                    vec![
+                        "DTSTART:16010101T020000".into(),
+                        "TZOFFSETFROM:+0100".into(),
+                        "TZOFFSETTO:+0200".into(),
+                    ],
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn parses_recurrence_id_of_component() {
+        let tests = [
+            (
+                "RECURRENCE-ID:20210102T090000Z",
+                Tz::UTC.with_ymd_and_hms(2021, 1, 2, 9, 0, 0).unwrap(),
+                RecurrenceRange::ThisInstance,
+            ),
+            (
+                "Recurrence-Id;RANGE=THISANDFUTURE;TZID=Europe/Berlin:20210102T090000",
+                Tz::Europe__Berlin
+                    .with_ymd_and_hms(2021, 1, 2, 9, 0, 0)
+                    .unwrap(),
+                RecurrenceRange::ThisAndFuture,
+            ),
+        ];
+        for (line, expected_start, expected_range) in tests {
+            let input = format!("BEGIN:VEVENT\nSTATUS:CANCELLED\n{line}\nEND:VEVENT");
+            let components = parse_components(&input).unwrap().components;
+            assert_eq!(components[0].recurrence_id.as_deref(), Some(line));
+            assert!(components[0].cancelled);
+            assert_eq!(
+                parse_recurrence_id(line, &[]),
+                Ok((expected_start, expected_range))
+            );
+        }
+        assert_eq!(
+            parse_recurrence_id("RECURRENCE-ID;RANGE=THISANDPRIOR:20210102T090000Z", &[]),
+            Err(ParseError::InvalidRecurrenceRange("THISANDPRIOR".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_components() {
+        let tests = [
+            (
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR",
+                ParseError::UnterminatedComponent("VEVENT".into()),
+            ),
+            (
+                "BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VEVENT",
+                ParseError::UnterminatedComponent("VCALENDAR".into()),
+            ),
+            (
+                "END:VEVENT",
+                ParseError::UnexpectedComponentEnd("VEVENT".into()),
+            ),
+        ];
+        for (input, expected_error) in tests {
+            assert_eq!(parse_components(input).err(), Some(expected_error));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::parser::{regex::get_property_name, ParseError};
+use crate::parser::{regex::get_property_name, utils::find_unquoted, ParseError};
 
 use super::PropertyName;
 
@@ -22,21 +22,24 @@ impl<'a> ContentLineCaptures<'a> {
                 value: line,
             }),
             property_name => {
-                let mut parameters = None;
-                if line.starts_with(&format!("{};", property_name)) {
-                    let only_colon_idx = line.find(':');
-                    if let Some(only_colon_idx) = only_colon_idx {
-                        parameters =
-                            Some(&line[property_name.to_string().len() + 1..only_colon_idx]);
-                    }
-                }
+                // Quoted parameter values may contain a ':', so the value starts after
+                // the first ':' outside of quotes.
+                let value_idx = find_unquoted(line, ':')?;
+                let name_len = property_name.to_string().len();
+                let has_parameters = line.get(..=name_len).is_some_and(|prefix| {
+                    prefix.eq_ignore_ascii_case(&format!("{property_name};"))
+                });
+
+                let parameters = match value_idx {
+                    Some(value_idx) if has_parameters => Some(&line[name_len + 1..value_idx]),
+                    _ => None,
+                };
 
                 Ok(Self {
                     property_name,
                     parameters,
-                    value: line
-                        .split_once(':')
-                        .map(|(_name, val)| val)
+                    value: value_idx
+                        .map(|value_idx| &line[value_idx + 1..])
                         .unwrap_or_default(),
                 })
             }
@@ -108,6 +111,22 @@ mod tests {
                     value: "FREQ=DAILY;COUNT=10",
                 },
             ),
+            (
+                "dtstart;tzid=Europe/London:20120251T023000Z",
+                ContentLineCaptures {
+                    property_name: PropertyName::DtStart,
+                    parameters: Some("tzid=Europe/London"),
+                    value: "20120251T023000Z",
+                },
+            ),
+            (
+                "Exdate;X-NOTE=\"a;b:c\";TZID=\"Europe/London\":20120251T023000",
+                ContentLineCaptures {
+                    property_name: PropertyName::ExDate,
+                    parameters: Some("X-NOTE=\"a;b:c\";TZID=\"Europe/London\""),
+                    value: "20120251T023000",
+                },
+            ),
         ];
         for (input, expected_output) in tests {
             let output = ContentLineCaptures::new(input);
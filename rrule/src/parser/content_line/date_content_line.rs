@@ -1,16 +1,19 @@
 use std::{collections::HashMap, str::FromStr};
 
+use chrono::NaiveDate;
 use log::warn;
 
 use crate::{
     parser::{
-        datetime::{datestring_to_date, parse_timezone},
+        datetime::{
+            datestring_to_date, datestring_to_naive_date, datestring_to_period, resolve_timezone,
+        },
         ParseError,
     },
-    Tz,
+    Period, Tz,
 };
 
-use super::{content_line_parts::ContentLineCaptures, parameters::parse_parameters};
+use super::{content_line_parts::ContentLineCaptures, parameters::parse_parameters, PropertyName};
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub enum DateParameter {
@@ -31,32 +34,65 @@ impl FromStr for DateParameter {
     }
 }
 
-impl TryFrom<ContentLineCaptures<'_>> for Vec<chrono::DateTime<Tz>> {
+/// The values of an `RDATE` or `EXDATE` property, depending on its `VALUE` parameter.
+#[derive(Debug, PartialEq)]
+pub(crate) enum DateValues {
+    /// `VALUE=DATE-TIME`, which is the default.
+    DateTime(Vec<chrono::DateTime<Tz>>),
+    /// `VALUE=DATE`, calendar dates without a time part.
+    Date(Vec<NaiveDate>),
+    /// `VALUE=PERIOD`, periods of time which are only allowed for `RDATE`.
+    Period(Vec<Period>),
+}
+
+impl TryFrom<ContentLineCaptures<'_>> for DateValues {
     type Error = ParseError;
 
     fn try_from(value: ContentLineCaptures) -> Result<Self, Self::Error> {
+        Self::from_content_line(value, &[])
+    }
+}
+
+impl DateValues {
+    /// Parses the values of an `RDATE` or `EXDATE` line, with the `VTIMEZONE`s of the document
+    /// which a `TZID` can refer to.
+    pub(crate) fn from_content_line(
+        value: ContentLineCaptures,
+        timezones: &[Tz],
+    ) -> Result<Self, ParseError> {
         let parameters: HashMap<DateParameter, String> = value
             .parameters
             .map(parse_parameters)
             .transpose()?
             .unwrap_or_default();
 
+        let property = format!("{}", value.property_name);
+        let values = value.value.split(',').filter(|val| !val.is_empty());
+
+        let timezone = parameters
+            .get(&DateParameter::Timezone)
+            .map(|tz| resolve_timezone(tz, timezones))
+            .transpose()?;
+
         match parameters
             .get(&DateParameter::Value)
             .map(|val| val.to_ascii_lowercase())
             .as_deref()
         {
             Some("date") => {
-                warn!(
-                    "Parameter `DATE` is not supported for property name: `{}`. The dates will be interpreter with the `DATE-TIME` parameter instead.",
-                    value.property_name
-                );
+                let dates = values
+                    .map(|val| datestring_to_naive_date(val, &property))
+                    .collect::<Result<Vec<_>, _>>()?;
+                return Ok(Self::Date(dates));
             }
             Some("period") => {
-                warn!(
-                    "Parameter `PERIOD` is not supported for property name: `{}`. The dates will be interpreter with the `DATE-TIME` parameter instead.",
-                    value.property_name
-                );
+                if value.property_name != PropertyName::RDate {
+                    return Err(ParseError::PeriodNotAllowed(property));
+                }
+                let periods = values
+                    .map(|val| datestring_to_period(val, timezone, &property))
+                    .collect::<Result<Vec<_>, _>>()?;
+                return Ok(Self::Period(periods));
             }
             Some("date-time") => {}
             Some(param) => {
@@ -68,30 +104,19 @@ impl TryFrom<ContentLineCaptures<'_>> for Vec<chrono::DateTime<Tz>> {
             None => {}
         }
 
-        let timezone = parameters
-            .get(&DateParameter::Timezone)
-            .map(|tz| parse_timezone(tz))
-            .transpose()?;
-        let property = format!("{}", value.property_name);
+        let dates = values
+            .map(|val| datestring_to_date(val, timezone, &property))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let mut dates = vec![];
-        for val in value.value.split(',') {
-            if val.is_empty() {
-                continue;
-            }
-            let datetime = datestring_to_date(val, timezone, &property)?;
-            dates.push(datetime);
-        }
-
-        Ok(dates)
+        Ok(Self::DateTime(dates))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::TimeZone;
+    use chrono::{Duration, TimeZone};
 
-    use crate::{core::Tz, parser::content_line::PropertyName};
+    use crate::core::Tz;
 
     use super::*;
 
@@ -106,7 +131,7 @@ mod tests {
                     parameters: None,
                     value: "19970714T123000Z",
                 },
-                vec![UTC.with_ymd_and_hms(1997, 7, 14, 12, 30, 0).unwrap()],
+                DateValues::DateTime(vec![UTC.with_ymd_and_hms(1997, 7, 14, 12, 30, 0).unwrap()]),
             ),
             (
                 ContentLineCaptures {
@@ -114,7 +139,9 @@ mod tests {
                     parameters: None,
                     value: "19970714T123000",
                 },
-                vec![Tz::LOCAL.with_ymd_and_hms(1997, 7, 14, 12, 30, 0).unwrap()],
+                DateValues::DateTime(vec![Tz::LOCAL
+                    .with_ymd_and_hms(1997, 7, 14, 12, 30, 0)
+                    .unwrap()]),
             ),
             (
                 ContentLineCaptures {
@@ -122,12 +149,37 @@ mod tests {
                     parameters: Some("VALUE=DATE;TZID=UTC"),
                     value: "19970101,19970120,19970217,19970421",
                 },
-                vec![
-                    UTC.with_ymd_and_hms(1997, 1, 1, 0, 0, 0).unwrap(),
-                    UTC.with_ymd_and_hms(1997, 1, 20, 0, 0, 0).unwrap(),
-                    UTC.with_ymd_and_hms(1997, 2, 17, 0, 0, 0).unwrap(),
-                    UTC.with_ymd_and_hms(1997, 4, 21, 0, 0, 0).unwrap(),
-                ],
+                DateValues::Date(vec![
+                    NaiveDate::from_ymd_opt(1997, 1, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(1997, 1, 20).unwrap(),
+                    NaiveDate::from_ymd_opt(1997, 2, 17).unwrap(),
+                    NaiveDate::from_ymd_opt(1997, 4, 21).unwrap(),
+                ]),
+            ),
+            (
+                ContentLineCaptures {
+                    property_name: PropertyName::ExDate,
+                    parameters: Some("VALUE=DATE-TIME;TZID=UTC"),
+                    value: "19970101T090000",
+                },
+                DateValues::DateTime(vec![UTC.with_ymd_and_hms(1997, 1, 1, 9, 0, 0).unwrap()]),
+            ),
+            (
+                ContentLineCaptures {
+                    property_name: PropertyName::RDate,
+                    parameters: Some("VALUE=PERIOD"),
+                    value: "19960403T020000Z/19960403T040000Z,19960404T010000Z/PT3H",
+                },
+                DateValues::Period(vec![
+                    Period::new(
+                        UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap(),
+                        UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap(),
+                    ),
+                    Period::with_duration(
+                        UTC.with_ymd_and_hms(1996, 4, 4, 1, 0, 0).unwrap(),
+                        Duration::hours(3),
+                    ),
+                ]),
             ),
         ];
 
@@ -136,4 +188,33 @@ mod tests {
             assert_eq!(output, Ok(expected_output));
         }
     }
+
+    #[test]
+    fn rejects_datetime_with_date_value() {
+        let input = ContentLineCaptures {
+            property_name: PropertyName::ExDate,
+            parameters: Some("VALUE=DATE"),
+            value: "19970101,19970120T090000Z",
+        };
+        let output = DateValues::try_from(input);
+        assert_eq!(
+            output,
+            Err(ParseError::ParameterValueMismatch {
+                parameter: "VALUE".into(),
+                parameter_value: "DATE".into(),
+                found_value: "DATE-TIME".into()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_period_for_exdate() {
+        let input = ContentLineCaptures {
+            property_name: PropertyName::ExDate,
+            parameters: Some("VALUE=PERIOD"),
+            value: "19960403T020000Z/PT2H",
+        };
+        let output = DateValues::try_from(input);
+        assert_eq!(output, Err(ParseError::PeriodNotAllowed("EXDATE".into())));
+    }
 }
@@ -12,6 +12,7 @@ use crate::Tz;
 use crate::Unvalidated;
 
 pub(crate) use content_line_parts::ContentLineCaptures;
+pub(crate) use date_content_line::DateValues;
 pub(crate) use start_date_content_line::StartDateContentLine;
 
 use super::ParseError;
@@ -20,8 +21,10 @@ use super::ParseError;
 pub(crate) enum ContentLine {
     RRule(RRule<Unvalidated>),
     ExRule(RRule<Unvalidated>),
-    ExDate(Vec<chrono::DateTime<Tz>>),
-    RDate(Vec<chrono::DateTime<Tz>>),
+    ExDate(DateValues),
+    RDate(DateValues),
+    DtEnd(chrono::DateTime<Tz>),
+    Duration(chrono::Duration),
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -31,6 +34,9 @@ pub(crate) enum PropertyName {
     ExDate,
     RDate,
     DtStart,
+    DtEnd,
+    Duration,
+    RecurrenceId,
 }
 
 impl Display for PropertyName {
@@ -41,6 +47,9 @@ impl Display for PropertyName {
             Self::ExDate => write!(f, "EXDATE"),
             Self::RDate => write!(f, "RDATE"),
             Self::DtStart => write!(f, "DTSTART"),
+            Self::DtEnd => write!(f, "DTEND"),
+            Self::Duration => write!(f, "DURATION"),
+            Self::RecurrenceId => write!(f, "RECURRENCE-ID"),
         }
     }
 }
@@ -55,6 +64,9 @@ impl FromStr for PropertyName {
             "RDATE" => Self::RDate,
             "EXDATE" => Self::ExDate,
             "DTSTART" => Self::DtStart,
+            "DTEND" => Self::DtEnd,
+            "DURATION" => Self::Duration,
+            "RECURRENCE-ID" => Self::RecurrenceId,
             _ => return Err(ParseError::UnrecognizedPropertyName(s.into())),
         };
         Ok(name)
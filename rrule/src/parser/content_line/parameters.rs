@@ -1,21 +1,32 @@
 use std::{collections::HashMap, hash::Hash, str::FromStr};
 
-use crate::parser::ParseError;
+use crate::parser::{utils::split_unquoted, ParseError};
 
 /// Parses a string of semicolon seperated key value pairs into a `HashMap` with
 /// predefined keys. It will return an error if duplicate keys are found.
+///
+/// Values may be quoted, in which case they can contain `;` and `:`, and the quotes are
+/// removed. Unrecognized experimental (`X-`) keys are ignored.
 pub(super) fn parse_parameters<K: FromStr<Err = ParseError> + Hash + Eq>(
     raw_parameters: &str,
 ) -> Result<HashMap<K, String>, ParseError> {
     let mut parameters = HashMap::new();
-    for raw_parameter in raw_parameters.split(';') {
+    for raw_parameter in split_unquoted(raw_parameters, ';') {
         if raw_parameter.is_empty() {
             continue;
         }
         let (raw_parameter, value) = raw_parameter
             .split_once('=')
             .ok_or_else(|| ParseError::InvalidParameterFormat(raw_parameter.into()))?;
-        let parameter = K::from_str(raw_parameter)?;
+        let parameter = match K::from_str(raw_parameter) {
+            Ok(parameter) => parameter,
+            Err(ParseError::UnrecognizedParameter(_)) if is_experimental(raw_parameter) => continue,
+            Err(err) => return Err(err),
+        };
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
 
         if parameters.insert(parameter, value.into()).is_some() {
             return Err(ParseError::DuplicateProperty(raw_parameter.into()));
@@ -24,6 +35,12 @@ pub(super) fn parse_parameters<K: FromStr<Err = ParseError> + Hash + Eq>(
     Ok(parameters)
 }
 
+/// Whether a parameter name, e.g. `X-EXAMPLE`, is an experimental name.
+pub(super) fn is_experimental(name: &str) -> bool {
+    name.get(..2)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::date_content_line::DateParameter;
@@ -53,6 +70,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_quoted_property_parameters() {
+        let tests = [
+            (
+                "TZID=\"Europe/London\"",
+                [(DateParameter::Timezone, "Europe/London".to_string())]
+                    .into_iter()
+                    .collect::<HashMap<_, _>>(),
+            ),
+            (
+                "X-LABEL=\"a;b:c\";value=DATE",
+                [(DateParameter::Value, "DATE".to_string())]
+                    .into_iter()
+                    .collect::<HashMap<_, _>>(),
+            ),
+        ];
+
+        for (input, expected_output) in tests {
+            let output = parse_parameters(input);
+            assert_eq!(output, Ok(expected_output));
+        }
+    }
+
+    #[test]
+    fn ignores_experimental_property_parameters() {
+        let tests = [
+            (
+                "X-FOO=BAR;TZID=Europe/London;x-bar=1",
+                [(DateParameter::Timezone, "Europe/London".to_string())]
+                    .into_iter()
+                    .collect::<HashMap<_, _>>(),
+            ),
+            ("X-FOO=BAR", HashMap::new()),
+        ];
+
+        for (input, expected_output) in tests {
+            let output = parse_parameters(input);
+            assert_eq!(output, Ok(expected_output));
+        }
+    }
+
     #[test]
     fn rejecets_unrecognized_property_parameters() {
         let tests = [(
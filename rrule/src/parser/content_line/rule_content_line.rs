@@ -4,10 +4,13 @@ use chrono::Weekday;
 
 use crate::{
     parser::{
-        content_line::parameters::parse_parameters, datetime::parse_weekdays, str_to_weekday,
-        utils::parse_str_to_vec, ParseError,
+        content_line::parameters::{is_experimental, parse_parameters},
+        datetime::parse_weekdays,
+        str_to_weekday,
+        utils::{parse_str_to_vec, split_unquoted},
+        ParseError,
     },
-    Frequency, RRule, Unvalidated,
+    Frequency, RRule, RScale, Skip, Unvalidated,
 };
 
 use super::content_line_parts::ContentLineCaptures;
@@ -32,6 +35,8 @@ pub enum RRuleProperty {
     ByEaster,
     XIncludeDtstart,
     LocalTzid,
+    RScale,
+    Skip,
 }
 
 impl FromStr for RRuleProperty {
@@ -57,6 +62,8 @@ impl FromStr for RRuleProperty {
             "BYEASTER" => Self::ByEaster,
             "X-INCLUDE-DTSTART" => Self::XIncludeDtstart,
             "LOCAL-TZID" => Self::LocalTzid,
+            "RSCALE" => Self::RScale,
+            "SKIP" => Self::Skip,
             _ => return Err(ParseError::UnrecognizedParameter(s.into())),
         };
         Ok(prop)
@@ -68,7 +75,11 @@ impl TryFrom<ContentLineCaptures<'_>> for RRule<Unvalidated> {
 
     fn try_from(value: ContentLineCaptures) -> Result<Self, Self::Error> {
         if let Some(parameters) = value.parameters {
-            if !parameters.is_empty() {
+            // Experimental parameters are ignored, as they don't change the rule
+            if split_unquoted(parameters, ';')
+                .into_iter()
+                .any(|parameter| !parameter.is_empty() && !is_experimental(parameter))
+            {
                 return Err(ParseError::PropertyParametersNotSupported(
                     parameters.into(),
                 ));
@@ -141,11 +152,28 @@ fn props_to_rrule(
         })
         .transpose()?
         .unwrap_or_default();
-    let by_month = props
+    let (by_month, by_leap_month) = props
         .get(&RRuleProperty::ByMonth)
         .map(|by_month| {
-            parse_str_to_vec(by_month, |month| (1..=12).contains(&month))
-                .map_err(|_| ParseError::InvalidByMonth(by_month.into()))
+            // Leap months have a `L` suffix, e.g. `5L`.
+            let (by_leap_month, by_regular_month): (Vec<_>, Vec<_>) = by_month
+                .split(',')
+                .partition(|month| month.ends_with(['L', 'l']));
+            let by_leap_month = by_leap_month
+                .iter()
+                .map(|month| &month[..month.len() - 1])
+                .collect::<Vec<_>>();
+            let parse_months = |months: &[&str]| {
+                if months.contains(&"") {
+                    return Err(ParseError::InvalidByMonth(by_month.into()));
+                }
+                parse_str_to_vec(&months.join(","), |month| (1..=12).contains(&month))
+                    .map_err(|_| ParseError::InvalidByMonth(by_month.into()))
+            };
+            Ok((
+                parse_months(&by_regular_month)?,
+                parse_months(&by_leap_month)?,
+            ))
         })
         .transpose()?
         .unwrap_or_default();
@@ -225,6 +253,16 @@ fn props_to_rrule(
         )
         .transpose()?;
 
+    let rscale = props
+        .get(&RRuleProperty::RScale)
+        .map(|rscale| RScale::from_str(rscale))
+        .transpose()?;
+    let skip = props
+        .get(&RRuleProperty::Skip)
+        .map(|skip| Skip::from_str(skip))
+        .transpose()?
+        .unwrap_or_default();
+
     // Check if mandatory fields are set
     Ok(RRule {
         freq,
@@ -234,6 +272,7 @@ fn props_to_rrule(
         week_start,
         by_set_pos,
         by_month,
+        by_leap_month,
         by_month_day,
         by_n_month_day: vec![],
         by_year_day,
@@ -246,6 +285,8 @@ fn props_to_rrule(
         include_dtstart,
         local_tzid,
         dtstart_is_floating: false, // Default to false during parsing; will be set later in RRuleSet
+        rscale,
+        skip,
         stage: PhantomData,
     })
 }
@@ -389,14 +430,24 @@ mod tests {
 
     #[test]
     fn rejects_property_parameters_in_rrule_line() {
-        let tests = [(
-            ContentLineCaptures {
-                property_name: PropertyName::RRule,
-                parameters: Some("TZID=Europe/London"),
-                value: "BYHOUR=4",
-            },
-            ParseError::PropertyParametersNotSupported("TZID=Europe/London".into()),
-        )];
+        let tests = [
+            (
+                ContentLineCaptures {
+                    property_name: PropertyName::RRule,
+                    parameters: Some("TZID=Europe/London"),
+                    value: "BYHOUR=4",
+                },
+                ParseError::PropertyParametersNotSupported("TZID=Europe/London".into()),
+            ),
+            (
+                ContentLineCaptures {
+                    property_name: PropertyName::RRule,
+                    parameters: Some("X-FOO=BAR;TZID=Europe/London"),
+                    value: "BYHOUR=4",
+                },
+                ParseError::PropertyParametersNotSupported("X-FOO=BAR;TZID=Europe/London".into()),
+            ),
+        ];
 
         for (input, expected_output) in tests {
             let output = RRule::try_from(input);
@@ -404,6 +455,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ignores_experimental_parameters_in_rrule_line() {
+        let input = ContentLineCaptures {
+            property_name: PropertyName::RRule,
+            parameters: Some("X-FOO=BAR;X-NOTE=\"a;b:c\""),
+            value: "FREQ=DAILY;X-FOO=BAR",
+        };
+        let output = RRule::try_from(input).unwrap();
+        assert_eq!(output.freq, Frequency::Daily);
+    }
+
     #[test]
     fn rejects_invalid_freq() {
         let mut props = HashMap::new();
@@ -443,4 +505,57 @@ mod tests {
             ParseError::InvalidByMinute("4,5,64".into())
         );
     }
+
+    #[test]
+    fn parses_rscale_and_skip() {
+        let input = ContentLineCaptures {
+            property_name: PropertyName::RRule,
+            parameters: None,
+            value: "RSCALE=gregorian;FREQ=MONTHLY;SKIP=BACKWARD",
+        };
+        assert_eq!(
+            RRule::try_from(input),
+            Ok(RRule {
+                freq: Frequency::Monthly,
+                rscale: Some(RScale::Gregorian),
+                skip: Skip::Backward,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn parses_leap_months() {
+        let mut props = HashMap::new();
+        props.insert(RRuleProperty::Freq, "YEARLY".into());
+        props.insert(RRuleProperty::ByMonth, "6,5L,1,12l".into());
+        let rrule = props_to_rrule(&props).unwrap();
+        assert_eq!(rrule.by_month, vec![1, 6]);
+        assert_eq!(rrule.by_leap_month, vec![5, 12]);
+
+        for by_month in ["13L", "L", "5LL", "1,,5L"] {
+            props.insert(RRuleProperty::ByMonth, by_month.into());
+            let res = props_to_rrule(&props);
+            assert_eq!(
+                res.unwrap_err(),
+                ParseError::InvalidByMonth(by_month.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_rscale_and_skip() {
+        let mut props = HashMap::new();
+        props.insert(RRuleProperty::Freq, "MONTHLY".into());
+        props.insert(RRuleProperty::Skip, "SIDEWAYS".into());
+        let res = props_to_rrule(&props);
+        assert_eq!(res.unwrap_err(), ParseError::InvalidSkip("SIDEWAYS".into()));
+
+        props.insert(RRuleProperty::RScale, "MARTIAN".into());
+        let res = props_to_rrule(&props);
+        assert_eq!(
+            res.unwrap_err(),
+            ParseError::InvalidRScale("MARTIAN".into())
+        );
+    }
 }
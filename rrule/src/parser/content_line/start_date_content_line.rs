@@ -7,7 +7,7 @@ use super::{
 use crate::{
     core::Tz,
     parser::{
-        datetime::{datestring_to_date_with_local_tzid, parse_timezone},
+        datetime::{datestring_to_date_with_local_tzid, resolve_timezone},
         ParseError,
     },
 };
@@ -34,10 +34,12 @@ impl StartDateContentLine {
         self.value == "DATE-TIME" && self.timezone.is_none()
     }
 
-    /// Create a StartDateContentLine with optional LOCAL-TZID support
+    /// Create a StartDateContentLine with optional LOCAL-TZID support, and the `VTIMEZONE`s
+    /// of the document which a `TZID` can refer to.
     pub(crate) fn try_from_with_local_tzid(
         content_line: &ContentLineCaptures,
         local_tzid: Option<Tz>,
+        timezones: &[Tz],
     ) -> Result<Self, ParseError> {
         let parameters: HashMap<DateParameter, String> = content_line
             .parameters
@@ -48,7 +50,7 @@ impl StartDateContentLine {
 
         let mut timezone = parameters
             .get(&DateParameter::Timezone)
-            .map(|tz| parse_timezone(tz))
+            .map(|tz| resolve_timezone(tz, timezones))
             .transpose()?;
         if timezone.is_none() && content_line.value.to_uppercase().ends_with('Z') {
             timezone = Some(UTC);
@@ -70,7 +72,8 @@ impl StartDateContentLine {
             }
         }
 
-        let datetime = datestring_to_date_with_local_tzid(content_line.value, timezone, "DTSTART", local_tzid)?;
+        let property = content_line.property_name.to_string();
+        let datetime = datestring_to_date_with_local_tzid(content_line.value, timezone, &property, local_tzid)?;
 
         Ok(Self {
             datetime,
@@ -85,7 +88,7 @@ impl TryFrom<&ContentLineCaptures<'_>> for StartDateContentLine {
 
     fn try_from(content_line: &ContentLineCaptures) -> Result<Self, Self::Error> {
         // Use the existing method with no LOCAL-TZID for backward compatibility
-        Self::try_from_with_local_tzid(content_line, None)
+        Self::try_from_with_local_tzid(content_line, None, &[])
     }
 }
 
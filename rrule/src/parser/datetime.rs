@@ -1,14 +1,44 @@
-use std::str::FromStr;
+use super::{
+    regex::{ParsedDateString, ParsedDuration},
+    ParseError,
+};
+use crate::{
+    core::{lookup_timezone, Tz},
+    NWeekday, Period,
+};
+use chrono::{Duration, FixedOffset, NaiveDate, TimeZone, Weekday};
 
-use super::{regex::ParsedDateString, ParseError};
-use crate::{core::Tz, NWeekday};
-use chrono::{NaiveDate, TimeZone, Weekday};
-
-/// Attempts to convert a `str` to a `chrono_tz::Tz`.
+/// Attempts to convert a `str` to a [`Tz`] with the installed `TimezoneProvider`, or to a fixed
+/// offset if it is named like `UTC+05:30`. With the `windows-zones` feature, a Windows timezone
+/// name is accepted also.
 pub(crate) fn parse_timezone(tz: &str) -> Result<Tz, ParseError> {
-    chrono_tz::Tz::from_str(tz)
-        .map_err(|_| ParseError::InvalidTimezone(tz.into()))
-        .map(Tz::Tz)
+    let timezone = lookup_timezone(tz);
+    // Outlook and Exchange use Windows timezone names, like `Pacific Standard Time`
+    #[cfg(feature = "windows-zones")]
+    let timezone = timezone.or_else(|| crate::core::windows_to_iana(tz).and_then(lookup_timezone));
+    timezone
+        .or_else(|| parse_fixed_offset_name(tz).map(Tz::Fixed))
+        .ok_or_else(|| ParseError::InvalidTimezone(tz.into()))
+}
+
+/// Attempts to convert the name of a fixed offset, like `UTC+05:30`, to a `FixedOffset`.
+fn parse_fixed_offset_name(tz: &str) -> Option<FixedOffset> {
+    let offset = tz.strip_prefix("UTC")?;
+    let sign = offset.get(..1)?;
+    let parts = offset[1..].split(':').collect::<Vec<_>>();
+    if !matches!(parts.len(), 2 | 3) || parts.iter().any(|part| part.len() != 2) {
+        return None;
+    }
+    parse_utc_offset(&format!("{sign}{}", parts.concat())).ok()
+}
+
+/// Attempts to convert a `TZID` to a [`Tz`]. The `VTIMEZONE`s of the document, given as
+/// `timezones`, take precedence over the timezones of `chrono_tz`.
+pub(crate) fn resolve_timezone(tz: &str, timezones: &[Tz]) -> Result<Tz, ParseError> {
+    match timezones.iter().find(|timezone| timezone.name() == tz) {
+        Some(timezone) => Ok(*timezone),
+        None => parse_timezone(tz),
+    }
 }
 
 /// Convert a datetime string and a timezone to a `chrono::DateTime<Tz>`.
@@ -119,6 +149,121 @@ pub(crate) fn datestring_to_date_with_local_tzid(
     Ok(datetime)
 }
 
+/// Convert a date string with the format `YYYYMMDD` to a `chrono::NaiveDate`.
+/// This is used for properties which have the `VALUE=DATE` parameter set.
+pub(crate) fn datestring_to_naive_date(dt: &str, property: &str) -> Result<NaiveDate, ParseError> {
+    let ParsedDateString {
+        year,
+        month,
+        day,
+        time,
+        ..
+    } = ParsedDateString::from_ical_datetime(dt).map_err(|_| ParseError::InvalidDateTime {
+        value: dt.into(),
+        property: property.into(),
+    })?;
+
+    if time.is_some() {
+        return Err(ParseError::ParameterValueMismatch {
+            parameter: "VALUE".into(),
+            parameter_value: "DATE".into(),
+            found_value: "DATE-TIME".into(),
+        });
+    }
+
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| ParseError::InvalidDateTime {
+        value: dt.into(),
+        property: property.into(),
+    })
+}
+
+/// Convert a duration string, like `PT1H30M` or `-P2W`, to a `chrono::Duration`.
+/// Days are taken as exactly 24 hours.
+pub(crate) fn parse_duration(val: &str) -> Result<Duration, ParseError> {
+    let ParsedDuration {
+        negative,
+        weeks,
+        days,
+        hours,
+        minutes,
+        seconds,
+    } = ParsedDuration::from_ical_duration(val)?;
+
+    let duration = Duration::weeks(i64::from(weeks))
+        + Duration::days(i64::from(days))
+        + Duration::hours(i64::from(hours))
+        + Duration::minutes(i64::from(minutes))
+        + Duration::seconds(i64::from(seconds));
+    // A longer duration can't be between two datetimes of `chrono`.
+    let longest =
+        chrono::DateTime::<chrono::Utc>::MAX_UTC - chrono::DateTime::<chrono::Utc>::MIN_UTC;
+    if duration > longest {
+        return Err(ParseError::InvalidDuration(val.into()));
+    }
+
+    Ok(if negative { -duration } else { duration })
+}
+
+/// Convert a UTC offset string with the format `(+|-)HHMM(SS)`, like the `TZOFFSETTO` of a
+/// `VTIMEZONE`, to a `chrono::FixedOffset`.
+/// ref: <https://icalendar.org/iCalendar-RFC-5545/3-3-14-utc-offset.html>
+pub(crate) fn parse_utc_offset(val: &str) -> Result<FixedOffset, ParseError> {
+    let invalid = || ParseError::InvalidUtcOffset(val.into());
+
+    let (sign, digits) = match val.split_at(val.len().min(1)) {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return Err(invalid()),
+    };
+    if !matches!(digits.len(), 4 | 6) || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let part = |idx: usize| -> i32 {
+        digits
+            .get(idx..idx + 2)
+            .map_or(0, |part| part.parse().unwrap_or_default())
+    };
+    let (hours, minutes, seconds) = (part(0), part(2), part(4));
+    if minutes > 59 || seconds > 59 {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds)).ok_or_else(invalid)
+}
+
+/// Convert a period string, like `19960403T020000Z/19960403T040000Z` or
+/// `19960403T020000Z/PT2H`, and a timezone to a [`Period`].
+/// This is used for properties which have the `VALUE=PERIOD` parameter set.
+pub(crate) fn datestring_to_period(
+    val: &str,
+    tz: Option<Tz>,
+    property: &str,
+) -> Result<Period, ParseError> {
+    let invalid_period = || ParseError::InvalidPeriod {
+        value: val.into(),
+        property: property.into(),
+    };
+
+    let (start, end) = val.split_once('/').ok_or_else(invalid_period)?;
+    let start = datestring_to_date(start, tz, property)?;
+
+    let period = if end.starts_with(['P', '+', '-']) {
+        let duration = parse_duration(end)?;
+        if duration < Duration::zero() || start.checked_add_signed(duration).is_none() {
+            return Err(invalid_period());
+        }
+        Period::with_duration(start, duration)
+    } else {
+        let end = datestring_to_date(end, tz, property)?;
+        if end < start {
+            return Err(invalid_period());
+        }
+        Period::new(start, end)
+    };
+
+    Ok(period)
+}
+
 /// Attempts to convert a `str` to a `Weekday`.
 pub(crate) fn str_to_weekday(d: &str) -> Result<Weekday, ParseError> {
     let day = match &d.to_uppercase()[..] {
@@ -218,6 +363,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_fixed_offset_timezones() {
+        let offset = |seconds| Ok(Tz::Fixed(FixedOffset::east_opt(seconds).unwrap()));
+        let tests = [
+            ("UTC+05:30", offset(5 * 3600 + 30 * 60)),
+            ("UTC-08:00", offset(-8 * 3600)),
+            ("UTC+00:00", offset(0)),
+            ("UTC+05:30:15", offset(5 * 3600 + 30 * 60 + 15)),
+            ("UTC", Ok(Tz::UTC)),
+        ];
+        for (tz, expected_output) in tests {
+            assert_eq!(parse_timezone(tz), expected_output);
+        }
+
+        for tz in ["UTC+5:30", "UTC+05:60", "UTC05:30", "UTC+0530", "+05:30"] {
+            assert_eq!(
+                parse_timezone(tz),
+                Err(ParseError::InvalidTimezone(tz.into()))
+            );
+        }
+    }
+
     #[test]
     fn parses_valid_datestime_str() {
         let tests = [
@@ -250,6 +417,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_valid_date_str() {
+        let tests = [
+            ("19970902", NaiveDate::from_ymd_opt(1997, 9, 2).unwrap()),
+            ("20240229", NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+        ];
+
+        for (date_str, expected_output) in tests {
+            let output = datestring_to_naive_date(date_str, "EXDATE");
+            assert_eq!(output, Ok(expected_output));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_date_str() {
+        let tests = [
+            (
+                "20230229",
+                ParseError::InvalidDateTime {
+                    value: "20230229".into(),
+                    property: "EXDATE".into(),
+                },
+            ),
+            (
+                "1997090",
+                ParseError::InvalidDateTime {
+                    value: "1997090".into(),
+                    property: "EXDATE".into(),
+                },
+            ),
+            (
+                "19970902T090000Z",
+                ParseError::ParameterValueMismatch {
+                    parameter: "VALUE".into(),
+                    parameter_value: "DATE".into(),
+                    found_value: "DATE-TIME".into(),
+                },
+            ),
+        ];
+
+        for (date_str, expected_output) in tests {
+            let output = datestring_to_naive_date(date_str, "EXDATE");
+            assert_eq!(output, Err(expected_output));
+        }
+    }
+
     #[test]
     fn rejects_invalid_datetime_str() {
         let tests = [
@@ -264,4 +477,65 @@ mod tests {
             assert!(res.is_err());
         }
     }
+
+    #[test]
+    fn parses_valid_durations() {
+        let tests = [
+            ("PT2H", Duration::hours(2)),
+            ("P1W", Duration::weeks(1)),
+            ("P1DT12H", Duration::hours(36)),
+            ("-PT15M", Duration::minutes(-15)),
+        ];
+        for (input, expected_output) in tests {
+            assert_eq!(parse_duration(input), Ok(expected_output));
+        }
+    }
+
+    #[test]
+    fn rejects_durations_longer_than_the_range_of_datetimes() {
+        for input in ["P4000000000W", "-P4000000000W", "P4000000000D"] {
+            assert_eq!(
+                parse_duration(input),
+                Err(ParseError::InvalidDuration(input.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_valid_periods() {
+        let start = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap();
+        let end = Tz::UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap();
+        let tests = [
+            ("19960403T020000Z/19960403T040000Z", Period::new(start, end)),
+            (
+                "19960403T020000Z/PT2H",
+                Period::with_duration(start, Duration::hours(2)),
+            ),
+        ];
+        for (input, expected_output) in tests {
+            assert_eq!(
+                datestring_to_period(input, None, "RDATE"),
+                Ok(expected_output)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_periods() {
+        let tests = [
+            "19960403T020000Z",
+            "19960403T020000Z/",
+            "19960403T020000Z/PT",
+            "19960403T020000Z/-PT2H",
+            "19960403T020000Z/P20000000W",
+            "19960403T040000Z/19960403T020000Z",
+            "/PT2H",
+        ];
+        for input in tests {
+            assert!(
+                datestring_to_period(input, None, "RDATE").is_err(),
+                "{input}"
+            );
+        }
+    }
 }
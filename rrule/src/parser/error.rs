@@ -18,8 +18,20 @@ pub enum ParseError {
         date1: String,
         date2: String,
     },
+    #[error("`{0}` is not a valid duration.")]
+    InvalidDuration(String),
+    #[error("`{value}` is not a valid period for `{property}`. Expected `start/end` or `start/duration` with the end after the start.")]
+    InvalidPeriod { value: String, property: String },
+    #[error("`VALUE=PERIOD` is only allowed for `RDATE`, found it for `{0}`.")]
+    PeriodNotAllowed(String),
+    #[error("`DTEND` and `DURATION` can't be used together, only one of them can specify the end of the event.")]
+    DtEndAndDuration,
     #[error("`{0}` is not a valid frequency.")]
     InvalidFrequency(String),
+    #[error("`{0}` is not a supported RSCALE value. Supported values are `GREGORIAN`, `HEBREW` and `CHINESE`.")]
+    InvalidRScale(String),
+    #[error("`{0}` is not a valid SKIP value. Valid values are `OMIT`, `BACKWARD` and `FORWARD`.")]
+    InvalidSkip(String),
     #[error("`{0}` is not a valid weekday. Valid values are `MO`, `TU`, `WE`, `TH`, `FR`, `SA` and `SU`.")]
     InvalidWeekday(String),
     #[error("`{0}` is not a valid weekday start. Valid values are `MO`, `TU`, `WE`, `TH`, `FR`, `SA` and `SU`.")]
@@ -42,7 +54,7 @@ pub enum ParseError {
     InvalidByYearDay(String),
     #[error("`{0}` is not a valid BYMONTHDAY value. Expected a comma-separated list of values in range -31..=31, e.g. `-30,10`")]
     InvalidByMonthDay(String),
-    #[error("`{0}` is not a valid BYMONTH value. Expected a comma-separated list of values in range 1..=12, e.g. `6,9,10`")]
+    #[error("`{0}` is not a valid BYMONTH value. Expected a comma-separated list of values in range 1..=12, with a `L` suffix for leap months, e.g. `6,9,10` or `5L`")]
     InvalidByMonth(String),
     #[error("`{0}` is not a valid BYMINUTE value. Expected a comma-separated list of values in range 0..=59, e.g. `0,15,30,45`")]
     InvalidByMinute(String),
@@ -67,7 +79,7 @@ pub enum ParseError {
     #[error("Property parameters aren't supported for RRULE / EXRULE, found parameters: `{0}`")]
     PropertyParametersNotSupported(String),
     #[error(
-        "`{0}` is not a valid property name, expected one of: `RRULE,EXRULE,DTSTART,RDATE,EXDATE`"
+        "`{0}` is not a valid property name, expected one of: `RRULE,EXRULE,DTSTART,DTEND,DURATION,RDATE,EXDATE`"
     )]
     UnrecognizedPropertyName(String),
     #[error(
@@ -78,6 +90,42 @@ pub enum ParseError {
         "The value of `DTSTART` was specified in local timezone, but `UNTIL` was specified with a zulu time when it had to be specified in local time as well"
     )]
     DtStartUntilMismatchTimezone,
+    #[error("`{0}` is not a valid UTC offset. Expected `+` or `-` followed by `HHMM` or `HHMMSS`, e.g. `-0500`.")]
+    InvalidUtcOffset(String),
+    #[error("The `VTIMEZONE` `{0}` needs at least one `STANDARD` or `DAYLIGHT` component.")]
+    MissingTimezoneObservance(String),
+    #[error("The `VTIMEZONE` `{0}` has an observance whose `RRULE` isn't yearly or has more than 10000 onsets.")]
+    UnsupportedTimezoneObservance(String),
+    #[error("The `VTIMEZONE` `{0}` can't be stored, as the stored timezone definitions reached their limit of memory.")]
+    TooManyTimezones(String),
+    #[error("`{0}` is not a valid RANGE value for `RECURRENCE-ID`. The only valid value is `THISANDFUTURE`.")]
+    InvalidRecurrenceRange(String),
+    #[error("The `{0}` component isn't closed by a matching `END:{0}`.")]
+    UnterminatedComponent(String),
+    #[error("Found `END:{0}` without a matching `BEGIN:{0}`.")]
+    UnexpectedComponentEnd(String),
+    #[error("`{0}` can't be understood in a recurrence description.")]
+    UnrecognizedText(String),
+    #[error("A recurrence description needs to start with a frequency, e.g. `every week` or `every Monday`, found `{0}`.")]
+    MissingTextFrequency(String),
+    #[error("`{0}` is not a valid number in a recurrence description.")]
+    InvalidTextNumber(String),
+    #[error("`{0}` is not a valid time of the day, e.g. `9:30`, `9am` or `noon`.")]
+    InvalidTextTime(String),
+    #[error("`{0}` is not a valid date, e.g. `March 3, 2025`, `2025-03-03` or `June`.")]
+    InvalidTextDate(String),
+    #[error("The times `{0}` can't be described by one rule, every hour needs to occur at the same minutes and seconds.")]
+    UnsupportedTextTimes(String),
+    #[error("Invalid TZif data for timezone `{0}`.")]
+    InvalidTzif(String),
+    #[error("`{0}` is not a valid property value, it can't contain a line break or, in a parameter, a `\"`.")]
+    InvalidPropertyValue(String),
+    #[error("`{0}` is not a valid jCal value.")]
+    InvalidJCal(String),
+    #[error("Invalid xCal document: {0}.")]
+    InvalidXCal(String),
+    #[error("Invalid binary encoding: {0}.")]
+    InvalidBinary(String),
     #[error("Property parameter `{parameter}` was set to have value `{parameter_value}`, but found `{found_value}` ")]
     ParameterValueMismatch {
         parameter: String,
@@ -1,5 +1,6 @@
 //! Module for parsing text inputs to a [`Grammar`] which can further be used
 //! to construct an [`crate::RRuleSet`].
+mod calendar;
 mod content_line;
 mod datetime;
 mod error;
@@ -8,13 +9,21 @@ mod utils;
 
 use std::str::FromStr;
 
-pub(crate) use content_line::{ContentLine, ContentLineCaptures};
-pub(crate) use datetime::str_to_weekday;
+pub(crate) use calendar::{parse_components, parse_recurrence_id, ComponentLines};
+pub(crate) use content_line::{ContentLine, ContentLineCaptures, DateValues};
+#[cfg(any(feature = "serde", feature = "binary"))]
+pub(crate) use datetime::parse_timezone;
+pub(crate) use datetime::{parse_duration, str_to_weekday};
 pub use error::ParseError;
+#[cfg(feature = "json-schema")]
+pub(crate) use regex::DURATION_PATTERN;
+#[cfg(any(feature = "jcal", feature = "xcal"))]
+pub(crate) use utils::{find_unquoted, split_unquoted};
 
-use crate::RRule;
+use crate::{RRule, Tz};
 
 use self::content_line::{PropertyName, StartDateContentLine};
+use self::utils::unfold_lines;
 
 /// Grammar represents a well-formatted rrule input.
 #[derive(Debug, PartialEq)]
@@ -27,9 +36,17 @@ impl FromStr for Grammar {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let content_lines_parts = s
-            .lines()
-            .map(ContentLineCaptures::new)
+        Self::parse_with_timezones(s, &[])
+    }
+}
+
+impl Grammar {
+    /// Parses the input with the `VTIMEZONE`s of the document which a `TZID` can refer to.
+    pub(crate) fn parse_with_timezones(s: &str, timezones: &[Tz]) -> Result<Self, ParseError> {
+        let lines = unfold_lines(s);
+        let content_lines_parts = lines
+            .iter()
+            .map(|line| ContentLineCaptures::new(line))
             .collect::<Result<Vec<_>, _>>()?;
 
         // Process lines in order to find LOCAL-TZID before DTSTART
@@ -58,7 +75,9 @@ impl FromStr for Grammar {
 
         let start = dtstart_index
             .and_then(|i| content_lines_parts.get(i))
-            .map(|parts| StartDateContentLine::try_from_with_local_tzid(parts, local_tzid))
+            .map(|parts| {
+                StartDateContentLine::try_from_with_local_tzid(parts, local_tzid, timezones)
+            })
             .transpose()?;
 
         let mut content_lines = vec![];
@@ -73,16 +92,47 @@ impl FromStr for Grammar {
                     let rrule = RRule::try_from(parts)?;
                     ContentLine::ExRule(rrule)
                 }
-                PropertyName::RDate => ContentLine::RDate(TryFrom::try_from(parts)?),
-                PropertyName::ExDate => ContentLine::ExDate(TryFrom::try_from(parts)?),
+                PropertyName::RDate => {
+                    ContentLine::RDate(DateValues::from_content_line(parts, timezones)?)
+                }
+                PropertyName::ExDate => {
+                    ContentLine::ExDate(DateValues::from_content_line(parts, timezones)?)
+                }
+                PropertyName::DtEnd => ContentLine::DtEnd(
+                    StartDateContentLine::try_from_with_local_tzid(&parts, local_tzid, timezones)?
+                        .datetime,
+                ),
+                PropertyName::Duration => ContentLine::Duration(parse_duration(parts.value)?),
                 PropertyName::DtStart => {
                     // Nothing to do
                     continue;
                 }
+                // Only meaningful in a component of an iCalendar document, see `calendar`
+                PropertyName::RecurrenceId => {
+                    return Err(ParseError::UnrecognizedPropertyName(
+                        parts.property_name.to_string(),
+                    ))
+                }
             };
             content_lines.push(line);
         }
 
+        // The end of the event can only be given once, by either `DTEND` or `DURATION`
+        let end_lines = content_lines
+            .iter()
+            .filter(|line| matches!(line, ContentLine::DtEnd(_) | ContentLine::Duration(_)))
+            .collect::<Vec<_>>();
+        match end_lines[..] {
+            [ContentLine::DtEnd(_), ContentLine::DtEnd(_), ..] => {
+                return Err(ParseError::DuplicateProperty("DTEND".into()))
+            }
+            [ContentLine::Duration(_), ContentLine::Duration(_), ..] => {
+                return Err(ParseError::DuplicateProperty("DURATION".into()))
+            }
+            [_, _, ..] => return Err(ParseError::DtEndAndDuration),
+            _ => {}
+        }
+
         // Need to be at least one `RDATE` or `RRULE`
         if !content_lines
             .iter()
@@ -103,7 +153,11 @@ mod test {
     use chrono::{TimeZone, Weekday};
 
     use super::*;
-    use crate::{core::Tz, parser::content_line::ContentLine, Frequency, NWeekday, RRule};
+    use crate::{
+        core::Tz,
+        parser::content_line::{ContentLine, DateValues},
+        Frequency, NWeekday, RRule,
+    };
 
     const UTC: Tz = Tz::UTC;
     const BERLIN: Tz = Tz::Europe__Berlin;
@@ -143,10 +197,10 @@ mod test {
             count: Some(5),
             ..Default::default()
         }),
-        ContentLine::ExDate(vec![
+        ContentLine::ExDate(DateValues::DateTime(vec![
             BERLIN.with_ymd_and_hms(2012, 2, 2,13, 0, 0).unwrap(),
             BERLIN.with_ymd_and_hms(2012, 2, 3,13, 0, 0).unwrap(),
-        ])
+        ]))
     ]
 }),
 ("DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;COUNT=5\nEXDATE;TZID=Europe/Berlin:20120202T130000,20120203T130000\nEXRULE:FREQ=WEEKLY;COUNT=10", Grammar {
@@ -157,10 +211,10 @@ mod test {
             count: Some(5),
             ..Default::default()
         }),
-        ContentLine::ExDate(vec![
+        ContentLine::ExDate(DateValues::DateTime(vec![
             BERLIN.with_ymd_and_hms(2012, 2, 2,13, 0, 0).unwrap(),
             BERLIN.with_ymd_and_hms(2012, 2, 3,13, 0, 0).unwrap(),
-        ]),
+        ])),
         ContentLine::ExRule(RRule {
             freq: Frequency::Weekly,
             count: Some(10),
@@ -175,6 +229,29 @@ mod test {
         }
     }
 
+    #[test]
+    fn parses_folded_ics_lines() {
+        let input =
+            "dtstart;X-LABEL=\"Start: now\";TZID=\"Europe/Berlin\":20120201T\r\n 120000\r\n\
+            Rrule:FREQ=DAILY;\r\n\tCOUNT=5\r\n";
+        let grammar = Grammar::from_str(input);
+        assert_eq!(
+            grammar,
+            Ok(Grammar {
+                start: Some(StartDateContentLine {
+                    datetime: BERLIN.with_ymd_and_hms(2012, 2, 1, 12, 0, 0).unwrap(),
+                    timezone: Some(BERLIN),
+                    value: "DATE-TIME"
+                }),
+                content_lines: vec![ContentLine::RRule(RRule {
+                    freq: Frequency::Daily,
+                    count: Some(5),
+                    ..Default::default()
+                })]
+            })
+        );
+    }
+
     #[test]
     fn rejects_input_without_date_generation() {
         let tests = [
@@ -86,13 +86,64 @@ impl ParsedDateString {
     }
 }
 
-/// Get the line property name, the `RRULE:`, `EXRULE:` etc part.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParsedDuration {
+    pub negative: bool,
+    pub weeks: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+}
+
+/// The pattern of a duration, like `PT1H30M` or `-P2W`.
+pub(crate) const DURATION_PATTERN: &str =
+    r"^([+-])?P(?:([0-9]+)W|(?:([0-9]+)D)?(?:T(?:([0-9]+)H)?(?:([0-9]+)M)?(?:([0-9]+)S)?)?)$";
+
+impl ParsedDuration {
+    /// Parses a duration string with format `[+-]P(nW|nDTnHnMnS)` where every
+    /// part of the day and time form is optional, but at least one has to be present.
+    pub(crate) fn from_ical_duration(val: &str) -> Result<Self, ParseError> {
+        static DURATION_RE: OnceLock<Regex> = OnceLock::new();
+
+        let captures = DURATION_RE
+            .get_or_init(|| Regex::new(DURATION_PATTERN).expect("DURATION_RE must compile"))
+            .captures(val)
+            .ok_or_else(|| ParseError::InvalidDuration(val.into()))?;
+
+        // `P` and `PT` on their own match the regex, but are not valid durations.
+        if (2..=6).all(|idx| captures.get(idx).is_none()) || val.ends_with('T') {
+            return Err(ParseError::InvalidDuration(val.into()));
+        }
+
+        let get = |idx: usize| -> Result<u32, ParseError> {
+            captures.get(idx).map_or(Ok(0), |part| {
+                part.as_str()
+                    .parse()
+                    .map_err(|_| ParseError::InvalidDuration(val.into()))
+            })
+        };
+
+        Ok(Self {
+            negative: captures.get(1).map(|sign| sign.as_str()) == Some("-"),
+            weeks: get(2)?,
+            days: get(3)?,
+            hours: get(4)?,
+            minutes: get(5)?,
+            seconds: get(6)?,
+        })
+    }
+}
+
+/// Get the line property name, the `RRULE:`, `EXRULE:` etc part. Property names are
+/// case-insensitive.
 pub(crate) fn get_property_name(val: &str) -> Result<Option<PropertyName>, ParseError> {
     static PARSE_PROPERTY_NAME_RE: OnceLock<Regex> = OnceLock::new();
 
     PARSE_PROPERTY_NAME_RE
         .get_or_init(|| {
-            Regex::new(r"(?m)^([A-Z]+?)[:;]").expect("PARSE_PROPERTY_NAME_RE regex must compile")
+            Regex::new(r"(?m)^([A-Za-z-]+?)[:;]")
+                .expect("PARSE_PROPERTY_NAME_RE regex must compile")
         })
         .captures(val)
         .and_then(|captures| captures.get(1))
@@ -104,7 +155,7 @@ pub(crate) fn get_property_name(val: &str) -> Result<Option<PropertyName>, Parse
 mod tests {
     use crate::parser::{content_line::PropertyName, regex::get_property_name, ParseError};
 
-    use super::{ParsedDateString, ParsedDateStringFlags, ParsedDateStringTime};
+    use super::{ParsedDateString, ParsedDateStringFlags, ParsedDateStringTime, ParsedDuration};
 
     const GARBAGE_INPUTS: [&str; 4] = ["", "  ", "fasfa!2414", "-20101017T120000Z"];
 
@@ -194,6 +245,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_valid_durations_correctly() {
+        let tests = [
+            ("PT2H", false, [0, 0, 2, 0, 0]),
+            ("P15DT5H0M20S", false, [0, 15, 5, 0, 20]),
+            ("-P7W", true, [7, 0, 0, 0, 0]),
+            ("+P1D", false, [0, 1, 0, 0, 0]),
+            ("PT30M10S", false, [0, 0, 0, 30, 10]),
+        ];
+        for (input, negative, [weeks, days, hours, minutes, seconds]) in tests {
+            let output = ParsedDuration::from_ical_duration(input);
+            assert_eq!(
+                output,
+                Ok(ParsedDuration {
+                    negative,
+                    weeks,
+                    days,
+                    hours,
+                    minutes,
+                    seconds
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        let tests = [
+            GARBAGE_INPUTS.to_vec(),
+            ["P", "PT", "P1DT", "P1W2D", "PT1S2M", "2H", "P1.5D"].to_vec(),
+        ]
+        .concat();
+        for input in tests {
+            let res = ParsedDuration::from_ical_duration(input);
+            assert!(res.is_err(), "{input}");
+        }
+    }
+
     #[test]
     fn parses_property_name_from_line() {
         let tests = [
@@ -209,6 +298,12 @@ mod tests {
                 "DTSTART;TZID=America/Everywhere:20120251T023000Z\nFREQ=DAILY;INTERVAL=1;",
                 PropertyName::DtStart,
             ),
+            ("rrule:FREQ=DAILY", PropertyName::RRule),
+            ("ExDate;VALUE=DATE:20120251", PropertyName::ExDate),
+            (
+                "RECURRENCE-ID;TZID=Europe/London:20120251T023000",
+                PropertyName::RecurrenceId,
+            ),
         ];
         for (input, expected_output) in tests {
             let output = get_property_name(input);
@@ -1,4 +1,5 @@
 //! Utility functions for parsing rrule input.
+use super::ParseError;
 use std::str::FromStr;
 
 /// Attempts to convert a comma separated `&str` to a `Vec<T>` of unique and sorted values.
@@ -27,9 +28,60 @@ pub(crate) fn parse_str_to_vec<T: FromStr + Ord + PartialEq + Copy, F: Fn(T) ->
     Ok(parsed_vals)
 }
 
+/// Unfolds the content lines of an iCalendar text as described in
+/// [RFC 5545 section 3.1](https://datatracker.ietf.org/doc/html/rfc5545#section-3.1).
+/// A line break directly followed by a single space or horizontal tab continues the
+/// previous line. Both `CRLF` and `LF` line breaks are accepted, and empty lines are skipped.
+pub(crate) fn unfold_lines(val: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in val.lines() {
+        if let (Some(continuation), Some(previous)) =
+            (line.strip_prefix([' ', '\t']), lines.last_mut())
+        {
+            previous.push_str(continuation);
+        } else if !line.is_empty() {
+            lines.push(line.into());
+        }
+    }
+    lines
+}
+
+/// Returns the byte index of the first `delimiter` in `val` which isn't part of a
+/// quoted string, e.g. the `:` in a parameter value like `TZID="Custom: Zone"` is skipped.
+/// Fails if the delimiter isn't found because a quote is left open.
+pub(crate) fn find_unquoted(val: &str, delimiter: char) -> Result<Option<usize>, ParseError> {
+    let mut quoted = false;
+    let idx = val.char_indices().find_map(|(idx, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == delimiter && !quoted {
+            return Some(idx);
+        }
+        None
+    });
+    if quoted {
+        return Err(ParseError::InvalidParameterFormat(val.into()));
+    }
+    Ok(idx)
+}
+
+/// Splits `val` at every `delimiter` which isn't part of a quoted string. The quotes have to
+/// be balanced, like in the parameters before the value found with [`find_unquoted`].
+pub(crate) fn split_unquoted(val: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut rest = val;
+    while let Ok(Some(idx)) = find_unquoted(rest, delimiter) {
+        parts.push(&rest[..idx]);
+        rest = &rest[idx + delimiter.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_str_to_vec;
+    use super::{find_unquoted, parse_str_to_vec, split_unquoted, unfold_lines};
+    use crate::parser::ParseError;
 
     #[test]
     fn parses_str_to_vec() {
@@ -51,4 +103,42 @@ mod tests {
             assert_eq!(output, expected_output);
         }
     }
+
+    #[test]
+    fn unfolds_lines() {
+        let tests = [
+            ("RRULE:FREQ=DAILY", vec!["RRULE:FREQ=DAILY"]),
+            (
+                "DTSTART:20120201T093000Z\r\nRRULE:FREQ=WEEK\r\n LY;COUNT=3\r\n",
+                vec!["DTSTART:20120201T093000Z", "RRULE:FREQ=WEEKLY;COUNT=3"],
+            ),
+            (
+                "RRULE:FREQ=DAILY;\n\tCOUNT=3\n\nEXDATE:2012\n 0202T093000Z",
+                vec!["RRULE:FREQ=DAILY;COUNT=3", "EXDATE:20120202T093000Z"],
+            ),
+            // Only the first whitespace character is part of the fold
+            (
+                "RRULE:FREQ=DAILY;\n  COUNT=3",
+                vec!["RRULE:FREQ=DAILY; COUNT=3"],
+            ),
+        ];
+        for (input, expected_output) in tests {
+            assert_eq!(unfold_lines(input), expected_output);
+        }
+    }
+
+    #[test]
+    fn splits_outside_of_quoted_strings() {
+        assert_eq!(find_unquoted("TZID=\"A:B\":2012", ':'), Ok(Some(10)));
+        assert_eq!(find_unquoted("TZID=A", ':'), Ok(None));
+        assert_eq!(
+            find_unquoted("TZID=\"A:B", ':'),
+            Err(ParseError::InvalidParameterFormat("TZID=\"A:B".into()))
+        );
+        assert_eq!(
+            split_unquoted("VALUE=DATE;X-NOTE=\"a;b\";TZID=UTC", ';'),
+            vec!["VALUE=DATE", "X-NOTE=\"a;b\"", "TZID=UTC"]
+        );
+        assert_eq!(split_unquoted("", ';'), vec![""]);
+    }
 }
@@ -0,0 +1,287 @@
+//! The properties of an [`RRuleSet`] in a model which is shared by its jCal and xCal
+//! representations. The properties are read from the text printed by `Display for RRuleSet`,
+//! and are printed as text again to be parsed, so all formats stay consistent.
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    parser::{find_unquoted, split_unquoted, ParseError},
+    RRuleError, RRuleSet,
+};
+
+/// The names of the properties which describe an [`RRuleSet`].
+const RECURRENCE_PROPERTIES: [&str; 7] = [
+    "dtstart", "dtend", "duration", "rrule", "exrule", "rdate", "exdate",
+];
+
+/// A property, like `RDATE;VALUE=DATE:20250101,20250102`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Property {
+    /// The lowercase name, like `rdate`.
+    pub name: String,
+    /// The lowercase names and unquoted values of the parameters, except for `VALUE`.
+    pub parameters: Vec<(String, String)>,
+    /// The value type, which is `date`, `date-time`, `duration`, `period` or `recur`.
+    pub value_type: String,
+    pub values: PropertyValues,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PropertyValues {
+    /// Values in the extended format of ISO 8601, like `2025-01-01T09:00:00Z` or
+    /// `2025-01-01T09:00:00Z/PT1H`, as used by jCal and xCal.
+    Text(Vec<String>),
+    /// The parts of a `recur` value, like `byday` with `MO` and `FR`. An `until` date is in the
+    /// extended format.
+    Recur(Vec<(String, Vec<String>)>),
+}
+
+/// Returns the properties of the set, in the order in which they are printed.
+pub(crate) fn rrule_set_to_properties(rrule_set: &RRuleSet) -> Vec<Property> {
+    rrule_set
+        .to_string()
+        .lines()
+        .map(Property::from_line)
+        .collect()
+}
+
+/// Creates a set from its properties. Properties which don't describe recurrences, like the
+/// `summary` of an event, are ignored.
+pub(crate) fn rrule_set_from_properties(properties: &[Property]) -> Result<RRuleSet, RRuleError> {
+    let lines = properties
+        .iter()
+        .filter(|property| is_recurrence_property(&property.name))
+        .map(|property| property.check_content_line().map(|()| property.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    RRuleSet::from_str(&lines.join("\n"))
+}
+
+/// Returns whether a property with the lowercase `name` describes recurrences.
+pub(crate) fn is_recurrence_property(name: &str) -> bool {
+    RECURRENCE_PROPERTIES.contains(&name)
+}
+
+/// Checks that the keys and values of a `RECUR` value are printed as the same parts, so they
+/// can't end the content line, add another part or split into several values.
+pub(crate) fn check_recur_parts(parts: &[(String, Vec<String>)]) -> Result<(), ParseError> {
+    parts
+        .iter()
+        .flat_map(|(key, values)| std::iter::once(key).chain(values))
+        .try_for_each(|value| check_value(value, &[';', '=', ',']))
+}
+
+/// Returns an error if the value contains a line break, or any other control character apart
+/// from a tab, which would end the content line, or one of the `delimiters` of its value, like
+/// the `"` which would end a quoted parameter value.
+fn check_value(value: &str, delimiters: &[char]) -> Result<(), ParseError> {
+    if value
+        .chars()
+        .any(|c| (c.is_control() && c != '\t') || delimiters.contains(&c))
+    {
+        return Err(ParseError::InvalidPropertyValue(
+            value.escape_debug().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl Property {
+    /// Checks that the property is printed as a single content line with the same parameters,
+    /// so a jCal or xCal document can't add properties by the values of another one.
+    fn check_content_line(&self) -> Result<(), ParseError> {
+        for (parameter, value) in &self.parameters {
+            if parameter.is_empty()
+                || !parameter
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                return Err(ParseError::InvalidPropertyValue(
+                    parameter.escape_debug().to_string(),
+                ));
+            }
+            check_value(value, &['"'])?;
+        }
+        match &self.values {
+            // The values are joined with `,`
+            PropertyValues::Text(values) => values
+                .iter()
+                .try_for_each(|value| check_value(value, &[','])),
+            PropertyValues::Recur(parts) => check_recur_parts(parts),
+        }
+    }
+
+    fn from_line(line: &str) -> Self {
+        // The lines are printed by the set, so the quotes are balanced
+        let value_idx = find_unquoted(line, ':')
+            .ok()
+            .flatten()
+            .unwrap_or(line.len());
+        let (name_and_parameters, value) = line.split_at(value_idx);
+        let value = value.strip_prefix(':').unwrap_or(value);
+        let mut parts = split_unquoted(name_and_parameters, ';').into_iter();
+        let name = parts.next().unwrap_or_default().to_lowercase();
+
+        let mut value_type = None;
+        let mut parameters = vec![];
+        for parameter in parts {
+            let (parameter, parameter_value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let parameter_value = parameter_value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(parameter_value);
+            if parameter.eq_ignore_ascii_case("VALUE") {
+                value_type = Some(parameter_value.to_lowercase());
+            } else {
+                parameters.push((parameter.to_lowercase(), parameter_value.into()));
+            }
+        }
+
+        let (value_type, values) = match name.as_str() {
+            "rrule" | "exrule" => ("recur".into(), PropertyValues::Recur(recur_parts(value))),
+            "duration" => ("duration".into(), PropertyValues::Text(vec![value.into()])),
+            _ => {
+                let value_type = value_type.unwrap_or_else(|| "date-time".into());
+                let values = value
+                    .split(',')
+                    .map(|value| match value_type.as_str() {
+                        "period" => period_to_extended(value),
+                        _ => datetime_to_extended(value),
+                    })
+                    .collect();
+                (value_type, PropertyValues::Text(values))
+            }
+        };
+        Self {
+            name,
+            parameters,
+            value_type,
+            values,
+        }
+    }
+}
+
+impl Display for Property {
+    /// Prints the property as a content line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name.to_uppercase())?;
+        for (parameter, value) in &self.parameters {
+            let parameter = parameter.to_uppercase();
+            if value.contains([':', ';', ',']) {
+                write!(f, ";{parameter}=\"{value}\"")?;
+            } else {
+                write!(f, ";{parameter}={value}")?;
+            }
+        }
+        if matches!(self.value_type.as_str(), "date" | "period") {
+            write!(f, ";VALUE={}", self.value_type.to_uppercase())?;
+        }
+
+        let value = match &self.values {
+            PropertyValues::Recur(parts) => recur_from_parts(parts),
+            PropertyValues::Text(values) => values
+                .iter()
+                .map(|value| match self.value_type.as_str() {
+                    "period" => period_from_extended(value),
+                    "date" | "date-time" => datetime_from_extended(value),
+                    _ => value.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        write!(f, ":{value}")
+    }
+}
+
+/// Splits a `RECUR` value, like `FREQ=WEEKLY;BYDAY=MO,FR`, into its lowercase keys and their
+/// values.
+pub(crate) fn recur_parts(recur: &str) -> Vec<(String, Vec<String>)> {
+    recur
+        .split(';')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (key, value) = part.split_once('=').unwrap_or((part, ""));
+            let key = key.to_lowercase();
+            let values = value
+                .split(',')
+                .map(|value| match key.as_str() {
+                    "until" => datetime_to_extended(value),
+                    _ => value.into(),
+                })
+                .collect();
+            (key, values)
+        })
+        .collect()
+}
+
+/// Joins the parts of a `RECUR` value, see [`recur_parts`].
+pub(crate) fn recur_from_parts(parts: &[(String, Vec<String>)]) -> String {
+    let mut parts = parts
+        .iter()
+        .map(|(key, values)| {
+            let values = values
+                .iter()
+                .map(|value| match key.as_str() {
+                    "until" => datetime_from_extended(value),
+                    _ => value.clone(),
+                })
+                .collect::<Vec<_>>();
+            format!("{}={}", key.to_uppercase(), values.join(","))
+        })
+        .collect::<Vec<_>>();
+    // The frequency comes first, as it does in the text form
+    parts.sort_by_key(|part| !part.starts_with("FREQ="));
+    parts.join(";")
+}
+
+/// Converts a `DATE` or `DATE-TIME` value, like `20250101` or `20250101T090000Z`, to the
+/// extended format, like `2025-01-01` or `2025-01-01T09:00:00Z`.
+fn datetime_to_extended(value: &str) -> String {
+    let (date, time) = value.split_once('T').unwrap_or((value, ""));
+    let date = match (date.get(..4), date.get(4..6), date.get(6..)) {
+        (Some(year), Some(month), Some(day)) if date.len() == 8 => {
+            format!("{year}-{month}-{day}")
+        }
+        _ => return value.into(),
+    };
+    if time.is_empty() {
+        return date;
+    }
+    match (time.get(..2), time.get(2..4), time.get(4..)) {
+        (Some(hour), Some(minute), Some(rest)) => format!("{date}T{hour}:{minute}:{rest}"),
+        _ => value.into(),
+    }
+}
+
+/// Converts a `date` or `date-time` value in the extended format to the iCalendar format.
+fn datetime_from_extended(value: &str) -> String {
+    value.replace(['-', ':'], "")
+}
+
+/// Converts a `PERIOD` value, like `20250101T090000Z/PT1H`, to the extended format.
+fn period_to_extended(value: &str) -> String {
+    match value.split_once('/') {
+        Some((start, end)) if end.starts_with(['P', '+', '-']) => {
+            format!("{}/{end}", datetime_to_extended(start))
+        }
+        Some((start, end)) => format!(
+            "{}/{}",
+            datetime_to_extended(start),
+            datetime_to_extended(end)
+        ),
+        None => value.into(),
+    }
+}
+
+/// Converts a `period` value in the extended format to the iCalendar format.
+fn period_from_extended(value: &str) -> String {
+    match value.split_once('/') {
+        Some((start, end)) if end.starts_with(['P', '+', '-']) => {
+            format!("{}/{end}", datetime_from_extended(start))
+        }
+        Some((start, end)) => format!(
+            "{}/{}",
+            datetime_from_extended(start),
+            datetime_from_extended(end)
+        ),
+        None => value.into(),
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::{Duration, TimeZone};
+
+use crate::{
+    Calendar, DstOverlap, ParseError, RRule, RRuleError, RRuleSet, RecurrenceOverride,
+    RecurrenceRange, Tz,
+};
+
+#[test]
+fn rrule_set_to_and_from_binary() {
+    let tests = [
+        "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+         RRULE:FREQ=WEEKLY;UNTIL=20250331T080000Z;BYDAY=MO,-1FR,2TH\n\
+         RDATE;VALUE=PERIOD;TZID=Europe/Berlin:20250301T090000/PT2H\n\
+         EXDATE;TZID=Europe/Berlin:20250110T090000,20250113T090000",
+        "DTSTART:20250101T090000Z\n\
+         DURATION:PT1H30M\n\
+         RRULE:FREQ=MONTHLY;COUNT=12;BYMONTHDAY=1,15,-1;BYSETPOS=1,-1\n\
+         RDATE;VALUE=DATE:20250201\n\
+         RDATE;VALUE=PERIOD:20250301T090000Z/PT2H,20250302T090000Z/20250302T100000Z",
+        "DTSTART:20250101T090000\n\
+         DTEND:20250101T100000\n\
+         RRULE:FREQ=YEARLY;INTERVAL=2;WKST=SU;BYWEEKNO=1,-1;BYDAY=SU;BYHOUR=9,17;BYMINUTE=0,30\n\
+         RRULE:FREQ=YEARLY;BYYEARDAY=-366,100,366;BYSECOND=0,59\n\
+         EXDATE;VALUE=DATE:20270101",
+        "DTSTART;TZID=\"UTC+05:30\":20250101T090000\n\
+         RRULE:FREQ=DAILY;COUNT=2;LOCAL-TZID=Asia/Tokyo",
+        "DTSTART;TZID=America/New_York:20250101T090000\n\
+         RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=8;SKIP=FORWARD",
+    ];
+    for text in tests {
+        let rrule_set: RRuleSet = text.parse().unwrap();
+        let bytes = rrule_set.to_binary();
+        assert!(bytes.len() < text.len(), "{text}");
+
+        let decoded = RRuleSet::from_binary(&bytes).unwrap();
+        assert_eq!(decoded, rrule_set, "{text}");
+        assert_eq!(decoded.to_string(), rrule_set.to_string(), "{text}");
+        assert_eq!(decoded.all(20).dates, rrule_set.all(20).dates, "{text}");
+    }
+}
+
+#[test]
+fn rrule_set_options_to_and_from_binary() {
+    let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+         RRULE:FREQ=DAILY;COUNT=10"
+        .parse()
+        .unwrap();
+    let dt = |day| {
+        Tz::Europe__Berlin
+            .with_ymd_and_hms(2025, 1, day, 9, 0, 0)
+            .unwrap()
+    };
+    let rrule_set = rrule_set
+        .after(dt(7))
+        .before(dt(14))
+        .dst_overlap(DstOverlap::Both)
+        .override_occurrence(dt(8), RecurrenceOverride::cancelled())
+        .override_occurrence(
+            dt(10),
+            RecurrenceOverride::moved(dt(10) + Duration::hours(2))
+                .duration(Duration::minutes(45))
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .limit();
+
+    let decoded = RRuleSet::from_binary(&rrule_set.to_binary()).unwrap();
+    assert_eq!(decoded, rrule_set);
+    assert_eq!(decoded.all(20).dates, rrule_set.all(20).dates);
+}
+
+#[test]
+fn rrule_set_with_vtimezone_to_and_from_binary() {
+    let calendar: Calendar = "BEGIN:VCALENDAR\n\
+        BEGIN:VTIMEZONE\n\
+        TZID:W. Europe Standard Time\n\
+        BEGIN:STANDARD\n\
+        DTSTART:16010101T030000\n\
+        TZOFFSETFROM:+0200\n\
+        TZOFFSETTO:+0100\n\
+        RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\n\
+        END:STANDARD\n\
+        BEGIN:DAYLIGHT\n\
+        DTSTART:16010101T020000\n\
+        TZOFFSETFROM:+0100\n\
+        TZOFFSETTO:+0200\n\
+        RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\n\
+        END:DAYLIGHT\n\
+        END:VTIMEZONE\n\
+        BEGIN:VEVENT\n\
+        UID:weekly\n\
+        DTSTART;TZID=W. Europe Standard Time:20250303T090000\n\
+        RRULE:FREQ=WEEKLY;COUNT=6\n\
+        END:VEVENT\n\
+        END:VCALENDAR"
+        .parse()
+        .unwrap();
+    let rrule_set = calendar.into_components().remove(0).into_rrule_set();
+    assert!(matches!(
+        rrule_set.get_dt_start().timezone(),
+        Tz::VTimezone(_)
+    ));
+
+    let decoded = RRuleSet::from_binary(&rrule_set.to_binary()).unwrap();
+    assert_eq!(decoded, rrule_set);
+    assert_eq!(decoded.all(20).dates, rrule_set.all(20).dates);
+}
+
+#[test]
+fn rrule_to_and_from_binary() {
+    let dt_start = Tz::UTC.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap();
+    let rrule = "FREQ=MONTHLY;COUNT=5;BYDAY=-1SU,2MO;X-INCLUDE-DTSTART=TRUE"
+        .parse::<RRule<_>>()
+        .unwrap()
+        .validate(dt_start)
+        .unwrap();
+    let bytes = rrule.to_binary();
+    assert_eq!(RRule::from_binary(&bytes).unwrap(), rrule);
+    assert_eq!(
+        RRuleSet::from_binary(&bytes),
+        Err(RRuleError::ParserError(ParseError::InvalidBinary(
+            "the bytes don't encode an `RRuleSet`".into()
+        )))
+    );
+}
+
+#[test]
+fn rrule_set_from_invalid_binary() {
+    let rrule_set: RRuleSet = "DTSTART:20250101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+        .parse()
+        .unwrap();
+    let bytes = rrule_set.to_binary();
+
+    let mut corrupted = bytes.clone();
+    corrupted[4] ^= 1;
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    let tests = [
+        (&corrupted[..], "the checksum doesn't match"),
+        (&trailing[..], "the checksum doesn't match"),
+        (&bytes[..bytes.len() - 1], "the checksum doesn't match"),
+        (&bytes[..2], "the bytes are too short"),
+    ];
+    for (bytes, reason) in tests {
+        assert_eq!(
+            RRuleSet::from_binary(bytes),
+            Err(RRuleError::ParserError(ParseError::InvalidBinary(
+                reason.into()
+            )))
+        );
+    }
+}
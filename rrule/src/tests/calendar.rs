@@ -0,0 +1,286 @@
+use crate::tests::common::{check_occurrences, ymd_hms};
+use crate::{Calendar, ComponentKind, ParseError, RRuleError};
+use chrono::Duration;
+
+const OUTLOOK_CALENDAR: &str = "BEGIN:VCALENDAR\r\n\
+    PRODID:-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN\r\n\
+    VERSION:2.0\r\n\
+    METHOD:PUBLISH\r\n\
+    X-WR-CALNAME:Team\r\n\
+    BEGIN:VEVENT\r\n\
+    CLASS:PUBLIC\r\n\
+    DTSTAMP:20201220T120000Z\r\n\
+    UID:040000008200E00074C5B7101A82E00800000000\r\n\
+    SUMMARY;LANGUAGE=en-us:Weekly sync\r\n\
+    DTSTART:20210104T090000Z\r\n\
+    DTEND:20210104T093000Z\r\n\
+    RRULE:FREQ=WEEKLY;COUNT=5;BYDAY=MO\r\n\
+    EXDATE:20210118T090000Z\r\n\
+    ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r\n\
+    BEGIN:VALARM\r\n\
+    ACTION:DISPLAY\r\n\
+    TRIGGER:-PT15M\r\n\
+    END:VALARM\r\n\
+    END:VEVENT\r\n\
+    BEGIN:VEVENT\r\n\
+    UID:040000008200E00074C5B7101A82E00800000000\r\n\
+    RECURRENCE-ID:20210111T090000Z\r\n\
+    SUMMARY:Weekly sync (moved)\r\n\
+    DTSTART:20210112T140000Z\r\n\
+    DTEND:20210112T143000Z\r\n\
+    END:VEVENT\r\n\
+    BEGIN:VEVENT\r\n\
+    UID:040000008200E00074C5B7101A82E00800000000\r\n\
+    RECURRENCE-ID:20210125T090000Z\r\n\
+    STATUS:CANCELLED\r\n\
+    DTSTART:20210125T090000Z\r\n\
+    END:VEVENT\r\n\
+    BEGIN:VEVENT\r\n\
+    UID:all-day\r\n\
+    DTSTART;VALUE=DATE:20210301\r\n\
+    DURATION:P1D\r\n\
+    END:VEVENT\r\n\
+    BEGIN:VTODO\r\n\
+    UID:report\r\n\
+    DTSTART:20210101T080000Z\r\n\
+    DUE:20210101T170000Z\r\n\
+    RRULE:FREQ=MONTHLY;COUNT=2\r\n\
+    END:VTODO\r\n\
+    BEGIN:VTODO\r\n\
+    UID:undated\r\n\
+    SUMMARY:Without a start\r\n\
+    END:VTODO\r\n\
+    END:VCALENDAR\r\n";
+
+#[test]
+fn parses_components_of_calendar() {
+    let calendar: Calendar = OUTLOOK_CALENDAR.parse().unwrap();
+    let components = calendar.get_components();
+
+    let kinds = components
+        .iter()
+        .map(|component| (component.get_kind(), component.get_uid()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                ComponentKind::Event,
+                Some("040000008200E00074C5B7101A82E00800000000")
+            ),
+            (ComponentKind::Event, Some("all-day")),
+            (ComponentKind::Todo, Some("report")),
+        ]
+    );
+}
+
+#[test]
+fn applies_recurrence_id_components_to_their_event() {
+    let calendar: Calendar = OUTLOOK_CALENDAR.parse().unwrap();
+    let rrule_set = calendar.get_components()[0].get_rrule_set().clone();
+
+    assert_eq!(rrule_set.get_dt_end(), Some(&ymd_hms(2021, 1, 4, 9, 30, 0)));
+    assert_eq!(rrule_set.get_overrides().len(), 2);
+    check_occurrences(
+        &rrule_set.all(u16::MAX).dates,
+        &[
+            "2021-01-04T09:00:00+00:00",
+            "2021-01-12T14:00:00+00:00",
+            "2021-02-01T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn event_without_recurrence_occurs_at_its_start() {
+    let calendar: Calendar = OUTLOOK_CALENDAR.parse().unwrap();
+    let rrule_set = calendar.into_components().remove(1).into_rrule_set();
+
+    assert_eq!(rrule_set.get_duration(), Some(&Duration::days(1)));
+    check_occurrences(
+        &rrule_set.all(u16::MAX).dates,
+        &["2021-03-01T00:00:00+00:00"],
+    );
+}
+
+#[test]
+fn uses_due_as_end_of_todo() {
+    let calendar: Calendar = OUTLOOK_CALENDAR.parse().unwrap();
+    let rrule_set = calendar.get_components()[2].get_rrule_set().clone();
+
+    assert_eq!(rrule_set.get_dt_end(), Some(&ymd_hms(2021, 1, 1, 17, 0, 0)));
+    check_occurrences(
+        &rrule_set.all(u16::MAX).dates,
+        &["2021-01-01T08:00:00+00:00", "2021-02-01T08:00:00+00:00"],
+    );
+}
+
+#[test]
+fn applies_recurrence_id_with_range_given_before_its_event() {
+    let calendar: Calendar = "BEGIN:VCALENDAR\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        RECURRENCE-ID;RANGE=THISANDFUTURE:20210103T090000Z\n\
+        DTSTART:20210103T100000Z\n\
+        END:VEVENT\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY;COUNT=4\n\
+        END:VEVENT\n\
+        BEGIN:VEVENT\n\
+        UID:unknown\n\
+        RECURRENCE-ID:20210102T090000Z\n\
+        DTSTART:20210102T100000Z\n\
+        END:VEVENT\n\
+        END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    assert_eq!(calendar.get_components().len(), 1);
+    check_occurrences(
+        &calendar.get_components()[0]
+            .get_rrule_set()
+            .clone()
+            .all(u16::MAX)
+            .dates,
+        &[
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-02T09:00:00+00:00",
+            "2021-01-03T10:00:00+00:00",
+            "2021-01-04T10:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn applies_cancelled_recurrence_id_without_start() {
+    let calendar: Calendar = "BEGIN:VCALENDAR\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY;COUNT=3\n\
+        END:VEVENT\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        RECURRENCE-ID:20210102T090000Z\n\
+        STATUS:CANCELLED\n\
+        END:VEVENT\n\
+        END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    check_occurrences(
+        &calendar.get_components()[0]
+            .get_rrule_set()
+            .clone()
+            .all(u16::MAX)
+            .dates,
+        &["2021-01-01T09:00:00+00:00", "2021-01-03T09:00:00+00:00"],
+    );
+}
+
+#[test]
+fn keeps_end_of_moved_recurrence_id() {
+    let calendar: Calendar = "BEGIN:VCALENDAR\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        DTSTART:20210101T090000Z\n\
+        DTEND:20210101T093000Z\n\
+        RRULE:FREQ=DAILY;COUNT=3\n\
+        END:VEVENT\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        RECURRENCE-ID:20210102T090000Z\n\
+        DTSTART:20210102T140000Z\n\
+        DTEND:20210102T160000Z\n\
+        END:VEVENT\n\
+        BEGIN:VEVENT\n\
+        UID:daily\n\
+        RECURRENCE-ID:20210103T090000Z\n\
+        DURATION:PT45M\n\
+        END:VEVENT\n\
+        END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    let mut iter = calendar.get_components()[0]
+        .get_rrule_set()
+        .clone()
+        .into_iter();
+    let mut occurrences = vec![];
+    while let Some((date, period)) = iter.next_with_period() {
+        occurrences.push((date, period.map(|period| period.get_duration())));
+    }
+    assert_eq!(
+        occurrences,
+        vec![
+            (ymd_hms(2021, 1, 1, 9, 0, 0), None),
+            (ymd_hms(2021, 1, 2, 14, 0, 0), Some(Duration::hours(2))),
+            (ymd_hms(2021, 1, 3, 9, 0, 0), Some(Duration::minutes(45))),
+        ]
+    );
+}
+
+#[test]
+fn rejects_invalid_calendar() {
+    let tests = [
+        (
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20210101T090000Z\nEND:VCALENDAR",
+            ParseError::UnterminatedComponent("VEVENT".into()),
+        ),
+        (
+            "BEGIN:VEVENT\nDTSTART:20210101T090000Z\nRRULE:FREQ=DAYLY\nEND:VEVENT",
+            ParseError::InvalidFrequency("DAYLY".into()),
+        ),
+    ];
+    for (input, expected_error) in tests {
+        assert_eq!(
+            input.parse::<Calendar>(),
+            Err(RRuleError::ParserError(expected_error))
+        );
+    }
+}
+
+#[test]
+fn resolves_tzid_with_vtimezone_of_calendar() {
+    let calendar: Calendar = "BEGIN:VCALENDAR\r\n\
+        BEGIN:VTIMEZONE\r\n\
+        TZID:W. Europe Standard Time\r\n\
+        BEGIN:STANDARD\r\n\
+        DTSTART:16010101T030000\r\n\
+        TZOFFSETFROM:+0200\r\n\
+        TZOFFSETTO:+0100\r\n\
+        RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r\n\
+        END:STANDARD\r\n\
+        BEGIN:DAYLIGHT\r\n\
+        DTSTART:16010101T020000\r\n\
+        TZOFFSETFROM:+0100\r\n\
+        TZOFFSETTO:+0200\r\n\
+        RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\r\n\
+        END:DAYLIGHT\r\n\
+        END:VTIMEZONE\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:monthly\r\n\
+        DTSTART;TZID=W. Europe Standard Time:20210215T090000\r\n\
+        RRULE:FREQ=MONTHLY;COUNT=3\r\n\
+        EXDATE;TZID=W. Europe Standard Time:20210315T090000\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n"
+        .parse()
+        .unwrap();
+
+    assert_eq!(calendar.get_timezones().len(), 1);
+    assert_eq!(
+        calendar.get_timezones()[0].name(),
+        "W. Europe Standard Time"
+    );
+    check_occurrences(
+        &calendar.get_components()[0]
+            .get_rrule_set()
+            .clone()
+            .all(u16::MAX)
+            .dates,
+        &["2021-02-15T09:00:00+01:00", "2021-04-15T09:00:00+02:00"],
+    );
+}
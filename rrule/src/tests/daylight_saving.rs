@@ -1,4 +1,4 @@
-use crate::{tests::common::check_occurrences, RRuleSet};
+use crate::{tests::common::check_occurrences, DstGap, DstOverlap, RRuleSet};
 
 #[test]
 fn daylight_savings_1() {
@@ -65,3 +65,134 @@ fn daylight_savings_2() {
         ],
     );
 }
+
+/// 02:30 doesn't exist on 2021-03-14 in New York, the clocks skip from 02:00 to 03:00.
+const GAP: &str = "DTSTART;TZID=America/New_York:20210313T023000\nRRULE:FREQ=DAILY;COUNT=3";
+
+/// 01:30 exists twice on 2021-11-07 in New York, the clocks go back from 02:00 to 01:00.
+const OVERLAP: &str = "DTSTART;TZID=America/New_York:20211106T013000\nRRULE:FREQ=DAILY;COUNT=4";
+
+#[test]
+fn dst_gap_shifts_forward_by_default() {
+    let rrule_set: RRuleSet = GAP.parse().unwrap();
+    assert_eq!(rrule_set.get_dst_gap(), DstGap::ShiftForward);
+
+    check_occurrences(
+        &rrule_set.all(u16::MAX).dates,
+        &[
+            "2021-03-13T02:30:00-05:00",
+            "2021-03-14T03:30:00-04:00",
+            "2021-03-15T02:30:00-04:00",
+        ],
+    );
+}
+
+#[test]
+fn dst_gap_skip() {
+    let dates = GAP
+        .parse::<RRuleSet>()
+        .unwrap()
+        .dst_gap(DstGap::Skip)
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-03-13T02:30:00-05:00",
+            "2021-03-15T02:30:00-04:00",
+            "2021-03-16T02:30:00-04:00",
+        ],
+    );
+}
+
+#[test]
+fn dst_overlap_earliest_by_default() {
+    let rrule_set: RRuleSet = OVERLAP.parse().unwrap();
+    assert_eq!(rrule_set.get_dst_overlap(), DstOverlap::Earliest);
+
+    check_occurrences(
+        &rrule_set.all(u16::MAX).dates,
+        &[
+            "2021-11-06T01:30:00-04:00",
+            "2021-11-07T01:30:00-04:00",
+            "2021-11-08T01:30:00-05:00",
+            "2021-11-09T01:30:00-05:00",
+        ],
+    );
+}
+
+#[test]
+fn dst_overlap_latest() {
+    let dates = OVERLAP
+        .parse::<RRuleSet>()
+        .unwrap()
+        .dst_overlap(DstOverlap::Latest)
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-11-06T01:30:00-04:00",
+            "2021-11-07T01:30:00-05:00",
+            "2021-11-08T01:30:00-05:00",
+            "2021-11-09T01:30:00-05:00",
+        ],
+    );
+}
+
+#[test]
+fn dst_overlap_both() {
+    let rrule_set = OVERLAP
+        .parse::<RRuleSet>()
+        .unwrap()
+        .dst_overlap(DstOverlap::Both);
+    // Both datetimes count as occurrences for `COUNT`.
+    let expected = [
+        "2021-11-06T01:30:00-04:00",
+        "2021-11-07T01:30:00-04:00",
+        "2021-11-07T01:30:00-05:00",
+        "2021-11-08T01:30:00-05:00",
+    ];
+    check_occurrences(&rrule_set.clone().all(u16::MAX).dates, &expected);
+
+    let mut reversed = rrule_set.iter_rev().unwrap().collect::<Vec<_>>();
+    reversed.reverse();
+    check_occurrences(&reversed, &expected);
+}
+
+#[test]
+fn dst_overlap_skip() {
+    let dates = OVERLAP
+        .parse::<RRuleSet>()
+        .unwrap()
+        .dst_overlap(DstOverlap::Skip)
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-11-06T01:30:00-04:00",
+            "2021-11-08T01:30:00-05:00",
+            "2021-11-09T01:30:00-05:00",
+            "2021-11-10T01:30:00-05:00",
+        ],
+    );
+}
+
+#[test]
+fn dst_policy_applies_to_rdate_dates() {
+    // Midnight doesn't exist on 2021-03-14 in Havana, the clocks skip from 00:00 to 01:00.
+    let rrule_set: RRuleSet = "DTSTART;TZID=America/Havana:20210313T000000\n\
+        RRULE:FREQ=DAILY;COUNT=1\n\
+        RDATE;VALUE=DATE:20210314"
+        .parse()
+        .unwrap();
+    check_occurrences(
+        &rrule_set.clone().all(u16::MAX).dates,
+        &["2021-03-13T00:00:00-05:00", "2021-03-14T01:00:00-04:00"],
+    );
+    check_occurrences(
+        &rrule_set.dst_gap(DstGap::Skip).all(u16::MAX).dates,
+        &["2021-03-13T00:00:00-05:00"],
+    );
+}
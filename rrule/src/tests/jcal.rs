@@ -0,0 +1,235 @@
+use serde_json::json;
+
+use crate::{ParseError, RRule, RRuleError, RRuleSet, Unvalidated};
+
+#[test]
+fn rrule_to_and_from_jcal() {
+    let tests = [
+        (
+            "FREQ=YEARLY;COUNT=5;BYMONTH=10;BYDAY=-1SU,2MO",
+            json!({"freq": "YEARLY", "count": 5, "bymonth": 10, "byday": ["-1SU", "2MO"]}),
+        ),
+        (
+            "FREQ=WEEKLY;UNTIL=20250301T090000Z;INTERVAL=2;WKST=SU;BYSETPOS=-1",
+            json!({
+                "freq": "WEEKLY",
+                "until": "2025-03-01T09:00:00Z",
+                "interval": 2,
+                "wkst": "SU",
+                "bysetpos": -1,
+            }),
+        ),
+        (
+            "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=8;SKIP=FORWARD",
+            json!({
+                "rscale": "HEBREW",
+                "freq": "YEARLY",
+                "skip": "FORWARD",
+                "bymonth": "5L",
+                "bymonthday": 8,
+            }),
+        ),
+    ];
+    for (text, jcal) in tests {
+        let rrule: RRule<Unvalidated> = text.parse().unwrap();
+        assert_eq!(rrule.to_jcal(), jcal, "{text}");
+        assert_eq!(RRule::from_jcal(&jcal).unwrap(), rrule, "{text}");
+    }
+}
+
+#[test]
+fn rrule_set_to_and_from_jcal() {
+    let tests = [
+        (
+            "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,FR\n\
+             EXDATE;TZID=Europe/Berlin:20250110T090000,20250113T090000",
+            json!([
+                ["dtstart", {"tzid": "Europe/Berlin"}, "date-time", "2025-01-06T09:00:00"],
+                ["rrule", {}, "recur", {
+                        "freq": "WEEKLY",
+                        "byday": ["MO", "FR"],
+                        "byhour": 9,
+                        "byminute": 0,
+                        "bysecond": 0,
+                    }],
+                [
+                    "exdate",
+                    {"tzid": "Europe/Berlin"},
+                    "date-time",
+                    "2025-01-10T09:00:00",
+                    "2025-01-13T09:00:00",
+                ],
+            ]),
+        ),
+        (
+            "DTSTART:20250101T090000Z\n\
+             DURATION:PT1H30M\n\
+             RRULE:FREQ=DAILY;COUNT=3\n\
+             RDATE;VALUE=DATE:20250201\n\
+             RDATE;VALUE=PERIOD:20250301T090000Z/PT2H,20250302T090000Z/20250302T100000Z",
+            json!([
+                ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+                ["duration", {}, "duration", "PT1H30M"],
+                [
+                    "rrule",
+                    {},
+                    "recur",
+                    {"freq": "DAILY", "count": 3, "byhour": 9, "byminute": 0, "bysecond": 0},
+                ],
+                ["rdate", {}, "date", "2025-02-01"],
+                [
+                    "rdate",
+                    {},
+                    "period",
+                    "2025-03-01T09:00:00Z/PT2H",
+                    "2025-03-02T09:00:00Z/2025-03-02T10:00:00Z",
+                ],
+            ]),
+        ),
+        (
+            "DTSTART:20250101T090000Z\n\
+             RDATE;VALUE=PERIOD;TZID=Europe/Berlin:20250301T090000/PT2H,20250302T090000/20250302T100000",
+            json!([
+                ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+                [
+                    "rdate",
+                    {"tzid": "Europe/Berlin"},
+                    "period",
+                    "2025-03-01T09:00:00/PT2H",
+                    "2025-03-02T09:00:00/2025-03-02T10:00:00",
+                ],
+            ]),
+        ),
+        (
+            "DTSTART;TZID=\"UTC+05:30\":20250101T090000\nRRULE:FREQ=DAILY;COUNT=2",
+            json!([
+                ["dtstart", {"tzid": "UTC+05:30"}, "date-time", "2025-01-01T09:00:00"],
+                [
+                    "rrule",
+                    {},
+                    "recur",
+                    {"freq": "DAILY", "count": 2, "byhour": 9, "byminute": 0, "bysecond": 0},
+                ],
+            ]),
+        ),
+    ];
+    for (text, jcal) in tests {
+        let rrule_set: RRuleSet = text.parse().unwrap();
+        assert_eq!(rrule_set.to_jcal(), jcal, "{text}");
+        assert_eq!(RRuleSet::from_jcal(&jcal).unwrap(), rrule_set, "{text}");
+    }
+}
+
+#[test]
+fn rrule_set_from_jcal_string() {
+    let jcal = r#"[
+        ["dtstart", {"tzid": "America/New_York"}, "date-time", "1997-09-02T09:00:00"],
+        ["rrule", {}, "recur", {"freq": "DAILY", "count": 3}]
+    ]"#;
+    let rrule_set = RRuleSet::from_jcal(&serde_json::from_str(jcal).unwrap()).unwrap();
+    assert_eq!(
+        rrule_set.to_string(),
+        "DTSTART;TZID=America/New_York:19970902T090000\n\
+         RRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    );
+}
+
+#[test]
+fn rejects_jcal_values_which_end_the_content_line() {
+    let tests = [
+        json!([
+            ["dtstart", {"tzid": "UTC\nRDATE:20250102T090000Z"}, "date-time", "2025-01-01T09:00:00"],
+            ["rrule", {}, "recur", {"freq": "DAILY", "count": 3}]
+        ]),
+        json!([
+            ["dtstart", {"tzid": "UTC\":RDATE:20250102T090000Z"}, "date-time", "2025-01-01T09:00:00"],
+            ["rrule", {}, "recur", {"freq": "DAILY", "count": 3}]
+        ]),
+        json!([
+            ["dtstart", {"x-name:x": "value"}, "date-time", "2025-01-01T09:00:00Z"],
+            ["rrule", {}, "recur", {"freq": "DAILY", "count": 3}]
+        ]),
+        json!([
+            ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z\r\nRDATE:20250102T090000Z"],
+            ["rrule", {}, "recur", {"freq": "DAILY", "count": 3}]
+        ]),
+        json!([
+            ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+            ["rrule", {}, "recur", {"freq": "DAILY\nRDATE:20250102T090000Z"}]
+        ]),
+    ];
+    for jcal in tests {
+        assert!(
+            matches!(
+                RRuleSet::from_jcal(&jcal),
+                Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+            ),
+            "{jcal}"
+        );
+    }
+    assert!(matches!(
+        RRule::from_jcal(&json!({"freq": "DAILY\nRDATE:20250102T090000Z"})),
+        Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+    ));
+}
+
+#[test]
+fn rejects_jcal_values_which_add_rule_parts_or_values() {
+    let recurs = [
+        json!({"freq": "DAILY;COUNT=2"}),
+        json!({"freq": "DAILY", "count=2;byhour": 9}),
+        json!({"freq": "DAILY", "byhour": "9,10"}),
+        json!({"freq": "DAILY", "byday": ["MO", "TU,WE"]}),
+    ];
+    for recur in recurs {
+        assert!(
+            matches!(
+                RRule::from_jcal(&recur),
+                Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+            ),
+            "{recur}"
+        );
+        let jcal = json!([
+            ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+            ["rrule", {}, "recur", recur]
+        ]);
+        assert!(
+            matches!(
+                RRuleSet::from_jcal(&jcal),
+                Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+            ),
+            "{jcal}"
+        );
+    }
+
+    let jcal = json!([
+        ["dtstart", {}, "date-time", "2025-01-01T09:00:00Z"],
+        [
+            "rdate",
+            {},
+            "date-time",
+            "2025-01-02T09:00:00Z,2025-01-03T09:00:00Z"
+        ]
+    ]);
+    assert!(matches!(
+        RRuleSet::from_jcal(&jcal),
+        Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+    ));
+}
+
+#[test]
+fn rejects_invalid_jcal() {
+    let tests = [
+        json!({"freq": "DAILY"}),
+        json!([["dtstart", {}, "date-time"]]),
+        json!([["dtstart", "2025-01-01T09:00:00Z"]]),
+        json!([["rrule", {}, "recur", ["FREQ=DAILY"]]]),
+    ];
+    for jcal in tests {
+        assert!(matches!(
+            RRuleSet::from_jcal(&jcal),
+            Err(RRuleError::ParserError(ParseError::InvalidJCal(_)))
+        ));
+    }
+}
@@ -1,12 +1,25 @@
 #![cfg(test)]
 
+#[cfg(feature = "binary")]
+mod binary;
+mod calendar;
 mod common;
 mod datetime;
 mod daylight_saving;
+#[cfg(feature = "jcal")]
+mod jcal;
 mod local_tzid;
+mod recurrence_override;
 mod regression;
+mod reverse;
 mod rfc5545_dtstart;
+mod rfc7529;
 mod rfc_tests;
 mod rrule;
 mod rruleset;
+mod seek;
 mod serde;
+mod single_occurrence;
+mod text;
+#[cfg(feature = "xcal")]
+mod xcal;
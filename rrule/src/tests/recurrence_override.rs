@@ -0,0 +1,195 @@
+use crate::tests::common::{check_occurrences, ymd_hms};
+use crate::{RRuleSet, RecurrenceOverride, RecurrenceRange};
+
+fn daily_rrule_set() -> RRuleSet {
+    "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY;COUNT=5"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn moves_single_occurrence() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 2, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 2, 15, 30, 0)),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-02T15:30:00+00:00",
+            "2021-01-03T09:00:00+00:00",
+            "2021-01-04T09:00:00+00:00",
+            "2021-01-05T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn moved_occurrences_stay_sorted() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 2, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 4, 12, 0, 0)),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 5, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2020, 12, 31, 8, 0, 0)),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2020-12-31T08:00:00+00:00",
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-03T09:00:00+00:00",
+            "2021-01-04T09:00:00+00:00",
+            "2021-01-04T12:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn cancels_single_occurrence() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 3, 9, 0, 0),
+            RecurrenceOverride::cancelled(),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-02T09:00:00+00:00",
+            "2021-01-04T09:00:00+00:00",
+            "2021-01-05T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn ignores_override_which_is_not_an_occurrence() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 3, 10, 0, 0),
+            RecurrenceOverride::cancelled(),
+        )
+        .all(u16::MAX)
+        .dates;
+    assert_eq!(dates.len(), 5);
+}
+
+#[test]
+fn ignores_this_and_future_override_which_is_not_an_occurrence() {
+    let rrule_set = daily_rrule_set().override_occurrence(
+        ymd_hms(2021, 1, 3, 12, 0, 0),
+        RecurrenceOverride::moved(ymd_hms(2021, 1, 3, 15, 0, 0))
+            .range(RecurrenceRange::ThisAndFuture),
+    );
+    let expected = [
+        "2021-01-01T09:00:00+00:00",
+        "2021-01-02T09:00:00+00:00",
+        "2021-01-03T09:00:00+00:00",
+        "2021-01-04T09:00:00+00:00",
+        "2021-01-05T09:00:00+00:00",
+    ];
+    check_occurrences(&rrule_set.clone().all(u16::MAX).dates, &expected);
+
+    let mut dates = rrule_set.iter_rev().unwrap().collect::<Vec<_>>();
+    dates.reverse();
+    check_occurrences(&dates, &expected);
+}
+
+#[test]
+fn moves_this_and_future_occurrences() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 3, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 3, 11, 0, 0))
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-02T09:00:00+00:00",
+            "2021-01-03T11:00:00+00:00",
+            "2021-01-04T11:00:00+00:00",
+            "2021-01-05T11:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn single_override_wins_over_this_and_future() {
+    let dates = daily_rrule_set()
+        .override_occurrence(
+            ymd_hms(2021, 1, 2, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 2, 8, 0, 0))
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 4, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 4, 20, 0, 0)),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-01-01T09:00:00+00:00",
+            "2021-01-02T08:00:00+00:00",
+            "2021-01-03T08:00:00+00:00",
+            "2021-01-04T20:00:00+00:00",
+            "2021-01-05T08:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn cancels_this_and_future_occurrences_of_infinite_rule() {
+    let dates = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .override_occurrence(
+            ymd_hms(2021, 1, 3, 9, 0, 0),
+            RecurrenceOverride::cancelled().range(RecurrenceRange::ThisAndFuture),
+        )
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &["2021-01-01T09:00:00+00:00", "2021-01-02T09:00:00+00:00"],
+    );
+}
+
+#[test]
+fn occurrences_keep_recurrence_id() {
+    let rrule_set = "DTSTART:20210101T090000Z\n\
+        DURATION:PT1H\n\
+        RRULE:FREQ=DAILY;COUNT=2"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .override_occurrence(
+            ymd_hms(2021, 1, 1, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 3, 9, 0, 0)),
+        );
+
+    let occurrences = rrule_set.occurrences().collect::<Vec<_>>();
+    assert_eq!(occurrences.len(), 2);
+    assert_eq!(occurrences[0].start, ymd_hms(2021, 1, 2, 9, 0, 0));
+    assert_eq!(occurrences[0].recurrence_id, ymd_hms(2021, 1, 2, 9, 0, 0));
+    assert_eq!(occurrences[1].start, ymd_hms(2021, 1, 3, 9, 0, 0));
+    assert_eq!(occurrences[1].end, ymd_hms(2021, 1, 3, 10, 0, 0));
+    assert_eq!(occurrences[1].recurrence_id, ymd_hms(2021, 1, 1, 9, 0, 0));
+}
@@ -0,0 +1,145 @@
+use crate::tests::common::{check_occurrences, ymd_hms};
+use crate::{RRuleSet, RecurrenceOverride, RecurrenceRange};
+
+/// Checks that the reverse iterator returns the same dates as the forward iterator, reversed.
+fn check_reversed(rrule_set: &RRuleSet) {
+    let mut expected = rrule_set.clone().all(u16::MAX).dates;
+    expected.reverse();
+    let dates = rrule_set.iter_rev().unwrap().collect::<Vec<_>>();
+    assert_eq!(dates, expected);
+}
+
+#[test]
+fn reverses_bounded_rules() {
+    let rules = [
+        "FREQ=YEARLY;UNTIL=20300101T000000Z",
+        "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;UNTIL=20400101T000000Z",
+        "FREQ=YEARLY;INTERVAL=3;BYDAY=20MO;UNTIL=20400101T000000Z",
+        "FREQ=YEARLY;BYWEEKNO=1;BYDAY=MO;UNTIL=20300101T000000Z",
+        "FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20231231T000000Z",
+        "FREQ=MONTHLY;INTERVAL=5;BYMONTHDAY=31;UNTIL=20251231T000000Z",
+        "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;UNTIL=20221231T000000Z",
+        "FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20210601T000000Z",
+        "FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=SU,TU;UNTIL=20210601T000000Z",
+        "FREQ=DAILY;INTERVAL=3;UNTIL=20210401T000000Z",
+        "FREQ=DAILY;BYHOUR=9,18;BYMINUTE=0,30;UNTIL=20210201T000000Z",
+        "FREQ=HOURLY;INTERVAL=5;UNTIL=20210110T000000Z",
+        "FREQ=HOURLY;BYDAY=SA;BYHOUR=7,19;UNTIL=20210301T000000Z",
+        "FREQ=MINUTELY;INTERVAL=17;BYHOUR=10;UNTIL=20210105T000000Z",
+        "FREQ=SECONDLY;INTERVAL=7;BYMINUTE=5;BYHOUR=13;UNTIL=20210103T000000Z",
+        "FREQ=DAILY;COUNT=20",
+        "FREQ=MONTHLY;BYMONTHDAY=-1;COUNT=30",
+    ];
+    for rule in rules {
+        let rrule_set: RRuleSet = format!("DTSTART:20210101T090000Z\nRRULE:{rule}")
+            .parse()
+            .unwrap();
+        check_reversed(&rrule_set);
+    }
+}
+
+#[test]
+fn reverses_rules_in_timezone() {
+    let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20210301T023000\n\
+        RRULE:FREQ=DAILY;UNTIL=20210401T000000Z"
+        .parse()
+        .unwrap();
+    check_reversed(&rrule_set);
+
+    let rrule_set: RRuleSet = "DTSTART;TZID=America/New_York:20210101T120000\n\
+        RRULE:FREQ=HOURLY;INTERVAL=7;UNTIL=20210320T000000Z"
+        .parse()
+        .unwrap();
+    check_reversed(&rrule_set);
+}
+
+#[test]
+fn reverses_rule_set() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO;UNTIL=20210401T000000Z\n\
+        RRULE:FREQ=MONTHLY;BYMONTHDAY=1,15;COUNT=5\n\
+        EXRULE:FREQ=MONTHLY;BYDAY=1MO;UNTIL=20210401T000000Z\n\
+        RDATE:20210102T100000Z,20210601T100000Z\n\
+        EXDATE:20210315T090000Z"
+        .parse()
+        .unwrap();
+    check_reversed(&rrule_set);
+}
+
+#[test]
+fn starts_at_before() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY"
+        .parse()
+        .unwrap();
+    let dates = rrule_set
+        .before(ymd_hms(2021, 3, 1, 9, 0, 0))
+        .after(ymd_hms(2021, 2, 26, 0, 0, 0))
+        .iter_rev()
+        .unwrap()
+        .collect::<Vec<_>>();
+    check_occurrences(
+        &dates,
+        &[
+            "2021-03-01T09:00:00+00:00",
+            "2021-02-28T09:00:00+00:00",
+            "2021-02-27T09:00:00+00:00",
+            "2021-02-26T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn reverses_count_rule_with_before() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY;COUNT=10"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .before(ymd_hms(2021, 1, 4, 9, 0, 0));
+    check_reversed(&rrule_set);
+}
+
+#[test]
+fn reverses_include_dtstart() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=WEEKLY;BYDAY=TU;UNTIL=20210301T000000Z;X-INCLUDE-DTSTART=TRUE"
+        .parse()
+        .unwrap();
+    check_reversed(&rrule_set);
+}
+
+#[test]
+fn reverses_overrides() {
+    let rrule_set = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY;UNTIL=20210110T090000Z"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .override_occurrence(
+            ymd_hms(2021, 1, 2, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 5, 12, 0, 0)),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 9, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2020, 12, 31, 9, 0, 0)),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 4, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 4, 7, 0, 0))
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 7, 9, 0, 0),
+            RecurrenceOverride::cancelled().range(RecurrenceRange::ThisAndFuture),
+        );
+    check_reversed(&rrule_set);
+    check_reversed(&rrule_set.before(ymd_hms(2021, 1, 3, 0, 0, 0)));
+}
+
+#[test]
+fn requires_bounded_rules() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY"
+        .parse()
+        .unwrap();
+    assert!(rrule_set.iter_rev().is_err());
+}
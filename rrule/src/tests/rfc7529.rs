@@ -0,0 +1,298 @@
+//! This file contains the `SKIP` examples from:
+//! <https://datatracker.ietf.org/doc/html/rfc7529#section-4.3>
+
+use crate::tests::common;
+use crate::{RRule, RRuleError, RRuleSet, Unvalidated, ValidationError};
+
+fn dates(rrule_set: &str) -> Vec<String> {
+    rrule_set
+        .parse::<RRuleSet>()
+        .unwrap()
+        .all(u16::MAX)
+        .dates
+        .iter()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .collect()
+}
+
+/// Yearly on the leap day, moved to the 1st of March in other years
+#[test]
+fn yearly_leap_day_forward() {
+    let dates = "DTSTART:20120229T120000Z\n\
+        RRULE:RSCALE=GREGORIAN;FREQ=YEARLY;SKIP=FORWARD;COUNT=5"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .all(u16::MAX)
+        .dates;
+    common::check_occurrences(
+        &dates,
+        &[
+            "2012-02-29T12:00:00+00:00",
+            "2013-03-01T12:00:00+00:00",
+            "2014-03-01T12:00:00+00:00",
+            "2015-03-01T12:00:00+00:00",
+            "2016-02-29T12:00:00+00:00",
+        ],
+    );
+}
+
+/// Yearly on the leap day, moved to the 28th of February in other years
+#[test]
+fn yearly_leap_day_backward() {
+    assert_eq!(
+        dates(
+            "DTSTART:20120229T120000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=YEARLY;SKIP=BACKWARD;COUNT=5"
+        ),
+        [
+            "2012-02-29",
+            "2013-02-28",
+            "2014-02-28",
+            "2015-02-28",
+            "2016-02-29"
+        ]
+    );
+}
+
+/// Yearly on the leap day, without `SKIP` it only occurs in leap years
+#[test]
+fn yearly_leap_day_omit() {
+    assert_eq!(
+        dates(
+            "DTSTART:20120229T120000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=YEARLY;COUNT=3"
+        ),
+        ["2012-02-29", "2016-02-29", "2020-02-29"]
+    );
+}
+
+/// Monthly on the 31st, or the last day of the month if it is shorter
+#[test]
+fn monthly_last_day_backward() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240131T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=31;SKIP=BACKWARD;COUNT=6"
+        ),
+        [
+            "2024-01-31",
+            "2024-02-29",
+            "2024-03-31",
+            "2024-04-30",
+            "2024-05-31",
+            "2024-06-30"
+        ]
+    );
+}
+
+/// Monthly on the 31st, or the first day of the next month if it is shorter
+#[test]
+fn monthly_last_day_forward() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240131T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;SKIP=FORWARD;COUNT=6"
+        ),
+        [
+            "2024-01-31",
+            "2024-03-01",
+            "2024-03-31",
+            "2024-05-01",
+            "2024-05-31",
+            "2024-07-01"
+        ]
+    );
+}
+
+/// Monthly on the 31st to last day, which is rolled into the previous month or
+/// to the first day of the month
+#[test]
+fn monthly_negative_month_day() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240101T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=-31;SKIP=BACKWARD;COUNT=4"
+        ),
+        ["2024-01-01", "2024-01-31", "2024-03-01", "2024-03-31"]
+    );
+    assert_eq!(
+        dates(
+            "DTSTART:20240101T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=-31;SKIP=FORWARD;COUNT=4"
+        ),
+        ["2024-01-01", "2024-02-01", "2024-03-01", "2024-04-01"]
+    );
+}
+
+/// A rolled day which is also generated by its own month only occurs once
+#[test]
+fn monthly_forward_without_duplicates() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240101T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=1,31;SKIP=FORWARD;COUNT=7"
+        ),
+        [
+            "2024-01-01",
+            "2024-01-31",
+            "2024-02-01",
+            "2024-03-01",
+            "2024-03-31",
+            "2024-04-01",
+            "2024-05-01"
+        ]
+    );
+}
+
+/// Every other month on the 31st
+#[test]
+fn monthly_interval_forward() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240131T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;INTERVAL=2;SKIP=FORWARD;COUNT=6"
+        ),
+        [
+            "2024-01-31",
+            "2024-03-31",
+            "2024-05-31",
+            "2024-07-31",
+            "2024-10-01",
+            "2024-12-01"
+        ]
+    );
+}
+
+/// Monthly on the 31st, or the last day of the month if it is shorter, when it is a Friday
+#[test]
+fn monthly_rolled_day_by_weekday() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240101T090000Z\n\
+            RRULE:RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=31;BYDAY=FR;SKIP=BACKWARD;COUNT=3"
+        ),
+        ["2024-05-31", "2025-01-31", "2025-02-28"]
+    );
+}
+
+#[test]
+fn rscale_and_skip_round_trip() {
+    let rrule: RRule<Unvalidated> = "FREQ=MONTHLY;BYMONTHDAY=31;SKIP=BACKWARD;RSCALE=GREGORIAN"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule.to_string(),
+        "RSCALE=GREGORIAN;FREQ=MONTHLY;SKIP=BACKWARD;BYMONTHDAY=31"
+    );
+}
+
+#[test]
+fn skip_requires_rscale() {
+    let res = "DTSTART:20240131T090000Z\n\
+        RRULE:FREQ=MONTHLY;SKIP=BACKWARD"
+        .parse::<RRuleSet>();
+    assert_eq!(
+        res.unwrap_err(),
+        RRuleError::ValidationError(ValidationError::SkipWithoutRScale)
+    );
+}
+
+/// Chinese New Year
+#[test]
+fn chinese_new_year() {
+    assert_eq!(
+        dates(
+            "DTSTART;VALUE=DATE:20130210\n\
+            RRULE:RSCALE=CHINESE;FREQ=YEARLY;COUNT=6"
+        ),
+        [
+            "2013-02-10",
+            "2014-01-31",
+            "2015-02-19",
+            "2016-02-08",
+            "2017-01-28",
+            "2018-02-16"
+        ]
+    );
+}
+
+/// Monthly on the 15th day of the Chinese month, the full moon
+#[test]
+fn chinese_monthly_full_moon() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240901T200000Z\n\
+            RRULE:RSCALE=CHINESE;FREQ=MONTHLY;BYMONTHDAY=15;COUNT=3"
+        ),
+        ["2024-09-17", "2024-10-17", "2024-11-15"]
+    );
+}
+
+/// The first day of the leap month 6L, or of month 7 in years without it
+#[test]
+fn chinese_leap_month_forward() {
+    assert_eq!(
+        dates(
+            "DTSTART:20250101T090000Z\n\
+            RRULE:RSCALE=CHINESE;FREQ=YEARLY;BYMONTH=6L;BYMONTHDAY=1;SKIP=FORWARD;COUNT=2"
+        ),
+        ["2025-07-25", "2026-08-13"]
+    );
+}
+
+/// Rosh Hashanah, following DTSTART
+#[test]
+fn hebrew_new_year() {
+    assert_eq!(
+        dates(
+            "DTSTART;VALUE=DATE:20241003\n\
+            RRULE:RSCALE=HEBREW;FREQ=YEARLY;COUNT=3"
+        ),
+        ["2024-10-03", "2025-09-23", "2026-09-12"]
+    );
+}
+
+/// Passover, the 15th of Nisan
+#[test]
+fn hebrew_passover() {
+    assert_eq!(
+        dates(
+            "DTSTART:20240101T090000Z\n\
+            RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=7;BYMONTHDAY=15;COUNT=3"
+        ),
+        ["2024-04-23", "2025-04-13", "2026-04-02"]
+    );
+}
+
+/// The 8th of Adar I, or of Adar in years without a leap month
+#[test]
+fn hebrew_leap_month_forward() {
+    assert_eq!(
+        dates(
+            "DTSTART;VALUE=DATE:20140208\n\
+            RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=8;SKIP=FORWARD;COUNT=4"
+        ),
+        ["2014-02-08", "2015-02-27", "2016-02-17", "2017-03-06"]
+    );
+    // Without `SKIP` it only occurs in leap years.
+    assert_eq!(
+        dates(
+            "DTSTART;VALUE=DATE:20140208\n\
+            RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=8;COUNT=2"
+        ),
+        ["2014-02-08", "2016-02-17"]
+    );
+}
+
+#[test]
+fn leap_month_round_trip() {
+    let rrule: RRule<Unvalidated> = "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=6,5L;BYMONTHDAY=8"
+        .parse()
+        .unwrap();
+    assert_eq!(rrule.get_by_month(), [6]);
+    assert_eq!(rrule.get_by_leap_month(), [5]);
+    assert_eq!(
+        rrule.to_string(),
+        "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L,6;BYMONTHDAY=8"
+    );
+}
@@ -1,5 +1,8 @@
 use crate::tests::common::{check_occurrences, test_recurring_rrule_set, ymd_hms};
-use crate::{Frequency, NWeekday, RRule, RRuleSet, Weekday};
+use crate::{
+    Frequency, NWeekday, Occurrence, ParseError, Period, RRule, RRuleError, RRuleSet, Tz, Weekday,
+};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 
 #[test]
 fn rrule_and_utc_time_with_local_tzid() {
@@ -180,6 +183,357 @@ fn rrule_and_exdate_2() {
     );
 }
 
+#[test]
+fn rrule_and_exdate_with_date_value() {
+    let dt_start = ymd_hms(2021, 3, 1, 9, 0, 0);
+    let rrule = RRule::new(Frequency::Hourly)
+        .count(6)
+        .by_hour(vec![9, 17])
+        .validate(dt_start)
+        .unwrap();
+
+    let set = RRuleSet::new(dt_start)
+        .rrule(rrule)
+        .exdate_date(NaiveDate::from_ymd_opt(2021, 3, 2).unwrap());
+
+    test_recurring_rrule_set(
+        set,
+        &[
+            ymd_hms(2021, 3, 1, 9, 0, 0),
+            ymd_hms(2021, 3, 1, 17, 0, 0),
+            ymd_hms(2021, 3, 3, 9, 0, 0),
+            ymd_hms(2021, 3, 3, 17, 0, 0),
+        ],
+    );
+}
+
+#[test]
+fn exdate_with_date_value_uses_local_calendar_date() {
+    // The second occurrence is at 2020-12-15T23:30:00Z, which is already
+    // the 16th in Berlin, so it has to be excluded.
+    let dates = "DTSTART;TZID=Europe/Berlin:20201215T003000\n\
+        RRULE:FREQ=DAILY;COUNT=4\n\
+        EXDATE;VALUE=DATE:20201216,20201218"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &["2020-12-15T00:30:00+01:00", "2020-12-17T00:30:00+01:00"],
+    );
+}
+
+#[test]
+fn rdate_with_date_value() {
+    let dates = "DTSTART;TZID=Europe/Berlin:20201214T093000\n\
+        RRULE:FREQ=WEEKLY;COUNT=2\n\
+        RDATE;VALUE=DATE:20201216,20201225"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2020-12-14T09:30:00+01:00",
+            "2020-12-16T00:00:00+01:00",
+            "2020-12-21T09:30:00+01:00",
+            "2020-12-25T00:00:00+01:00",
+        ],
+    );
+}
+
+#[test]
+fn rdate_and_exdate_with_date_value_roundtrip() {
+    let rrule_set_str = "DTSTART:20201214T093000Z\n\
+        RRULE:FREQ=DAILY;COUNT=5;BYHOUR=9;BYMINUTE=30;BYSECOND=0\n\
+        RDATE;VALUE=DATE:20201224,20201225\n\
+        EXDATE;VALUE=DATE:20201215";
+    let rrule_set = rrule_set_str.parse::<RRuleSet>().unwrap();
+
+    assert_eq!(
+        rrule_set.get_rdate_date(),
+        &vec![
+            NaiveDate::from_ymd_opt(2020, 12, 24).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 12, 25).unwrap(),
+        ]
+    );
+    assert_eq!(
+        rrule_set.get_exdate_date(),
+        &vec![NaiveDate::from_ymd_opt(2020, 12, 15).unwrap()]
+    );
+    assert!(rrule_set.get_rdate().is_empty());
+    assert!(rrule_set.get_exdate().is_empty());
+    assert_eq!(rrule_set.to_string(), rrule_set_str);
+}
+
+#[test]
+fn rdate_with_period_value() {
+    let rrule_set = "DTSTART:19960401T020000Z\n\
+        RDATE;VALUE=PERIOD:19960403T020000Z/19960403T040000Z,19960404T010000Z/PT3H"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let mut iter = rrule_set.into_iter();
+    let (date, period) = iter.next_with_period().unwrap();
+    assert_eq!(date, Tz::UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap());
+    assert_eq!(
+        period.unwrap().get_end(),
+        Tz::UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap()
+    );
+    let (date, period) = iter.next_with_period().unwrap();
+    assert_eq!(date, Tz::UTC.with_ymd_and_hms(1996, 4, 4, 1, 0, 0).unwrap());
+    assert_eq!(period.unwrap().get_duration(), Duration::hours(3));
+    assert_eq!(iter.next_with_period(), None);
+}
+
+#[test]
+fn rdate_with_period_value_mixed_with_rrule() {
+    let rrule_set = "DTSTART:19960401T020000Z\n\
+        RRULE:FREQ=DAILY;COUNT=3\n\
+        RDATE;VALUE=PERIOD:19960402T120000Z/PT2H"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let mut iter = rrule_set.into_iter();
+    let mut occurrences = vec![];
+    while let Some((date, period)) = iter.next_with_period() {
+        occurrences.push((date.to_rfc3339(), period.is_some()));
+    }
+    assert_eq!(
+        occurrences,
+        vec![
+            ("1996-04-01T02:00:00+00:00".to_string(), false),
+            ("1996-04-02T02:00:00+00:00".to_string(), false),
+            ("1996-04-02T12:00:00+00:00".to_string(), true),
+            ("1996-04-03T02:00:00+00:00".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn rdate_with_period_value_roundtrip() {
+    let rrule_set_str = "DTSTART:19960401T020000Z\n\
+        RDATE;VALUE=PERIOD:19960403T020000Z/19960403T040000Z,19960404T010000Z/PT3H";
+    let rrule_set = rrule_set_str.parse::<RRuleSet>().unwrap();
+
+    assert_eq!(
+        rrule_set.get_rdate_period(),
+        &vec![
+            Period::new(
+                Tz::UTC.with_ymd_and_hms(1996, 4, 3, 2, 0, 0).unwrap(),
+                Tz::UTC.with_ymd_and_hms(1996, 4, 3, 4, 0, 0).unwrap()
+            ),
+            Period::with_duration(
+                Tz::UTC.with_ymd_and_hms(1996, 4, 4, 1, 0, 0).unwrap(),
+                Duration::hours(3)
+            ),
+        ]
+    );
+    assert_eq!(rrule_set.to_string(), rrule_set_str);
+}
+
+#[test]
+fn rdate_with_period_in_timezone_roundtrip() {
+    let rrule_set_str = "DTSTART:19960401T020000Z\n\
+        RDATE;VALUE=PERIOD:19960403T020000Z/PT2H\n\
+        RDATE;VALUE=PERIOD;TZID=Europe/Berlin:19960404T090000/19960404T110000,19960405T090000/PT1H";
+    let rrule_set = rrule_set_str.parse::<RRuleSet>().unwrap();
+
+    assert_eq!(
+        rrule_set.get_rdate_period()[1],
+        Period::new(
+            Tz::Europe__Berlin
+                .with_ymd_and_hms(1996, 4, 4, 9, 0, 0)
+                .unwrap(),
+            Tz::Europe__Berlin
+                .with_ymd_and_hms(1996, 4, 4, 11, 0, 0)
+                .unwrap()
+        )
+    );
+    assert_eq!(rrule_set.to_string(), rrule_set_str);
+}
+
+#[test]
+fn exdate_with_period_value_is_rejected() {
+    let res = "DTSTART:19960401T020000Z\n\
+        RDATE:19960403T020000Z\n\
+        EXDATE;VALUE=PERIOD:19960403T020000Z/PT2H"
+        .parse::<RRuleSet>();
+    assert!(res.is_err());
+}
+
+#[test]
+fn rdate_with_period_ending_after_latest_datetime_is_rejected() {
+    for period in [
+        "20250107T090000Z/P4000000000W",
+        "20250107T090000Z/P20000000W",
+    ] {
+        let res =
+            format!("DTSTART:20250106T090000Z\nRDATE;VALUE=PERIOD:{period}").parse::<RRuleSet>();
+        assert!(res.is_err(), "{period}");
+    }
+}
+
+#[test]
+fn occurrences_with_dtend() {
+    let rrule_set = "DTSTART:20201214T093000Z\n\
+        DTEND:20201214T103000Z\n\
+        RRULE:FREQ=DAILY;COUNT=2"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let occurrences = rrule_set.occurrences().collect::<Vec<_>>();
+    assert_eq!(
+        occurrences,
+        vec![
+            Occurrence {
+                start: Tz::UTC.with_ymd_and_hms(2020, 12, 14, 9, 30, 0).unwrap(),
+                end: Tz::UTC.with_ymd_and_hms(2020, 12, 14, 10, 30, 0).unwrap(),
+                recurrence_id: Tz::UTC.with_ymd_and_hms(2020, 12, 14, 9, 30, 0).unwrap(),
+            },
+            Occurrence {
+                start: Tz::UTC.with_ymd_and_hms(2020, 12, 15, 9, 30, 0).unwrap(),
+                end: Tz::UTC.with_ymd_and_hms(2020, 12, 15, 10, 30, 0).unwrap(),
+                recurrence_id: Tz::UTC.with_ymd_and_hms(2020, 12, 15, 9, 30, 0).unwrap(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn occurrences_with_duration_and_period() {
+    let rrule_set = "DTSTART:20201214T093000Z\n\
+        DURATION:PT15M\n\
+        RRULE:FREQ=DAILY;COUNT=1\n\
+        RDATE;VALUE=PERIOD:20201216T120000Z/PT3H\n\
+        RDATE:20201217T120000Z"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let occurrences = rrule_set
+        .occurrences()
+        .map(|occurrence| (occurrence.end - occurrence.start).num_minutes())
+        .collect::<Vec<_>>();
+    assert_eq!(occurrences, vec![15, 180, 15]);
+}
+
+#[test]
+fn occurrences_ending_after_latest_datetime_end_at_it() {
+    let rrule_set = "DTSTART:20250106T090000Z\n\
+        DURATION:P20000000W\n\
+        RRULE:FREQ=DAILY;COUNT=2"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let occurrences = rrule_set.occurrences().collect::<Vec<_>>();
+    assert_eq!(occurrences.len(), 2);
+    assert!(occurrences
+        .iter()
+        .all(|occurrence| occurrence.end == DateTime::<Utc>::MAX_UTC));
+}
+
+#[test]
+fn negative_duration_before_earliest_datetime_is_rejected() {
+    for duration in ["-P20000000W", "-P4000000000W"] {
+        let res = format!("DTSTART:20250106T090000Z\nDURATION:{duration}\nRRULE:FREQ=DAILY")
+            .parse::<RRuleSet>();
+        assert!(res.is_err(), "{duration}");
+    }
+}
+
+#[test]
+fn occurrences_without_duration() {
+    let rrule_set = "DTSTART:20201214T093000Z\n\
+        RRULE:FREQ=DAILY;COUNT=2"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    assert!(rrule_set
+        .occurrences()
+        .all(|occurrence| occurrence.start == occurrence.end));
+}
+
+#[test]
+fn overlapping_includes_ongoing_occurrences() {
+    let rrule_set = "DTSTART:20201214T090000Z\n\
+        DURATION:PT2H\n\
+        RRULE:FREQ=DAILY;COUNT=10"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let start = Tz::UTC.with_ymd_and_hms(2020, 12, 15, 10, 0, 0).unwrap();
+    let end = Tz::UTC.with_ymd_and_hms(2020, 12, 17, 8, 0, 0).unwrap();
+    let result = rrule_set.clone().overlapping(start, end, 10);
+    let starts = result
+        .occurrences
+        .iter()
+        .map(|occurrence| occurrence.start)
+        .collect::<Vec<_>>();
+    check_occurrences(
+        &starts,
+        &["2020-12-15T09:00:00+00:00", "2020-12-16T09:00:00+00:00"],
+    );
+    assert!(!result.limited);
+
+    // A start based filter misses the ongoing occurrence
+    let dates = rrule_set.after(start).before(end).all(10).dates;
+    check_occurrences(&dates, &["2020-12-16T09:00:00+00:00"]);
+}
+
+#[test]
+fn overlapping_respects_limit() {
+    let rrule_set = "DTSTART:20201214T090000Z\n\
+        DURATION:PT2H\n\
+        RRULE:FREQ=HOURLY"
+        .parse::<RRuleSet>()
+        .unwrap();
+
+    let start = Tz::UTC.with_ymd_and_hms(2020, 12, 15, 0, 0, 0).unwrap();
+    let end = Tz::UTC.with_ymd_and_hms(2020, 12, 16, 0, 0, 0).unwrap();
+    let result = rrule_set.overlapping(start, end, 3);
+    assert_eq!(result.occurrences.len(), 3);
+    assert!(result.limited);
+    assert_eq!(
+        result.occurrences[0].start,
+        Tz::UTC.with_ymd_and_hms(2020, 12, 14, 23, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn dtend_and_duration_roundtrip() {
+    let rrule_set_str = "DTSTART:20201214T093000Z\n\
+        DTEND:20201214T103000Z\n\
+        RRULE:FREQ=DAILY;COUNT=2;BYHOUR=9;BYMINUTE=30;BYSECOND=0";
+    let rrule_set = rrule_set_str.parse::<RRuleSet>().unwrap();
+    assert_eq!(
+        rrule_set.get_dt_end(),
+        Some(&Tz::UTC.with_ymd_and_hms(2020, 12, 14, 10, 30, 0).unwrap())
+    );
+    assert_eq!(rrule_set.to_string(), rrule_set_str);
+
+    let rrule_set_str = "DTSTART:20201214T093000Z\n\
+        DURATION:P1DT2H\n\
+        RRULE:FREQ=DAILY;COUNT=2;BYHOUR=9;BYMINUTE=30;BYSECOND=0";
+    let rrule_set = rrule_set_str.parse::<RRuleSet>().unwrap();
+    assert_eq!(rrule_set.get_duration(), Some(&Duration::hours(26)));
+    assert_eq!(rrule_set.to_string(), rrule_set_str);
+}
+
+#[test]
+fn rejects_invalid_event_end() {
+    let tests = [
+        "DTSTART:20201214T093000Z\nDTEND:20201214T103000Z\nDURATION:PT1H\nRRULE:FREQ=DAILY",
+        "DTSTART:20201214T093000Z\nDURATION:PT1H\nDURATION:PT2H\nRRULE:FREQ=DAILY",
+        "DTSTART:20201214T093000Z\nDTEND:20201214T083000Z\nRRULE:FREQ=DAILY",
+        "DTSTART:20201214T093000Z\nDURATION:-PT1H\nRRULE:FREQ=DAILY",
+    ];
+    for input in tests {
+        assert!(input.parse::<RRuleSet>().is_err(), "{input}");
+    }
+}
+
 #[test]
 #[cfg(feature = "exrule")]
 fn rrule_and_exyearly_yearly_big() {
@@ -674,3 +1028,106 @@ fn yearly_with_interval_2() {
         &[ymd_hms(1960, 1, 1, 9, 0, 0), ymd_hms(1962, 1, 1, 9, 0, 0)],
     );
 }
+
+#[test]
+fn parses_folded_lines_copied_from_ics_file() {
+    let dates = "DTSTART;TZID=\"Europe/Berlin\";X-LABEL=\"Team: weekly\":20201214T\r\n 093000\r\n\
+        rrule:FREQ=WEEKLY;COUNT=3;\r\n\tBYDAY=MO\r\n\
+        ExDate;TZID=Europe/Berlin:20201221T093000\r\n"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &["2020-12-14T09:30:00+01:00", "2020-12-28T09:30:00+01:00"],
+    );
+}
+
+#[test]
+fn rejects_parameter_with_open_quote() {
+    let res = "DTSTART:20250101T090000Z\n\
+        RDATE;X-A=\"oops:20250105T090000Z"
+        .parse::<RRuleSet>();
+    assert_eq!(
+        res.unwrap_err(),
+        RRuleError::from(ParseError::InvalidParameterFormat(
+            "RDATE;X-A=\"oops:20250105T090000Z".into()
+        ))
+    );
+}
+
+#[cfg(feature = "windows-zones")]
+#[test]
+fn parses_and_prints_windows_timezone_names() {
+    let rrule_set = "DTSTART;TZID=Pacific Standard Time:20210305T090000\n\
+        RRULE:FREQ=WEEKLY;COUNT=3\n\
+        EXDATE;TZID=Pacific Standard Time:20210312T090000"
+        .parse::<RRuleSet>()
+        .unwrap();
+    assert_eq!(
+        rrule_set.get_dt_start().timezone(),
+        Tz::America__Los_Angeles
+    );
+    check_occurrences(
+        &rrule_set.clone().all(u16::MAX).dates,
+        &["2021-03-05T09:00:00-08:00", "2021-03-19T09:00:00-07:00"],
+    );
+
+    let windows = rrule_set.to_windows_string();
+    assert!(windows.starts_with("DTSTART;TZID=Pacific Standard Time:20210305T090000\n"));
+    assert_eq!(windows.parse::<RRuleSet>().unwrap(), rrule_set);
+    assert!(rrule_set
+        .to_string()
+        .starts_with("DTSTART;TZID=America/Los_Angeles:20210305T090000\n"));
+}
+
+#[test]
+fn fixed_offset_datetimes_keep_their_offset() {
+    let offset = chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    let dt_start = offset.with_ymd_and_hms(2021, 3, 1, 9, 0, 0).unwrap();
+    let tz = Tz::from(*dt_start.offset());
+    let rrule_set = RRule::new(Frequency::Daily)
+        .count(2)
+        .build(dt_start.with_timezone(&tz))
+        .unwrap()
+        .rdate(tz.with_ymd_and_hms(2021, 3, 10, 9, 0, 0).unwrap())
+        .exdate(tz.with_ymd_and_hms(2021, 3, 2, 9, 0, 0).unwrap());
+
+    assert_eq!(tz.name(), "UTC+05:30");
+    check_occurrences(
+        &rrule_set.clone().all(u16::MAX).dates,
+        &["2021-03-01T09:00:00+05:30", "2021-03-10T09:00:00+05:30"],
+    );
+
+    let ical = rrule_set.to_string();
+    assert!(ical.starts_with("DTSTART;TZID=\"UTC+05:30\":20210301T090000\n"));
+    assert!(ical.contains("\nRDATE;VALUE=DATE-TIME;TZID=\"UTC+05:30\":20210310T090000"));
+    assert_eq!(ical.parse::<RRuleSet>().unwrap(), rrule_set);
+}
+
+#[test]
+fn fixed_offset_requires_utc_until() {
+    let tz = Tz::Fixed(chrono::FixedOffset::west_opt(3 * 3600).unwrap());
+    let dt_start = tz.with_ymd_and_hms(2021, 3, 1, 9, 0, 0).unwrap();
+
+    let result = RRule::new(Frequency::Daily)
+        .until(tz.with_ymd_and_hms(2021, 3, 5, 9, 0, 0).unwrap())
+        .build(dt_start);
+    assert!(result.is_err());
+
+    let dates = RRule::new(Frequency::Daily)
+        .until(Tz::UTC.with_ymd_and_hms(2021, 3, 3, 12, 0, 0).unwrap())
+        .build(dt_start)
+        .unwrap()
+        .all(u16::MAX)
+        .dates;
+    check_occurrences(
+        &dates,
+        &[
+            "2021-03-01T09:00:00-03:00",
+            "2021-03-02T09:00:00-03:00",
+            "2021-03-03T09:00:00-03:00",
+        ],
+    );
+}
@@ -0,0 +1,134 @@
+use crate::tests::common::{check_occurrences, ymd_hms};
+use crate::{RRuleSet, RecurrenceOverride, RecurrenceRange, Tz};
+use chrono::DateTime;
+
+/// Checks that seeking returns the same dates as filtering the forward iterator.
+fn check_seek(rrule_set: &RRuleSet, from: DateTime<Tz>) {
+    let expected = rrule_set
+        .into_iter()
+        .skip_while(|date| *date < from)
+        .take(50)
+        .collect::<Vec<_>>();
+    let dates = rrule_set.iter_from(from).take(50).collect::<Vec<_>>();
+    assert_eq!(dates, expected, "seeking to {from}");
+}
+
+#[test]
+fn seeks_like_filtering() {
+    let rules = [
+        "FREQ=YEARLY",
+        "FREQ=YEARLY;INTERVAL=4;BYMONTH=2;BYMONTHDAY=29",
+        "FREQ=YEARLY;BYDAY=20MO",
+        "FREQ=YEARLY;BYWEEKNO=53;BYDAY=MO",
+        "FREQ=MONTHLY;INTERVAL=5;BYMONTHDAY=31",
+        "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+        "FREQ=WEEKLY;INTERVAL=3;WKST=SU;BYDAY=SU,TU",
+        "FREQ=DAILY;INTERVAL=9",
+        "FREQ=DAILY;BYMONTH=3;BYHOUR=9,18",
+        "FREQ=HOURLY;INTERVAL=7",
+        "FREQ=HOURLY;BYDAY=SA;BYHOUR=7,19",
+        "FREQ=MINUTELY;INTERVAL=17;BYHOUR=10",
+        "FREQ=SECONDLY;INTERVAL=7;BYMINUTE=5",
+        "FREQ=DAILY;COUNT=1000",
+        "FREQ=WEEKLY;UNTIL=20210301T000000Z",
+    ];
+    let instants = [
+        ymd_hms(2020, 12, 31, 0, 0, 0),
+        ymd_hms(2021, 1, 1, 9, 0, 0),
+        ymd_hms(2021, 2, 13, 10, 30, 0),
+        ymd_hms(2021, 3, 4, 19, 0, 1),
+    ];
+    for rule in rules {
+        let rrule_set: RRuleSet = format!("DTSTART:20210101T090000Z\nRRULE:{rule}")
+            .parse()
+            .unwrap();
+        for from in instants {
+            check_seek(&rrule_set, from);
+        }
+    }
+}
+
+#[test]
+fn seeks_in_timezone() {
+    let rrule_set: RRuleSet = "DTSTART;TZID=Europe/Berlin:20210101T023000\n\
+        RRULE:FREQ=DAILY"
+        .parse()
+        .unwrap();
+    check_seek(&rrule_set, ymd_hms(2021, 3, 27, 0, 0, 0));
+    check_seek(&rrule_set, ymd_hms(2021, 10, 31, 1, 0, 0));
+
+    let rrule_set: RRuleSet = "DTSTART;TZID=America/New_York:20210101T120000\n\
+        RRULE:FREQ=HOURLY;INTERVAL=5"
+        .parse()
+        .unwrap();
+    check_seek(&rrule_set, ymd_hms(2021, 3, 14, 3, 0, 0));
+}
+
+#[test]
+fn seeks_far_into_the_future() {
+    let rrule_set: RRuleSet = "DTSTART:19900101T090000Z\n\
+        RRULE:FREQ=SECONDLY;INTERVAL=3"
+        .parse()
+        .unwrap();
+    let dates = rrule_set
+        .iter_from(ymd_hms(2030, 6, 1, 12, 0, 1))
+        .take(2)
+        .collect::<Vec<_>>();
+    check_occurrences(
+        &dates,
+        &["2030-06-01T12:00:03+00:00", "2030-06-01T12:00:06+00:00"],
+    );
+}
+
+#[test]
+fn seeks_rule_set() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO\n\
+        RRULE:FREQ=MONTHLY;BYMONTHDAY=1,15;COUNT=20\n\
+        EXRULE:FREQ=MONTHLY;BYDAY=1MO\n\
+        RDATE:20210102T100000Z,20210601T100000Z\n\
+        EXDATE:20210315T090000Z"
+        .parse()
+        .unwrap();
+    check_seek(&rrule_set, ymd_hms(2021, 3, 1, 10, 0, 0));
+    check_seek(&rrule_set, ymd_hms(2021, 5, 31, 10, 0, 0));
+}
+
+#[test]
+fn seeks_with_overrides() {
+    let rrule_set = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=DAILY"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .override_occurrence(
+            ymd_hms(2021, 1, 2, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 5, 12, 0, 0)),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 4, 9, 0, 0),
+            RecurrenceOverride::moved(ymd_hms(2021, 1, 4, 7, 0, 0))
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .override_occurrence(
+            ymd_hms(2021, 1, 20, 9, 0, 0),
+            RecurrenceOverride::cancelled().range(RecurrenceRange::ThisAndFuture),
+        );
+    check_seek(&rrule_set, ymd_hms(2021, 1, 5, 0, 0, 0));
+    check_seek(&rrule_set, ymd_hms(2021, 1, 10, 0, 0, 0));
+    check_seek(&rrule_set, ymd_hms(2021, 1, 25, 0, 0, 0));
+}
+
+#[test]
+fn all_starts_at_after() {
+    let dates = "DTSTART:19900101T090000Z\n\
+        RRULE:FREQ=MINUTELY"
+        .parse::<RRuleSet>()
+        .unwrap()
+        .after(ymd_hms(2030, 1, 1, 0, 0, 0))
+        .all(2)
+        .dates;
+    check_occurrences(
+        &dates,
+        &["2030-01-01T00:00:00+00:00", "2030-01-01T00:01:00+00:00"],
+    );
+}
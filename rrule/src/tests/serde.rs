@@ -7,6 +7,7 @@ fn rrule_set_to_and_from_str() {
         "DTSTART:20120201T093000Z\nRRULE:FREQ=YEARLY;COUNT=3",
         "DTSTART:20120201T093000Z\nRRULE:FREQ=WEEKLY;INTERVAL=5;BYDAY=-2MO,FR",
         "DTSTART;TZID=America/New_York:19000201T093000Z\nRRULE:UNTIL=19990404T110000Z;FREQ=WEEKLY;BYDAY=TU,WE",
+        "DTSTART;TZID=\"UTC+05:30\":20120201T093000\nRRULE:FREQ=DAILY;COUNT=3\nRDATE;TZID=\"UTC+05:30\":20120210T093000",
     ];
 
     for test_str in test_cases {
@@ -31,6 +32,7 @@ fn serialize_deserialize_json_to_and_from_rrule_set() {
         "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=5",
         "DTSTART:20120201T093000Z\nRRULE:FREQ=WEEKLY;INTERVAL=5;BYDAY=-2MO,FR",
         "DTSTART;TZID=America/New_York:19000201T093000Z\nRRULE:UNTIL=19990404T110000Z;FREQ=WEEKLY;BYDAY=TU,WE",
+        "DTSTART;TZID=\"UTC+05:30\":20120201T093000\nRRULE:FREQ=DAILY;COUNT=3\nRDATE;TZID=\"UTC+05:30\":20120210T093000",
     ];
 
     for test_str in test_cases {
@@ -43,3 +45,137 @@ fn serialize_deserialize_json_to_and_from_rrule_set() {
         assert_eq!(src_obj, final_obj);
     }
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn structured_rrule_set_round_trips() {
+    use crate::Structured;
+
+    let test_cases = [
+        "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=5",
+        "DTSTART;TZID=Europe/Berlin:20250106T090000\nDTEND;TZID=Europe/Berlin:20250106T100000\n\
+         RRULE:FREQ=MONTHLY;UNTIL=20251231T230000Z;BYDAY=-1FR;BYMONTH=3,6,9,12\n\
+         EXDATE;TZID=Europe/Berlin:20250328T090000",
+        "DTSTART:20250101T090000\nDURATION:PT1H30M\nRRULE:FREQ=YEARLY;BYMONTHDAY=1,-1;WKST=SU\n\
+         RDATE;VALUE=DATE:20250201\nEXDATE;VALUE=DATE:20260101",
+        "DTSTART:20250101T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=3\n\
+         RDATE;VALUE=PERIOD:20250301T090000Z/PT2H,20250302T090000Z/20250302T100000Z",
+        "DTSTART;TZID=\"UTC+05:30\":20120201T093000\nRRULE:FREQ=DAILY;COUNT=3\nRDATE;TZID=\"UTC+05:30\":20120210T093000",
+        "DTSTART:20250101T000000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;SKIP=FORWARD;BYMONTH=5L;BYMONTHDAY=8",
+    ];
+
+    for test_str in test_cases {
+        let rrule_set: RRuleSet = test_str.parse().unwrap();
+        let json = serde_json::to_string(&Structured(rrule_set.clone())).unwrap();
+        let Structured(deserialized) = serde_json::from_str::<Structured<RRuleSet>>(&json).unwrap();
+        assert_eq!(deserialized, rrule_set, "{json}");
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn structured_rrule_set_has_typed_fields() {
+    use crate::Structured;
+    use serde_json::json;
+
+    let rrule_set: RRuleSet = "DTSTART;TZID=America/New_York:20250106T090000\n\
+        RRULE:FREQ=MONTHLY;COUNT=4;BYMONTH=1,7;BYDAY=MO,-1FR"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(Structured(rrule_set)).unwrap(),
+        json!({
+            "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "America/New_York"},
+            "rrule": [{
+                "freq": "MONTHLY",
+                "interval": 1,
+                "count": 4,
+                "week_start": "Mon",
+                "by_month": ["January", "July"],
+                "by_weekday": [{"weekday": "Mon"}, {"n": -1, "weekday": "Fri"}],
+                "by_hour": [9],
+                "by_minute": [0],
+                "by_second": [0],
+            }],
+        })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn structured_rrule_round_trips_unvalidated() {
+    use crate::{RRule, Structured, Unvalidated};
+
+    let rrule: RRule<Unvalidated> = "FREQ=WEEKLY;UNTIL=20250301T090000Z;BYDAY=TU,2WE;BYSETPOS=1"
+        .parse()
+        .unwrap();
+    let json = serde_json::to_string(&Structured(rrule.clone())).unwrap();
+    let Structured(deserialized) =
+        serde_json::from_str::<Structured<RRule<Unvalidated>>>(&json).unwrap();
+    assert_eq!(deserialized, rrule);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn structured_rrule_set_is_validated() {
+    use crate::{RRuleError, Structured, ValidationError};
+
+    let json = r#"{
+        "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+        "rrule": [{"freq": "DAILY", "by_hour": [25]}]
+    }"#;
+    let error = serde_json::from_str::<Structured<RRuleSet>>(json).unwrap_err();
+    let expected = RRuleError::from(ValidationError::InvalidFieldValueRange {
+        field: "BYHOUR".into(),
+        value: "25".into(),
+        start_idx: "0".into(),
+        end_idx: "23".into(),
+    });
+    assert!(
+        error.to_string().starts_with(&expected.to_string()),
+        "{error}"
+    );
+
+    let json = r#"{
+        "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+        "dt_end": {"datetime": "2025-01-06T08:00:00", "tzid": "UTC"},
+        "rrule": [{"freq": "DAILY"}]
+    }"#;
+    let error = serde_json::from_str::<Structured<RRuleSet>>(json).unwrap_err();
+    assert!(error.to_string().contains("is later"), "{error}");
+
+    let json = r#"{
+        "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+        "duration": "-P20000000W",
+        "rrule": [{"freq": "DAILY"}]
+    }"#;
+    let error = serde_json::from_str::<Structured<RRuleSet>>(json).unwrap_err();
+    assert!(error.to_string().contains("is later"), "{error}");
+
+    let json = r#"{
+        "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+        "rdate_period": [{
+            "start": {"datetime": "2025-01-07T09:00:00", "tzid": "UTC"},
+            "duration": "P20000000W"
+        }]
+    }"#;
+    let error = serde_json::from_str::<Structured<RRuleSet>>(json).unwrap_err();
+    assert!(error.to_string().contains("not a valid period"), "{error}");
+
+    let json = r#"{
+        "dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "UTC"},
+        "rrule": [{"freq": "MONTHLY", "by_weekday": [{"n": 0, "weekday": "Mon"}]}]
+    }"#;
+    let error = serde_json::from_str::<Structured<RRuleSet>>(json).unwrap_err();
+    let expected = RRuleError::from(ValidationError::InvalidFieldValue {
+        field: "BYDAY".into(),
+        value: "0".into(),
+    });
+    assert!(
+        error.to_string().starts_with(&expected.to_string()),
+        "{error}"
+    );
+
+    let json = r#"{"dt_start": {"datetime": "2025-01-06T09:00:00", "tzid": "Mars/Olympus"}}"#;
+    assert!(serde_json::from_str::<Structured<RRuleSet>>(json).is_err());
+}
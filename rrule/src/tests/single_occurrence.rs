@@ -0,0 +1,111 @@
+use crate::tests::common::ymd_hms;
+use crate::{Frequency, RRule, RRuleSet};
+
+fn weekly_rrule_set() -> RRuleSet {
+    "DTSTART:20210104T090000Z\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO,TH;UNTIL=20210301T090000Z\n\
+        EXDATE:20210111T090000Z"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn next_after() {
+    let rrule_set = weekly_rrule_set();
+    let thursday = ymd_hms(2021, 1, 7, 9, 0, 0);
+
+    assert_eq!(rrule_set.next_after(thursday, true), Some(thursday));
+    // The next monday is excluded
+    assert_eq!(
+        rrule_set.next_after(thursday, false),
+        Some(ymd_hms(2021, 1, 14, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule_set.next_after(ymd_hms(2020, 1, 1, 0, 0, 0), false),
+        Some(ymd_hms(2021, 1, 4, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule_set.next_after(ymd_hms(2021, 3, 1, 9, 0, 0), false),
+        None
+    );
+}
+
+#[test]
+fn prev_before() {
+    let rrule_set = weekly_rrule_set();
+    let thursday = ymd_hms(2021, 1, 14, 9, 0, 0);
+
+    assert_eq!(rrule_set.prev_before(thursday, true), Some(thursday));
+    // The previous monday is excluded
+    assert_eq!(
+        rrule_set.prev_before(thursday, false),
+        Some(ymd_hms(2021, 1, 7, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2030, 1, 1, 0, 0, 0), false),
+        Some(ymd_hms(2021, 3, 1, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2021, 1, 4, 9, 0, 0), false),
+        None
+    );
+}
+
+#[test]
+fn prev_before_infinite_rule() {
+    let rrule_set: RRuleSet = "DTSTART:19900101T090000Z\n\
+        RRULE:FREQ=HOURLY;INTERVAL=5"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2030, 1, 1, 0, 0, 0), true),
+        Some(ymd_hms(2029, 12, 31, 23, 0, 0))
+    );
+}
+
+#[test]
+fn is_occurrence() {
+    let rrule_set = weekly_rrule_set();
+
+    assert!(rrule_set.is_occurrence(ymd_hms(2021, 1, 4, 9, 0, 0)));
+    assert!(rrule_set.is_occurrence(ymd_hms(2021, 3, 1, 9, 0, 0)));
+    assert!(!rrule_set.is_occurrence(ymd_hms(2021, 1, 11, 9, 0, 0)));
+    assert!(!rrule_set.is_occurrence(ymd_hms(2021, 1, 5, 9, 0, 0)));
+    assert!(!rrule_set.is_occurrence(ymd_hms(2021, 3, 4, 9, 0, 0)));
+}
+
+#[test]
+fn ignores_before_and_after() {
+    let rrule_set = weekly_rrule_set()
+        .after(ymd_hms(2021, 2, 1, 0, 0, 0))
+        .before(ymd_hms(2021, 2, 8, 0, 0, 0));
+
+    assert_eq!(
+        rrule_set.next_after(ymd_hms(2021, 1, 1, 0, 0, 0), false),
+        Some(ymd_hms(2021, 1, 4, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2030, 1, 1, 0, 0, 0), false),
+        Some(ymd_hms(2021, 3, 1, 9, 0, 0))
+    );
+}
+
+#[test]
+fn rrule_queries() {
+    let dt_start = ymd_hms(2021, 1, 1, 9, 0, 0);
+    let rrule = RRule::new(Frequency::Daily)
+        .interval(2)
+        .validate(dt_start)
+        .unwrap();
+
+    assert_eq!(
+        rrule.next_after(dt_start, ymd_hms(2021, 1, 2, 0, 0, 0), false),
+        Some(ymd_hms(2021, 1, 3, 9, 0, 0))
+    );
+    assert_eq!(
+        rrule.prev_before(dt_start, ymd_hms(2021, 1, 3, 9, 0, 0), false),
+        Some(dt_start)
+    );
+    assert!(rrule.is_occurrence(dt_start, ymd_hms(2021, 1, 5, 9, 0, 0)));
+    assert!(!rrule.is_occurrence(dt_start, ymd_hms(2021, 1, 4, 9, 0, 0)));
+}
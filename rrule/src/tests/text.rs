@@ -0,0 +1,284 @@
+use crate::tests::common::ymd_hms;
+use crate::{ParseError, RRule, RRuleError, RRuleSet, Unvalidated};
+
+fn text(rrule: &str) -> String {
+    rrule.parse::<RRule<Unvalidated>>().unwrap().to_text()
+}
+
+#[test]
+fn describes_frequencies() {
+    assert_eq!(text("FREQ=YEARLY"), "every year");
+    assert_eq!(text("FREQ=MONTHLY;INTERVAL=3"), "every 3 months");
+    assert_eq!(text("FREQ=WEEKLY"), "every week");
+    assert_eq!(text("FREQ=DAILY;INTERVAL=2"), "every 2 days");
+    assert_eq!(text("FREQ=HOURLY"), "every hour");
+    assert_eq!(text("FREQ=MINUTELY;INTERVAL=15"), "every 15 minutes");
+    assert_eq!(text("FREQ=SECONDLY"), "every second");
+}
+
+#[test]
+fn describes_weekdays() {
+    assert_eq!(
+        text("FREQ=WEEKLY;BYDAY=MO,WE,FR"),
+        "every week on Monday, Wednesday and Friday"
+    );
+    assert_eq!(text("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"), "every weekday");
+    assert_eq!(text("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR"), "every weekday");
+    assert_eq!(
+        text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TU,WE,TH,FR"),
+        "every 2 weeks on Monday, Tuesday, Wednesday, Thursday and Friday"
+    );
+    assert_eq!(
+        text("FREQ=MONTHLY;BYDAY=1MO,-1FR,-2SU"),
+        "every month on the 1st Monday, the last Friday and the 2nd to last Sunday"
+    );
+}
+
+#[test]
+fn describes_month_days() {
+    assert_eq!(
+        text("FREQ=MONTHLY;BYMONTHDAY=1,15"),
+        "every month on the 1st and the 15th"
+    );
+    assert_eq!(
+        text("FREQ=MONTHLY;BYMONTHDAY=-1"),
+        "every month on the last day"
+    );
+    assert_eq!(
+        text("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13"),
+        "every month on Friday the 13th"
+    );
+}
+
+#[test]
+fn describes_yearly_parts() {
+    assert_eq!(
+        text("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"),
+        "every year in November on the 4th Thursday"
+    );
+    assert_eq!(
+        text("FREQ=YEARLY;BYYEARDAY=1,100,-1"),
+        "every year on the last, 1st and 100th day of the year"
+    );
+    assert_eq!(
+        text("FREQ=YEARLY;BYWEEKNO=1,-1;BYDAY=MO"),
+        "every year in the last week and week 1 on Monday"
+    );
+}
+
+#[test]
+fn describes_times() {
+    assert_eq!(
+        text("FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30"),
+        "every day at 9:00, 9:30, 17:00 and 17:30"
+    );
+    assert_eq!(
+        text("FREQ=DAILY;BYHOUR=9;BYMINUTE=0;BYSECOND=30"),
+        "every day at 9:00:30"
+    );
+    assert_eq!(text("FREQ=DAILY;BYHOUR=9"), "every day at hour 9");
+    assert_eq!(
+        text("FREQ=HOURLY;BYHOUR=9,10;BYMINUTE=30;BYSECOND=0"),
+        "every hour at hour 9 and 10 at minute 30"
+    );
+    assert_eq!(
+        text("FREQ=MINUTELY;BYSECOND=15,45"),
+        "every minute at second 15 and 45"
+    );
+}
+
+#[test]
+fn describes_count_and_until() {
+    assert_eq!(text("FREQ=DAILY;COUNT=1"), "every day for 1 time");
+    assert_eq!(text("FREQ=DAILY;COUNT=10"), "every day for 10 times");
+    assert_eq!(
+        text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z"),
+        "every 2 weeks on Monday and Friday until March 3, 2025"
+    );
+}
+
+#[test]
+fn describes_validated_rule() {
+    let rrule_set: RRuleSet = "DTSTART:20210115T093000Z\n\
+        RRULE:FREQ=MONTHLY;COUNT=3"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.to_text(),
+        "every month on the 15th at 9:30 for 3 times"
+    );
+    assert!(rrule_set.unsupported_text_parts().is_empty());
+}
+
+#[test]
+fn describes_rule_set() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO\n\
+        RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1\n\
+        EXDATE:20210104T090000Z"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.to_text(),
+        "every week on Monday at 9:00 and every month on Monday, Tuesday, Wednesday, Thursday and Friday at 9:00"
+    );
+    assert_eq!(
+        rrule_set.unsupported_text_parts(),
+        vec!["BYSETPOS=-1", "EXDATE"]
+    );
+}
+
+#[test]
+fn reports_unsupported_parts() {
+    let rrule: RRule<Unvalidated> = "FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO".parse().unwrap();
+    assert_eq!(rrule.unsupported_text_parts(), vec!["WKST=SU"]);
+
+    let rrule: RRule<Unvalidated> = "FREQ=WEEKLY;WKST=SU;BYDAY=MO".parse().unwrap();
+    assert!(rrule.unsupported_text_parts().is_empty());
+    let rrule: RRule<Unvalidated> = "RSCALE=GREGORIAN;FREQ=MONTHLY;SKIP=BACKWARD;BYMONTHDAY=31"
+        .parse()
+        .unwrap();
+    assert_eq!(rrule.unsupported_text_parts(), vec!["SKIP=BACKWARD"]);
+
+    let rrule: RRule<Unvalidated> = "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=7;BYMONTHDAY=15"
+        .parse()
+        .unwrap();
+    assert_eq!(rrule.unsupported_text_parts(), vec!["RSCALE=HEBREW"]);
+}
+
+fn from_text(text: &str) -> Result<RRule<Unvalidated>, RRuleError> {
+    RRule::from_text_relative_to(text, ymd_hms(2024, 8, 15, 12, 0, 0))
+}
+
+#[test]
+fn parses_text() {
+    let tests = [
+        (
+            "every other Tuesday at 9am until June",
+            "FREQ=WEEKLY;UNTIL=20250630T235959Z;INTERVAL=2;BYHOUR=9;BYMINUTE=0;BYSECOND=0;BYDAY=TU",
+        ),
+        ("daily", "FREQ=DAILY"),
+        ("Every 3 months", "FREQ=MONTHLY;INTERVAL=3"),
+        ("every two weeks", "FREQ=WEEKLY;INTERVAL=2"),
+        (
+            "every Monday and Friday until March 3, 2025",
+            "FREQ=WEEKLY;UNTIL=20250303T235959Z;BYDAY=MO,FR",
+        ),
+        (
+            "every weekday at 9:30 pm",
+            "FREQ=WEEKLY;BYHOUR=21;BYMINUTE=30;BYSECOND=0;BYDAY=MO,TU,WE,TH,FR",
+        ),
+        ("every January and July", "FREQ=YEARLY;BYMONTH=1,7"),
+        (
+            "every month on the second Monday twice",
+            "FREQ=MONTHLY;COUNT=2;BYDAY=2MO",
+        ),
+        (
+            "every day at noon and midnight 10 times",
+            "FREQ=DAILY;COUNT=10;BYHOUR=0,12;BYMINUTE=0;BYSECOND=0",
+        ),
+        (
+            "every year until 3rd of March 2025",
+            "FREQ=YEARLY;UNTIL=20250303T235959Z",
+        ),
+        (
+            "every week until 2025-03-03",
+            "FREQ=WEEKLY;UNTIL=20250303T235959Z",
+        ),
+        // Dates without a year are on or after the reference date.
+        (
+            "every day until August 15",
+            "FREQ=DAILY;UNTIL=20240815T235959Z",
+        ),
+        (
+            "every day until August 14",
+            "FREQ=DAILY;UNTIL=20250814T235959Z",
+        ),
+    ];
+    for (text, expected) in tests {
+        assert_eq!(from_text(text).unwrap().to_string(), expected, "{text}");
+    }
+}
+
+#[test]
+fn round_trips_text() {
+    let rrules = [
+        "FREQ=YEARLY;INTERVAL=2",
+        "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+        "FREQ=YEARLY;BYYEARDAY=-1,1,100",
+        "FREQ=YEARLY;BYWEEKNO=-2,-1,1,20;BYDAY=MO",
+        "FREQ=MONTHLY;BYMONTHDAY=-1,1,15",
+        "FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR",
+        "FREQ=MONTHLY;COUNT=1;BYDAY=-2SU,-1FR,1MO",
+        "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TU,WE,TH,FR",
+        "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR",
+        "FREQ=DAILY;COUNT=10;BYHOUR=9,17;BYMINUTE=0,30;BYSECOND=0",
+        "FREQ=DAILY;BYHOUR=9;BYMINUTE=0;BYSECOND=30",
+        "FREQ=DAILY;BYHOUR=9",
+        "FREQ=HOURLY;BYHOUR=9,10;BYMINUTE=30",
+        "FREQ=MINUTELY;INTERVAL=15;BYSECOND=15,45",
+        "FREQ=SECONDLY",
+    ];
+    for rrule in rrules {
+        let rrule: RRule<Unvalidated> = rrule.parse().unwrap();
+        let text = rrule.to_text();
+        assert_eq!(from_text(&text).unwrap(), rrule, "{text}");
+    }
+
+    // `UNTIL` is rendered as a date, which includes the whole day when it is parsed.
+    let text = text("FREQ=DAILY;UNTIL=20250303T090000Z");
+    assert_eq!(from_text(&text).unwrap().to_text(), text);
+}
+
+#[test]
+fn rejects_invalid_text() {
+    let tests = [
+        (
+            "sometimes",
+            ParseError::MissingTextFrequency("sometimes".into()),
+        ),
+        (
+            "every fortnight",
+            ParseError::MissingTextFrequency("fortnight".into()),
+        ),
+        (
+            "every day except holidays",
+            ParseError::UnrecognizedText("except holidays".into()),
+        ),
+        (
+            "every month on the 1st business day",
+            ParseError::UnrecognizedText("business day".into()),
+        ),
+        (
+            "every day at 25:00",
+            ParseError::InvalidTextTime("25:00".into()),
+        ),
+        (
+            "every day at 13pm",
+            ParseError::InvalidTextTime("13pm".into()),
+        ),
+        (
+            "every day until tomorrow",
+            ParseError::InvalidTextDate("tomorrow".into()),
+        ),
+        (
+            "every year until February 30, 2025",
+            ParseError::InvalidTextDate("february 30, 2025".into()),
+        ),
+        (
+            "every day at 9:00 and 17:30",
+            ParseError::UnsupportedTextTimes("9:00 and 17:30".into()),
+        ),
+        (
+            "every month on the 200th",
+            ParseError::InvalidTextNumber("200".into()),
+        ),
+    ];
+    for (text, expected) in tests {
+        assert_eq!(
+            from_text(text).unwrap_err(),
+            RRuleError::ParserError(expected),
+            "{text}"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{ParseError, RRuleError, RRuleSet};
+
+#[test]
+fn rrule_set_to_and_from_xcal() {
+    let tests = [
+        (
+            "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,FR\n\
+             EXDATE;TZID=Europe/Berlin:20250110T090000,20250113T090000",
+            "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+             <dtstart>\
+             <parameters><tzid><text>Europe/Berlin</text></tzid></parameters>\
+             <date-time>2025-01-06T09:00:00</date-time>\
+             </dtstart>\
+             <rrule><recur>\
+             <freq>WEEKLY</freq><byhour>9</byhour><byminute>0</byminute><bysecond>0</bysecond>\
+             <byday>MO</byday><byday>FR</byday>\
+             </recur></rrule>\
+             <exdate>\
+             <parameters><tzid><text>Europe/Berlin</text></tzid></parameters>\
+             <date-time>2025-01-10T09:00:00</date-time><date-time>2025-01-13T09:00:00</date-time>\
+             </exdate>\
+             </properties>",
+        ),
+        (
+            "DTSTART:20250101T090000Z\n\
+             DURATION:PT1H30M\n\
+             RRULE:FREQ=DAILY;COUNT=3\n\
+             RDATE;VALUE=DATE:20250201\n\
+             RDATE;VALUE=PERIOD:20250301T090000Z/PT2H,20250302T090000Z/20250302T100000Z",
+            "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+             <dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart>\
+             <duration><duration>PT1H30M</duration></duration>\
+             <rrule><recur>\
+             <freq>DAILY</freq><count>3</count>\
+             <byhour>9</byhour><byminute>0</byminute><bysecond>0</bysecond>\
+             </recur></rrule>\
+             <rdate><date>2025-02-01</date></rdate>\
+             <rdate>\
+             <period><start>2025-03-01T09:00:00Z</start><duration>PT2H</duration></period>\
+             <period><start>2025-03-02T09:00:00Z</start><end>2025-03-02T10:00:00Z</end></period>\
+             </rdate>\
+             </properties>",
+        ),
+        (
+            "DTSTART:20250101T090000Z\n\
+             RDATE;VALUE=PERIOD;TZID=Europe/Berlin:20250301T090000/PT2H,20250302T090000/20250302T100000",
+            "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+             <dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart>\
+             <rdate>\
+             <parameters><tzid><text>Europe/Berlin</text></tzid></parameters>\
+             <period><start>2025-03-01T09:00:00</start><duration>PT2H</duration></period>\
+             <period><start>2025-03-02T09:00:00</start><end>2025-03-02T10:00:00</end></period>\
+             </rdate>\
+             </properties>",
+        ),
+    ];
+    for (text, xcal) in tests {
+        let rrule_set: RRuleSet = text.parse().unwrap();
+        assert_eq!(rrule_set.to_xcal(), xcal, "{text}");
+        assert_eq!(RRuleSet::from_xcal(xcal).unwrap(), rrule_set, "{text}");
+    }
+}
+
+#[cfg(feature = "exrule")]
+#[test]
+fn rrule_set_with_exrule_to_and_from_xcal() {
+    let rrule_set: RRuleSet = "DTSTART:20250101T090000Z\n\
+         RRULE:FREQ=DAILY;COUNT=10\n\
+         EXRULE:FREQ=WEEKLY;UNTIL=20250115T090000Z;BYDAY=SA,SU"
+        .parse()
+        .unwrap();
+    let xcal = rrule_set.to_xcal();
+    assert!(xcal.contains("<exrule><recur><freq>WEEKLY</freq><until>2025-01-15T09:00:00Z</until>"));
+    assert_eq!(RRuleSet::from_xcal(&xcal).unwrap(), rrule_set);
+}
+
+#[test]
+fn rrule_set_from_xcal_document() {
+    let xcal = r#"<?xml version="1.0" encoding="utf-8"?>
+        <icalendar xmlns="urn:ietf:params:xml:ns:icalendar-2.0">
+          <vcalendar>
+            <properties>
+              <prodid><text>-//Example Corp.//Example Client//EN</text></prodid>
+              <version><text>2.0</text></version>
+            </properties>
+            <components>
+              <vevent>
+                <properties>
+                  <summary><text>Planning &amp; review</text></summary>
+                  <dtstart>
+                    <parameters><tzid><text>America/New_York</text></tzid></parameters>
+                    <date-time>1997-09-02T09:00:00</date-time>
+                  </dtstart>
+                  <rrule>
+                    <recur><freq>DAILY</freq><count>3</count></recur>
+                  </rrule>
+                </properties>
+              </vevent>
+            </components>
+          </vcalendar>
+        </icalendar>"#;
+    let rrule_set = RRuleSet::from_xcal(xcal).unwrap();
+    assert_eq!(
+        rrule_set.to_string(),
+        "DTSTART;TZID=America/New_York:19970902T090000\n\
+         RRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    );
+}
+
+#[test]
+fn rrule_set_from_xcal_event_after_timezone() {
+    let xcal = r#"<icalendar xmlns="urn:ietf:params:xml:ns:icalendar-2.0">
+          <vcalendar>
+            <components>
+              <vtimezone>
+                <properties><tzid><text>America/New_York</text></tzid></properties>
+                <components>
+                  <standard>
+                    <properties>
+                      <dtstart><date-time>2007-11-04T02:00:00</date-time></dtstart>
+                      <tzoffsetfrom><utc-offset>-04:00</utc-offset></tzoffsetfrom>
+                      <tzoffsetto><utc-offset>-05:00</utc-offset></tzoffsetto>
+                    </properties>
+                  </standard>
+                </components>
+              </vtimezone>
+              <vevent>
+                <properties>
+                  <geo><latitude>37.386013</latitude><longitude>-122.082932</longitude></geo>
+                  <dtstart>
+                    <parameters><tzid><text>America/New_York</text></tzid></parameters>
+                    <date-time>1997-09-02T09:00:00</date-time>
+                  </dtstart>
+                  <rrule>
+                    <recur><freq>DAILY</freq><count>3</count></recur>
+                  </rrule>
+                </properties>
+              </vevent>
+            </components>
+          </vcalendar>
+        </icalendar>"#;
+    let rrule_set = RRuleSet::from_xcal(xcal).unwrap();
+    assert_eq!(
+        rrule_set.to_string(),
+        "DTSTART;TZID=America/New_York:19970902T090000\n\
+         RRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    );
+}
+
+#[test]
+fn rejects_xcal_values_which_end_the_content_line() {
+    let tests = [
+        "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+         <dtstart>\
+         <parameters><tzid><text>UTC&#10;RDATE:20250102T090000Z</text></tzid></parameters>\
+         <date-time>2025-01-01T09:00:00</date-time>\
+         </dtstart>\
+         <rrule><recur><freq>DAILY</freq><count>3</count></recur></rrule>\
+         </properties>",
+        "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+         <dtstart><date-time>2025-01-01T09:00:00Z&#13;&#10;RDATE:20250102T090000Z</date-time></dtstart>\
+         <rrule><recur><freq>DAILY</freq><count>3</count></recur></rrule>\
+         </properties>",
+    ];
+    for xcal in tests {
+        assert!(
+            matches!(
+                RRuleSet::from_xcal(xcal),
+                Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+            ),
+            "{xcal}"
+        );
+    }
+}
+
+#[test]
+fn rejects_xcal_values_which_add_rule_parts_or_values() {
+    let recurs = [
+        "<freq>DAILY;COUNT=2</freq>",
+        "<freq>DAILY</freq><byhour>9,10</byhour>",
+        "<freq>DAILY</freq><byday>MO</byday><byday>TU,WE</byday>",
+    ];
+    for recur in recurs {
+        let xcal = format!(
+            "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+             <dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart>\
+             <rrule><recur>{recur}</recur></rrule>\
+             </properties>"
+        );
+        assert!(
+            matches!(
+                RRuleSet::from_xcal(&xcal),
+                Err(RRuleError::ParserError(ParseError::InvalidPropertyValue(_)))
+            ),
+            "{xcal}"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_xcal() {
+    let tests = [
+        "<properties",
+        "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\"></properties>",
+        "<properties><dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart></properties>",
+        "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+         <dtstart><date-time>2025-01-01T09:00:00Z</date-time><date>2025-01-01</date></dtstart>\
+         </properties>",
+        "<properties xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\">\
+         <dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart>\
+         <rdate><period><start>2025-01-02T09:00:00Z</start></period></rdate>\
+         </properties>",
+    ];
+    for xcal in tests {
+        assert!(
+            matches!(
+                RRuleSet::from_xcal(xcal),
+                Err(RRuleError::ParserError(ParseError::InvalidXCal(_)))
+            ),
+            "{xcal}"
+        );
+    }
+}
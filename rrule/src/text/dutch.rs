@@ -0,0 +1,243 @@
+use super::Locale;
+use crate::{Frequency, NWeekday, Tz};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Weekday};
+
+/// Describes recurrence rules in Dutch, e.g. "elke 2 weken op maandag en vrijdag".
+///
+/// Requires the `locale-nl` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dutch;
+
+const MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "maart",
+    "april",
+    "mei",
+    "juni",
+    "juli",
+    "augustus",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+
+impl Dutch {
+    fn numbers<N: ToString>(&self, numbers: &[N]) -> String {
+        self.list(&numbers.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    /// A week number, e.g. "week 1" or "de laatste week".
+    fn week(&self, week: i8) -> String {
+        if week < 0 {
+            format!("de {} week", self.ordinal(i32::from(week)))
+        } else {
+            format!("week {week}")
+        }
+    }
+
+    /// A day of the month, e.g. "de 1e" or "de laatste dag".
+    fn month_day(&self, month_day: i8) -> String {
+        if month_day < 0 {
+            format!("de {} dag", self.ordinal(i32::from(month_day)))
+        } else {
+            format!("de {}", self.ordinal(i32::from(month_day)))
+        }
+    }
+}
+
+impl Locale for Dutch {
+    fn weekday(&self, weekday: Weekday) -> String {
+        match weekday {
+            Weekday::Mon => "maandag",
+            Weekday::Tue => "dinsdag",
+            Weekday::Wed => "woensdag",
+            Weekday::Thu => "donderdag",
+            Weekday::Fri => "vrijdag",
+            Weekday::Sat => "zaterdag",
+            Weekday::Sun => "zondag",
+        }
+        .to_string()
+    }
+
+    fn nth_weekday(&self, weekday: NWeekday) -> String {
+        match weekday {
+            NWeekday::Every(weekday) => self.weekday(weekday),
+            NWeekday::Nth(number, weekday) => format!(
+                "de {} {}",
+                self.ordinal(i32::from(number)),
+                self.weekday(weekday)
+            ),
+        }
+    }
+
+    fn month(&self, month: u8) -> String {
+        MONTHS
+            .get(usize::from(month).wrapping_sub(1))
+            .map_or_else(|| month.to_string(), ToString::to_string)
+    }
+
+    fn ordinal(&self, number: i32) -> String {
+        match number {
+            -1 => "laatste".to_string(),
+            -2 => "voorlaatste".to_string(),
+            number if number < 0 => format!("{}e laatste", number.unsigned_abs()),
+            number => format!("{number}e"),
+        }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [items @ .., last] => format!("{} en {last}", items.join(", ")),
+        }
+    }
+
+    fn every(&self, freq: Frequency, interval: u16) -> String {
+        if interval == 1 {
+            match freq {
+                Frequency::Yearly => "elk jaar",
+                Frequency::Monthly => "elke maand",
+                Frequency::Weekly => "elke week",
+                Frequency::Daily => "elke dag",
+                Frequency::Hourly => "elk uur",
+                Frequency::Minutely => "elke minuut",
+                Frequency::Secondly => "elke seconde",
+            }
+            .to_string()
+        } else {
+            let unit = match freq {
+                Frequency::Yearly => "jaar",
+                Frequency::Monthly => "maanden",
+                Frequency::Weekly => "weken",
+                Frequency::Daily => "dagen",
+                Frequency::Hourly => "uur",
+                Frequency::Minutely => "minuten",
+                Frequency::Secondly => "seconden",
+            };
+            format!("elke {interval} {unit}")
+        }
+    }
+
+    fn every_weekday(&self) -> String {
+        "elke werkdag".to_string()
+    }
+
+    fn in_weeks(&self, weeks: &[i8]) -> String {
+        let weeks = weeks
+            .iter()
+            .map(|week| self.week(*week))
+            .collect::<Vec<_>>();
+        format!("in {}", self.list(&weeks))
+    }
+
+    fn in_months(&self, months: &[u8]) -> String {
+        let months = months
+            .iter()
+            .map(|month| self.month(*month))
+            .collect::<Vec<_>>();
+        format!("in {}", self.list(&months))
+    }
+
+    fn on_year_days(&self, year_days: &[i16]) -> String {
+        let year_days = year_days
+            .iter()
+            .map(|year_day| self.ordinal(i32::from(*year_day)))
+            .collect::<Vec<_>>();
+        format!("op de {} dag van het jaar", self.list(&year_days))
+    }
+
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+        let weekdays = weekdays
+            .iter()
+            .map(|weekday| self.nth_weekday(*weekday))
+            .collect::<Vec<_>>();
+        let month_days = month_days
+            .iter()
+            .map(|month_day| self.month_day(*month_day))
+            .collect::<Vec<_>>();
+        match (weekdays.is_empty(), month_days.is_empty()) {
+            (false, true) => format!("op {}", self.list(&weekdays)),
+            (true, _) => format!("op {}", self.list(&month_days)),
+            (false, false) => format!("op {} {}", self.list(&weekdays), self.list(&month_days)),
+        }
+    }
+
+    fn at_times(&self, times: &[NaiveTime]) -> String {
+        let times = times
+            .iter()
+            .map(|time| {
+                if time.second() == 0 {
+                    time.format("%-H:%M").to_string()
+                } else {
+                    time.format("%-H:%M:%S").to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("om {}", self.list(&times))
+    }
+
+    fn at_hours(&self, hours: &[u8]) -> String {
+        format!("op uur {}", self.numbers(hours))
+    }
+
+    fn at_minutes(&self, minutes: &[u8]) -> String {
+        format!("op minuut {}", self.numbers(minutes))
+    }
+
+    fn at_seconds(&self, seconds: &[u8]) -> String {
+        format!("op seconde {}", self.numbers(seconds))
+    }
+
+    fn count(&self, count: u32) -> String {
+        format!("voor {count} keer")
+    }
+
+    fn until(&self, until: &DateTime<Tz>) -> String {
+        format!(
+            "tot {} {} {}",
+            until.day(),
+            self.month(u8::try_from(until.month()).expect("range 1-12 is covered by u8")),
+            until.year()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RRule, Unvalidated};
+
+    fn text(rrule: &str) -> String {
+        rrule
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .to_text_with(&Dutch)
+    }
+
+    #[test]
+    fn describes_rules() {
+        assert_eq!(
+            text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z"),
+            "elke 2 weken op maandag en vrijdag tot 3 maart 2025"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=1MO,-1FR,-2SU"),
+            "elke maand op de 1e maandag, de laatste vrijdag en de voorlaatste zondag"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13;COUNT=3"),
+            "elke maand op vrijdag de 13e voor 3 keer"
+        );
+        assert_eq!(
+            text("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"),
+            "elk jaar in november op de 4e donderdag"
+        );
+        assert_eq!(
+            text("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30"),
+            "elke werkdag om 9:30"
+        );
+    }
+}
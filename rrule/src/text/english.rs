@@ -0,0 +1,244 @@
+use super::Locale;
+use crate::{Frequency, NWeekday, Tz};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Weekday};
+
+/// Describes recurrence rules in English, e.g. "every 2 weeks on Monday and Friday".
+///
+/// This is the locale used by [`RRule::to_text`](crate::RRule::to_text).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl English {
+    /// The ordinal of a number, without counting from the end, e.g. "1st" or "22nd".
+    fn positive_ordinal(number: u32) -> String {
+        let suffix = match (number % 10, number % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{number}{suffix}")
+    }
+
+    fn numbers<N: ToString>(&self, numbers: &[N]) -> String {
+        self.list(&numbers.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    /// A week number, e.g. "week 1" or "the last week".
+    fn week(&self, week: i8) -> String {
+        if week < 0 {
+            format!("the {} week", self.ordinal(i32::from(week)))
+        } else {
+            format!("week {week}")
+        }
+    }
+
+    /// A day of the month, e.g. "the 1st" or "the last day".
+    fn month_day(&self, month_day: i8) -> String {
+        if month_day < 0 {
+            format!("the {} day", self.ordinal(i32::from(month_day)))
+        } else {
+            format!("the {}", self.ordinal(i32::from(month_day)))
+        }
+    }
+}
+
+impl Locale for English {
+    fn weekday(&self, weekday: Weekday) -> String {
+        match weekday {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        }
+        .to_string()
+    }
+
+    fn nth_weekday(&self, weekday: NWeekday) -> String {
+        match weekday {
+            NWeekday::Every(weekday) => self.weekday(weekday),
+            NWeekday::Nth(number, weekday) => format!(
+                "the {} {}",
+                self.ordinal(i32::from(number)),
+                self.weekday(weekday)
+            ),
+        }
+    }
+
+    fn month(&self, month: u8) -> String {
+        MONTHS
+            .get(usize::from(month).wrapping_sub(1))
+            .map_or_else(|| month.to_string(), ToString::to_string)
+    }
+
+    fn ordinal(&self, number: i32) -> String {
+        match number {
+            -1 => "last".to_string(),
+            number if number < 0 => {
+                format!("{} to last", Self::positive_ordinal(number.unsigned_abs()))
+            }
+            number => Self::positive_ordinal(number.unsigned_abs()),
+        }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [items @ .., last] => format!("{} and {last}", items.join(", ")),
+        }
+    }
+
+    fn every(&self, freq: Frequency, interval: u16) -> String {
+        let unit = match freq {
+            Frequency::Yearly => "year",
+            Frequency::Monthly => "month",
+            Frequency::Weekly => "week",
+            Frequency::Daily => "day",
+            Frequency::Hourly => "hour",
+            Frequency::Minutely => "minute",
+            Frequency::Secondly => "second",
+        };
+        if interval == 1 {
+            format!("every {unit}")
+        } else {
+            format!("every {interval} {unit}s")
+        }
+    }
+
+    fn every_weekday(&self) -> String {
+        "every weekday".to_string()
+    }
+
+    fn in_weeks(&self, weeks: &[i8]) -> String {
+        let weeks = weeks
+            .iter()
+            .map(|week| self.week(*week))
+            .collect::<Vec<_>>();
+        format!("in {}", self.list(&weeks))
+    }
+
+    fn in_months(&self, months: &[u8]) -> String {
+        let months = months
+            .iter()
+            .map(|month| self.month(*month))
+            .collect::<Vec<_>>();
+        format!("in {}", self.list(&months))
+    }
+
+    fn on_year_days(&self, year_days: &[i16]) -> String {
+        let year_days = year_days
+            .iter()
+            .map(|year_day| self.ordinal(i32::from(*year_day)))
+            .collect::<Vec<_>>();
+        format!("on the {} day of the year", self.list(&year_days))
+    }
+
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+        let weekdays = weekdays
+            .iter()
+            .map(|weekday| self.nth_weekday(*weekday))
+            .collect::<Vec<_>>();
+        let month_days = month_days
+            .iter()
+            .map(|month_day| self.month_day(*month_day))
+            .collect::<Vec<_>>();
+        match (weekdays.is_empty(), month_days.is_empty()) {
+            (false, true) => format!("on {}", self.list(&weekdays)),
+            (true, _) => format!("on {}", self.list(&month_days)),
+            (false, false) => format!("on {} {}", self.list(&weekdays), self.list(&month_days)),
+        }
+    }
+
+    fn at_times(&self, times: &[NaiveTime]) -> String {
+        let times = times
+            .iter()
+            .map(|time| {
+                if time.second() == 0 {
+                    time.format("%-H:%M").to_string()
+                } else {
+                    time.format("%-H:%M:%S").to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("at {}", self.list(&times))
+    }
+
+    fn at_hours(&self, hours: &[u8]) -> String {
+        format!("at hour {}", self.numbers(hours))
+    }
+
+    fn at_minutes(&self, minutes: &[u8]) -> String {
+        format!("at minute {}", self.numbers(minutes))
+    }
+
+    fn at_seconds(&self, seconds: &[u8]) -> String {
+        format!("at second {}", self.numbers(seconds))
+    }
+
+    fn count(&self, count: u32) -> String {
+        if count == 1 {
+            "for 1 time".to_string()
+        } else {
+            format!("for {count} times")
+        }
+    }
+
+    fn until(&self, until: &DateTime<Tz>) -> String {
+        format!(
+            "until {} {}, {}",
+            self.month(u8::try_from(until.month()).expect("range 1-12 is covered by u8")),
+            until.day(),
+            until.year()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordinals() {
+        let ordinals = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111, 112]
+            .into_iter()
+            .map(|number| English.ordinal(number))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ordinals,
+            [
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd",
+                "101st", "111th", "112th"
+            ]
+        );
+        assert_eq!(English.ordinal(-1), "last");
+        assert_eq!(English.ordinal(-2), "2nd to last");
+    }
+
+    #[test]
+    fn lists() {
+        let items = ["a", "b", "c"].map(ToString::to_string);
+        assert_eq!(English.list(&items[..1]), "a");
+        assert_eq!(English.list(&items[..2]), "a and b");
+        assert_eq!(English.list(&items), "a, b and c");
+    }
+}
@@ -0,0 +1,267 @@
+use super::Locale;
+use crate::{Frequency, NWeekday, Tz};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Weekday};
+
+/// Describes recurrence rules in French, e.g. "toutes les 2 semaines le lundi et le vendredi".
+///
+/// Requires the `locale-fr` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct French;
+
+const MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+impl French {
+    /// Prefixes `word` with the article "le", which is elided before a vowel.
+    fn with_article(word: &str) -> String {
+        if word.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            format!("l'{word}")
+        } else {
+            format!("le {word}")
+        }
+    }
+
+    fn numbers<N: ToString>(&self, numbers: &[N]) -> String {
+        self.list(&numbers.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    /// A week number, e.g. "semaine 1" or "la dernière semaine".
+    fn week(week: i8) -> String {
+        match week {
+            -1 => "la dernière semaine".to_string(),
+            -2 => "l'avant-dernière semaine".to_string(),
+            week if week < 0 => format!("la {}e dernière semaine", week.unsigned_abs()),
+            week => format!("semaine {week}"),
+        }
+    }
+
+    /// A day of the month, e.g. "le 1er", "le 15" or "le dernier jour".
+    fn month_day(&self, month_day: i8) -> String {
+        match month_day {
+            1 => "le 1er".to_string(),
+            month_day if month_day < 0 => {
+                Self::with_article(&format!("{} jour", self.ordinal(i32::from(month_day))))
+            }
+            month_day => format!("le {month_day}"),
+        }
+    }
+}
+
+impl Locale for French {
+    fn weekday(&self, weekday: Weekday) -> String {
+        match weekday {
+            Weekday::Mon => "lundi",
+            Weekday::Tue => "mardi",
+            Weekday::Wed => "mercredi",
+            Weekday::Thu => "jeudi",
+            Weekday::Fri => "vendredi",
+            Weekday::Sat => "samedi",
+            Weekday::Sun => "dimanche",
+        }
+        .to_string()
+    }
+
+    fn nth_weekday(&self, weekday: NWeekday) -> String {
+        match weekday {
+            NWeekday::Every(weekday) => Self::with_article(&self.weekday(weekday)),
+            NWeekday::Nth(number, weekday) => Self::with_article(&format!(
+                "{} {}",
+                self.ordinal(i32::from(number)),
+                self.weekday(weekday)
+            )),
+        }
+    }
+
+    fn month(&self, month: u8) -> String {
+        MONTHS
+            .get(usize::from(month).wrapping_sub(1))
+            .map_or_else(|| month.to_string(), ToString::to_string)
+    }
+
+    fn ordinal(&self, number: i32) -> String {
+        match number {
+            1 => "1er".to_string(),
+            -1 => "dernier".to_string(),
+            -2 => "avant-dernier".to_string(),
+            number if number < 0 => format!("{}e dernier", number.unsigned_abs()),
+            number => format!("{number}e"),
+        }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [items @ .., last] => format!("{} et {last}", items.join(", ")),
+        }
+    }
+
+    fn every(&self, freq: Frequency, interval: u16) -> String {
+        if interval == 1 {
+            let unit = match freq {
+                Frequency::Yearly => "année",
+                Frequency::Monthly => "mois",
+                Frequency::Weekly => "semaine",
+                Frequency::Daily => "jour",
+                Frequency::Hourly => "heure",
+                Frequency::Minutely => "minute",
+                Frequency::Secondly => "seconde",
+            };
+            format!("chaque {unit}")
+        } else {
+            let (every, unit) = match freq {
+                Frequency::Yearly => ("tous les", "ans"),
+                Frequency::Monthly => ("tous les", "mois"),
+                Frequency::Weekly => ("toutes les", "semaines"),
+                Frequency::Daily => ("tous les", "jours"),
+                Frequency::Hourly => ("toutes les", "heures"),
+                Frequency::Minutely => ("toutes les", "minutes"),
+                Frequency::Secondly => ("toutes les", "secondes"),
+            };
+            format!("{every} {interval} {unit}")
+        }
+    }
+
+    fn every_weekday(&self) -> String {
+        "chaque jour de la semaine".to_string()
+    }
+
+    fn in_weeks(&self, weeks: &[i8]) -> String {
+        let weeks = weeks
+            .iter()
+            .map(|week| Self::week(*week))
+            .collect::<Vec<_>>();
+        format!("en {}", self.list(&weeks))
+    }
+
+    fn in_months(&self, months: &[u8]) -> String {
+        let months = months
+            .iter()
+            .map(|month| self.month(*month))
+            .collect::<Vec<_>>();
+        format!("en {}", self.list(&months))
+    }
+
+    fn on_year_days(&self, year_days: &[i16]) -> String {
+        let year_days = year_days
+            .iter()
+            .map(|year_day| self.ordinal(i32::from(*year_day)))
+            .collect::<Vec<_>>();
+        Self::with_article(&format!("{} jour de l'année", self.list(&year_days)))
+    }
+
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+        let weekdays = weekdays
+            .iter()
+            .map(|weekday| self.nth_weekday(*weekday))
+            .collect::<Vec<_>>();
+        let month_days = month_days
+            .iter()
+            .map(|month_day| self.month_day(*month_day))
+            .collect::<Vec<_>>();
+        match (weekdays.is_empty(), month_days.is_empty()) {
+            (false, true) => self.list(&weekdays),
+            (true, _) => self.list(&month_days),
+            // "le vendredi 13" instead of "le vendredi le 13"
+            (false, false) => {
+                let month_days = month_days
+                    .iter()
+                    .map(|month_day| month_day.trim_start_matches("le ").to_string())
+                    .collect::<Vec<_>>();
+                format!("{} {}", self.list(&weekdays), self.list(&month_days))
+            }
+        }
+    }
+
+    fn at_times(&self, times: &[NaiveTime]) -> String {
+        let times = times
+            .iter()
+            .map(|time| {
+                if time.second() == 0 {
+                    time.format("%-Hh%M").to_string()
+                } else {
+                    time.format("%-Hh%M:%S").to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("à {}", self.list(&times))
+    }
+
+    fn at_hours(&self, hours: &[u8]) -> String {
+        format!("à l'heure {}", self.numbers(hours))
+    }
+
+    fn at_minutes(&self, minutes: &[u8]) -> String {
+        format!("à la minute {}", self.numbers(minutes))
+    }
+
+    fn at_seconds(&self, seconds: &[u8]) -> String {
+        format!("à la seconde {}", self.numbers(seconds))
+    }
+
+    fn count(&self, count: u32) -> String {
+        format!("pour {count} fois")
+    }
+
+    fn until(&self, until: &DateTime<Tz>) -> String {
+        let day = if until.day() == 1 {
+            "1er".to_string()
+        } else {
+            until.day().to_string()
+        };
+        format!(
+            "jusqu'au {day} {} {}",
+            self.month(u8::try_from(until.month()).expect("range 1-12 is covered by u8")),
+            until.year()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RRule, Unvalidated};
+
+    fn text(rrule: &str) -> String {
+        rrule
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .to_text_with(&French)
+    }
+
+    #[test]
+    fn describes_rules() {
+        assert_eq!(
+            text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z"),
+            "toutes les 2 semaines le lundi et le vendredi jusqu'au 3 mars 2025"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=1MO,-1FR,-2SU"),
+            "chaque mois le 1er lundi, le dernier vendredi et l'avant-dernier dimanche"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13;COUNT=3"),
+            "chaque mois le vendredi 13 pour 3 fois"
+        );
+        assert_eq!(
+            text("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;UNTIL=20300101T000000Z"),
+            "chaque année en novembre le 4e jeudi jusqu'au 1er janvier 2030"
+        );
+        assert_eq!(
+            text("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30"),
+            "chaque jour de la semaine à 9h30"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use super::Locale;
+use crate::{Frequency, NWeekday, Tz};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Weekday};
+
+/// Describes recurrence rules in German, e.g. "alle 2 Wochen am Montag und Freitag".
+///
+/// Requires the `locale-de` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct German;
+
+const MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+impl German {
+    fn numbers<N: ToString>(&self, numbers: &[N]) -> String {
+        self.list(&numbers.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    /// A week number, e.g. "Woche 1" or "der letzten Woche".
+    fn week(&self, week: i8) -> String {
+        if week < 0 {
+            format!("der {} Woche", self.ordinal(i32::from(week)))
+        } else {
+            format!("Woche {week}")
+        }
+    }
+
+    /// A day of the month, e.g. "1." or "letzten Tag".
+    fn month_day(&self, month_day: i8) -> String {
+        if month_day < 0 {
+            format!("{} Tag", self.ordinal(i32::from(month_day)))
+        } else {
+            self.ordinal(i32::from(month_day))
+        }
+    }
+}
+
+impl Locale for German {
+    fn weekday(&self, weekday: Weekday) -> String {
+        match weekday {
+            Weekday::Mon => "Montag",
+            Weekday::Tue => "Dienstag",
+            Weekday::Wed => "Mittwoch",
+            Weekday::Thu => "Donnerstag",
+            Weekday::Fri => "Freitag",
+            Weekday::Sat => "Samstag",
+            Weekday::Sun => "Sonntag",
+        }
+        .to_string()
+    }
+
+    fn nth_weekday(&self, weekday: NWeekday) -> String {
+        match weekday {
+            NWeekday::Every(weekday) => self.weekday(weekday),
+            NWeekday::Nth(number, weekday) => format!(
+                "{} {}",
+                self.ordinal(i32::from(number)),
+                self.weekday(weekday)
+            ),
+        }
+    }
+
+    fn month(&self, month: u8) -> String {
+        MONTHS
+            .get(usize::from(month).wrapping_sub(1))
+            .map_or_else(|| month.to_string(), ToString::to_string)
+    }
+
+    fn ordinal(&self, number: i32) -> String {
+        // The ordinals are always preceded by "am" or "dem", hence the dative form.
+        match number {
+            -1 => "letzten".to_string(),
+            -2 => "vorletzten".to_string(),
+            number if number < 0 => format!("{}. letzten", number.unsigned_abs()),
+            number => format!("{number}."),
+        }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [items @ .., last] => format!("{} und {last}", items.join(", ")),
+        }
+    }
+
+    fn every(&self, freq: Frequency, interval: u16) -> String {
+        if interval == 1 {
+            match freq {
+                Frequency::Yearly => "jedes Jahr",
+                Frequency::Monthly => "jeden Monat",
+                Frequency::Weekly => "jede Woche",
+                Frequency::Daily => "jeden Tag",
+                Frequency::Hourly => "jede Stunde",
+                Frequency::Minutely => "jede Minute",
+                Frequency::Secondly => "jede Sekunde",
+            }
+            .to_string()
+        } else {
+            let unit = match freq {
+                Frequency::Yearly => "Jahre",
+                Frequency::Monthly => "Monate",
+                Frequency::Weekly => "Wochen",
+                Frequency::Daily => "Tage",
+                Frequency::Hourly => "Stunden",
+                Frequency::Minutely => "Minuten",
+                Frequency::Secondly => "Sekunden",
+            };
+            format!("alle {interval} {unit}")
+        }
+    }
+
+    fn every_weekday(&self) -> String {
+        "jeden Werktag".to_string()
+    }
+
+    fn in_weeks(&self, weeks: &[i8]) -> String {
+        let weeks = weeks
+            .iter()
+            .map(|week| self.week(*week))
+            .collect::<Vec<_>>();
+        format!("in {}", self.list(&weeks))
+    }
+
+    fn in_months(&self, months: &[u8]) -> String {
+        let months = months
+            .iter()
+            .map(|month| self.month(*month))
+            .collect::<Vec<_>>();
+        format!("im {}", self.list(&months))
+    }
+
+    fn on_year_days(&self, year_days: &[i16]) -> String {
+        let year_days = year_days
+            .iter()
+            .map(|year_day| self.ordinal(i32::from(*year_day)))
+            .collect::<Vec<_>>();
+        format!("am {} Tag des Jahres", self.list(&year_days))
+    }
+
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+        let weekdays = weekdays
+            .iter()
+            .map(|weekday| self.nth_weekday(*weekday))
+            .collect::<Vec<_>>();
+        let month_days = month_days
+            .iter()
+            .map(|month_day| self.month_day(*month_day))
+            .collect::<Vec<_>>();
+        match (weekdays.is_empty(), month_days.is_empty()) {
+            (false, true) => format!("am {}", self.list(&weekdays)),
+            (true, _) => format!("am {}", self.list(&month_days)),
+            (false, false) => format!(
+                "am {}, dem {}",
+                self.list(&weekdays),
+                self.list(&month_days)
+            ),
+        }
+    }
+
+    fn at_times(&self, times: &[NaiveTime]) -> String {
+        let times = times
+            .iter()
+            .map(|time| {
+                if time.second() == 0 {
+                    time.format("%-H:%M").to_string()
+                } else {
+                    time.format("%-H:%M:%S").to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("um {} Uhr", self.list(&times))
+    }
+
+    fn at_hours(&self, hours: &[u8]) -> String {
+        format!("zur Stunde {}", self.numbers(hours))
+    }
+
+    fn at_minutes(&self, minutes: &[u8]) -> String {
+        format!("zur Minute {}", self.numbers(minutes))
+    }
+
+    fn at_seconds(&self, seconds: &[u8]) -> String {
+        format!("zur Sekunde {}", self.numbers(seconds))
+    }
+
+    fn count(&self, count: u32) -> String {
+        format!("insgesamt {count} Mal")
+    }
+
+    fn until(&self, until: &DateTime<Tz>) -> String {
+        format!(
+            "bis zum {}. {} {}",
+            until.day(),
+            self.month(u8::try_from(until.month()).expect("range 1-12 is covered by u8")),
+            until.year()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RRule, Unvalidated};
+
+    fn text(rrule: &str) -> String {
+        rrule
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .to_text_with(&German)
+    }
+
+    #[test]
+    fn describes_rules() {
+        assert_eq!(
+            text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z"),
+            "alle 2 Wochen am Montag und Freitag bis zum 3. März 2025"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=1MO,-1FR,-2SU"),
+            "jeden Monat am 1. Montag, letzten Freitag und vorletzten Sonntag"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13;COUNT=3"),
+            "jeden Monat am Freitag, dem 13. insgesamt 3 Mal"
+        );
+        assert_eq!(
+            text("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"),
+            "jedes Jahr im November am 4. Donnerstag"
+        );
+        assert_eq!(
+            text("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30"),
+            "jeden Werktag um 9:30 Uhr"
+        );
+    }
+}
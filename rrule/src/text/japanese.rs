@@ -0,0 +1,258 @@
+use super::{Locale, TextPart};
+use crate::{Frequency, NWeekday, Tz};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Weekday};
+use std::fmt::Display;
+
+/// Describes recurrence rules in Japanese, e.g. "2週間ごとの月曜日と金曜日".
+///
+/// The end of the recurrence is put in front of the description.
+///
+/// Requires the `locale-ja` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Japanese;
+
+impl Japanese {
+    fn numbers<N: Display>(&self, numbers: &[N], unit: &str) -> String {
+        self.list(
+            &numbers
+                .iter()
+                .map(|number| format!("{number}{unit}"))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Whether the phrase is a part of the date, which are connected by "の".
+    fn is_date_part(part: TextPart) -> bool {
+        matches!(
+            part,
+            TextPart::Weeks | TextPart::Months | TextPart::YearDays | TextPart::Days
+        )
+    }
+
+    /// A day of the month or year, e.g. "13日" or "最終日".
+    fn day(&self, day: i32, positive_unit: &str) -> String {
+        if day < 0 {
+            format!("{}日", self.ordinal(day))
+        } else {
+            format!("{day}{positive_unit}")
+        }
+    }
+}
+
+impl Locale for Japanese {
+    fn weekday(&self, weekday: Weekday) -> String {
+        match weekday {
+            Weekday::Mon => "月曜日",
+            Weekday::Tue => "火曜日",
+            Weekday::Wed => "水曜日",
+            Weekday::Thu => "木曜日",
+            Weekday::Fri => "金曜日",
+            Weekday::Sat => "土曜日",
+            Weekday::Sun => "日曜日",
+        }
+        .to_string()
+    }
+
+    fn nth_weekday(&self, weekday: NWeekday) -> String {
+        match weekday {
+            NWeekday::Every(weekday) => self.weekday(weekday),
+            NWeekday::Nth(number, weekday) => format!(
+                "{}{}",
+                self.ordinal(i32::from(number)),
+                self.weekday(weekday)
+            ),
+        }
+    }
+
+    fn month(&self, month: u8) -> String {
+        format!("{month}月")
+    }
+
+    fn ordinal(&self, number: i32) -> String {
+        match number {
+            -1 => "最終".to_string(),
+            number if number < 0 => format!("最後から{}番目の", number.unsigned_abs()),
+            number => format!("第{number}"),
+        }
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [items @ .., last] => format!("{}と{last}", items.join("、")),
+        }
+    }
+
+    fn every(&self, freq: Frequency, interval: u16) -> String {
+        if interval == 1 {
+            match freq {
+                Frequency::Yearly => "毎年",
+                Frequency::Monthly => "毎月",
+                Frequency::Weekly => "毎週",
+                Frequency::Daily => "毎日",
+                Frequency::Hourly => "毎時",
+                Frequency::Minutely => "毎分",
+                Frequency::Secondly => "毎秒",
+            }
+            .to_string()
+        } else {
+            let unit = match freq {
+                Frequency::Yearly => "年",
+                Frequency::Monthly => "か月",
+                Frequency::Weekly => "週間",
+                Frequency::Daily => "日",
+                Frequency::Hourly => "時間",
+                Frequency::Minutely => "分",
+                Frequency::Secondly => "秒",
+            };
+            format!("{interval}{unit}ごと")
+        }
+    }
+
+    fn every_weekday(&self) -> String {
+        "毎週平日".to_string()
+    }
+
+    fn in_weeks(&self, weeks: &[i8]) -> String {
+        let weeks = weeks
+            .iter()
+            .map(|week| format!("{}週", self.ordinal(i32::from(*week))))
+            .collect::<Vec<_>>();
+        self.list(&weeks)
+    }
+
+    fn in_months(&self, months: &[u8]) -> String {
+        let months = months
+            .iter()
+            .map(|month| self.month(*month))
+            .collect::<Vec<_>>();
+        self.list(&months)
+    }
+
+    fn on_year_days(&self, year_days: &[i16]) -> String {
+        let year_days = year_days
+            .iter()
+            .map(|year_day| self.day(i32::from(*year_day), "日目"))
+            .collect::<Vec<_>>();
+        self.list(&year_days)
+    }
+
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+        let weekdays = weekdays
+            .iter()
+            .map(|weekday| self.nth_weekday(*weekday))
+            .collect::<Vec<_>>();
+        let month_days = month_days
+            .iter()
+            .map(|month_day| self.day(i32::from(*month_day), "日"))
+            .collect::<Vec<_>>();
+        match (weekdays.is_empty(), month_days.is_empty()) {
+            (false, true) => self.list(&weekdays),
+            (true, _) => self.list(&month_days),
+            (false, false) => format!("{}の{}", self.list(&month_days), self.list(&weekdays)),
+        }
+    }
+
+    fn at_times(&self, times: &[NaiveTime]) -> String {
+        let times = times
+            .iter()
+            .map(|time| {
+                if time.second() == 0 {
+                    time.format("%-H:%M").to_string()
+                } else {
+                    time.format("%-H:%M:%S").to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        self.list(&times)
+    }
+
+    fn at_hours(&self, hours: &[u8]) -> String {
+        self.numbers(hours, "時")
+    }
+
+    fn at_minutes(&self, minutes: &[u8]) -> String {
+        self.numbers(minutes, "分")
+    }
+
+    fn at_seconds(&self, seconds: &[u8]) -> String {
+        self.numbers(seconds, "秒")
+    }
+
+    fn count(&self, count: u32) -> String {
+        format!("計{count}回")
+    }
+
+    fn until(&self, until: &DateTime<Tz>) -> String {
+        format!("{}年{}月{}日まで", until.year(), until.month(), until.day())
+    }
+
+    fn join(&self, phrases: &[(TextPart, String)]) -> String {
+        let mut text = String::new();
+        if let Some((_, until)) = phrases.iter().find(|(part, _)| *part == TextPart::Until) {
+            text.push_str(until);
+            text.push('、');
+        }
+
+        let mut previous = None;
+        for (part, phrase) in phrases.iter().filter(|(part, _)| *part != TextPart::Until) {
+            match (previous, *part) {
+                // "2週間ごとの月曜日", but "毎週月曜日"
+                (Some(TextPart::Every), part)
+                    if Self::is_date_part(part) && text.ends_with("ごと") =>
+                {
+                    text.push('の');
+                }
+                (Some(previous), part)
+                    if Self::is_date_part(previous) && Self::is_date_part(part) =>
+                {
+                    text.push('の');
+                }
+                (Some(_), TextPart::Count) => text.push('、'),
+                _ => {}
+            }
+            text.push_str(phrase);
+            previous = Some(*part);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RRule, Unvalidated};
+
+    fn text(rrule: &str) -> String {
+        rrule
+            .parse::<RRule<Unvalidated>>()
+            .unwrap()
+            .to_text_with(&Japanese)
+    }
+
+    #[test]
+    fn describes_rules() {
+        assert_eq!(
+            text("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20250303T000000Z"),
+            "2025年3月3日まで、2週間ごとの月曜日と金曜日"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=1MO,-1FR,-2SU"),
+            "毎月第1月曜日、最終金曜日と最後から2番目の日曜日"
+        );
+        assert_eq!(
+            text("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13;COUNT=3"),
+            "毎月13日の金曜日、計3回"
+        );
+        assert_eq!(
+            text("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH"),
+            "毎年11月の第4木曜日"
+        );
+        assert_eq!(
+            text("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30"),
+            "毎週平日9:30"
+        );
+        assert_eq!(text("FREQ=HOURLY;BYMINUTE=15,45"), "毎時15分と45分");
+    }
+}
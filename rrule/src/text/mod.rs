@@ -0,0 +1,346 @@
+//! Human-readable descriptions of recurrence rules, like "every 2 weeks on Monday and Friday".
+#[cfg(feature = "locale-nl")]
+mod dutch;
+mod english;
+#[cfg(feature = "locale-fr")]
+mod french;
+#[cfg(feature = "locale-de")]
+mod german;
+#[cfg(feature = "locale-ja")]
+mod japanese;
+mod parse;
+
+#[cfg(feature = "locale-nl")]
+pub use dutch::Dutch;
+pub use english::English;
+#[cfg(feature = "locale-fr")]
+pub use french::French;
+#[cfg(feature = "locale-de")]
+pub use german::German;
+#[cfg(feature = "locale-ja")]
+pub use japanese::Japanese;
+pub(crate) use parse::rrule_from_text;
+
+use crate::{Frequency, NWeekday, RRule, RRuleSet, RScale, Skip, Tz};
+use chrono::{DateTime, NaiveTime, Weekday};
+
+/// The kind of a phrase in the description of a recurrence rule.
+///
+/// The phrases are passed to [`Locale::join`] in the order of the variants,
+/// so a locale can reorder them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextPart {
+    /// The frequency with its interval, see [`Locale::every`] and [`Locale::every_weekday`].
+    Every,
+    /// The week numbers, see [`Locale::in_weeks`].
+    Weeks,
+    /// The months, see [`Locale::in_months`].
+    Months,
+    /// The days of the year, see [`Locale::on_year_days`].
+    YearDays,
+    /// The weekdays and days of the month, see [`Locale::on_days`].
+    Days,
+    /// The times of the day, or the hours, minutes and seconds, see [`Locale::at_times`].
+    Times,
+    /// The number of occurrences, see [`Locale::count`].
+    Count,
+    /// The end of the recurrence, see [`Locale::until`].
+    Until,
+}
+
+/// The words and word order used to describe a recurrence rule.
+///
+/// Every method returns one phrase of the description, the phrases are joined by [`Locale::join`].
+/// [`English`] is always available, other locales are enabled by the `locale-de`, `locale-fr`,
+/// `locale-nl` and `locale-ja` features.
+///
+/// # Usage
+///
+/// A locale can be based on an existing one and only change some of the phrases:
+///
+/// ```
+/// use rrule::{English, Locale, NWeekday, RRule, Unvalidated, Weekday};
+/// # use rrule::{Frequency, Tz};
+/// # use chrono::{DateTime, NaiveTime};
+///
+/// struct Short;
+///
+/// impl Locale for Short {
+///     fn weekday(&self, weekday: Weekday) -> String {
+///         English.weekday(weekday)[..3].to_string()
+///     }
+///     fn nth_weekday(&self, weekday: NWeekday) -> String {
+///         match weekday {
+///             NWeekday::Every(weekday) => self.weekday(weekday),
+///             NWeekday::Nth(n, weekday) => format!("the {} {}", self.ordinal(n.into()), self.weekday(weekday)),
+///         }
+///     }
+///     fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String {
+///         let weekdays = weekdays.iter().map(|weekday| self.nth_weekday(*weekday)).collect::<Vec<_>>();
+///         format!("on {}", self.list(&weekdays))
+///     }
+///     // The other phrases are the English ones.
+/// #   fn month(&self, month: u8) -> String { English.month(month) }
+/// #   fn ordinal(&self, number: i32) -> String { English.ordinal(number) }
+/// #   fn list(&self, items: &[String]) -> String { English.list(items) }
+/// #   fn every(&self, freq: Frequency, interval: u16) -> String { English.every(freq, interval) }
+/// #   fn every_weekday(&self) -> String { English.every_weekday() }
+/// #   fn in_weeks(&self, weeks: &[i8]) -> String { English.in_weeks(weeks) }
+/// #   fn in_months(&self, months: &[u8]) -> String { English.in_months(months) }
+/// #   fn on_year_days(&self, year_days: &[i16]) -> String { English.on_year_days(year_days) }
+/// #   fn at_times(&self, times: &[NaiveTime]) -> String { English.at_times(times) }
+/// #   fn at_hours(&self, hours: &[u8]) -> String { English.at_hours(hours) }
+/// #   fn at_minutes(&self, minutes: &[u8]) -> String { English.at_minutes(minutes) }
+/// #   fn at_seconds(&self, seconds: &[u8]) -> String { English.at_seconds(seconds) }
+/// #   fn count(&self, count: u32) -> String { English.count(count) }
+/// #   fn until(&self, until: &DateTime<Tz>) -> String { English.until(until) }
+/// }
+///
+/// let rrule: RRule<Unvalidated> = "FREQ=MONTHLY;BYDAY=1MO,-1FR".parse().unwrap();
+/// assert_eq!(rrule.to_text_with(&Short), "every month on the 1st Mon and the last Fri");
+/// ```
+pub trait Locale {
+    /// The name of a weekday, e.g. "Monday".
+    fn weekday(&self, weekday: Weekday) -> String;
+
+    /// A weekday which is possibly numbered, e.g. "Monday" or "the last Friday".
+    fn nth_weekday(&self, weekday: NWeekday) -> String;
+
+    /// The name of a month from 1 to 12, e.g. "January".
+    fn month(&self, month: u8) -> String;
+
+    /// An ordinal number, negative numbers count from the end, e.g. "1st" or "2nd to last".
+    fn ordinal(&self, number: i32) -> String;
+
+    /// Joins the items of an enumeration, e.g. "Monday, Tuesday and Friday".
+    fn list(&self, items: &[String]) -> String;
+
+    /// The frequency with its interval, e.g. "every 2 weeks".
+    fn every(&self, freq: Frequency, interval: u16) -> String;
+
+    /// The frequency of a daily or weekly rule on Monday to Friday, e.g. "every weekday".
+    fn every_weekday(&self) -> String;
+
+    /// The week numbers, e.g. "in week 1 and week 52".
+    fn in_weeks(&self, weeks: &[i8]) -> String;
+
+    /// The months from 1 to 12, e.g. "in January and March".
+    fn in_months(&self, months: &[u8]) -> String;
+
+    /// The days of the year, e.g. "on the 1st and 100th day of the year".
+    fn on_year_days(&self, year_days: &[i16]) -> String;
+
+    /// The weekdays and days of the month, either of them can be empty,
+    /// e.g. "on Monday and Friday", "on the 1st and the 15th" or "on Friday the 13th".
+    fn on_days(&self, weekdays: &[NWeekday], month_days: &[i8]) -> String;
+
+    /// The times of the day, e.g. "at 9:00 and 17:30".
+    fn at_times(&self, times: &[NaiveTime]) -> String;
+
+    /// The hours of a rule which can't be described by times of the day, e.g. "at hour 9 and 17".
+    fn at_hours(&self, hours: &[u8]) -> String;
+
+    /// The minutes of a rule which can't be described by times of the day, e.g. "at minute 30".
+    fn at_minutes(&self, minutes: &[u8]) -> String;
+
+    /// The seconds of a rule which can't be described by times of the day, e.g. "at second 15".
+    fn at_seconds(&self, seconds: &[u8]) -> String;
+
+    /// The number of occurrences, e.g. "for 5 times".
+    fn count(&self, count: u32) -> String;
+
+    /// The end of the recurrence, e.g. "until March 3, 2025".
+    fn until(&self, until: &DateTime<Tz>) -> String;
+
+    /// Joins the phrases of a description, which are given in the order of [`TextPart`].
+    ///
+    /// By default the phrases are separated by spaces.
+    fn join(&self, phrases: &[(TextPart, String)]) -> String {
+        phrases
+            .iter()
+            .map(|(_, phrase)| phrase.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+const WEEKDAYS_MON_TO_FRI: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// Describes `rrule` with the words of `locale`.
+///
+/// The parts which can't be described are left out, see [`unsupported_parts`].
+pub(crate) fn rrule_to_text<S>(rrule: &RRule<S>, locale: &dyn Locale) -> String {
+    let mut phrases = vec![];
+
+    let month_days = rrule
+        .by_month_day
+        .iter()
+        .chain(&rrule.by_n_month_day)
+        .copied()
+        .collect::<Vec<_>>();
+    let mut weekdays = rrule.by_weekday.as_slice();
+
+    let is_every_weekday = matches!(rrule.freq, Frequency::Daily | Frequency::Weekly)
+        && rrule.interval == 1
+        && month_days.is_empty()
+        && weekdays.len() == WEEKDAYS_MON_TO_FRI.len()
+        && WEEKDAYS_MON_TO_FRI
+            .iter()
+            .all(|weekday| weekdays.contains(&NWeekday::Every(*weekday)));
+    if is_every_weekday {
+        phrases.push((TextPart::Every, locale.every_weekday()));
+        weekdays = &[];
+    } else {
+        phrases.push((TextPart::Every, locale.every(rrule.freq, rrule.interval)));
+    }
+
+    if !rrule.by_week_no.is_empty() {
+        phrases.push((TextPart::Weeks, locale.in_weeks(&rrule.by_week_no)));
+    }
+    if !rrule.by_month.is_empty() {
+        phrases.push((TextPart::Months, locale.in_months(&rrule.by_month)));
+    }
+    if !rrule.by_year_day.is_empty() {
+        phrases.push((TextPart::YearDays, locale.on_year_days(&rrule.by_year_day)));
+    }
+    if !weekdays.is_empty() || !month_days.is_empty() {
+        phrases.push((TextPart::Days, locale.on_days(weekdays, &month_days)));
+    }
+    phrases.extend(
+        time_phrases(rrule, locale)
+            .into_iter()
+            .map(|phrase| (TextPart::Times, phrase)),
+    );
+
+    if let Some(count) = rrule.count {
+        phrases.push((TextPart::Count, locale.count(count)));
+    }
+    if let Some(until) = &rrule.until {
+        phrases.push((TextPart::Until, locale.until(until)));
+    }
+
+    locale.join(&phrases)
+}
+
+/// Describes the times of the day, or the hours, minutes and seconds if they don't make up
+/// complete times.
+fn time_phrases<S>(rrule: &RRule<S>, locale: &dyn Locale) -> Vec<String> {
+    if rrule.freq <= Frequency::Daily && !rrule.by_hour.is_empty() && !rrule.by_minute.is_empty() {
+        let seconds = if rrule.by_second.is_empty() {
+            vec![0]
+        } else {
+            rrule.by_second.clone()
+        };
+        let mut times = rrule
+            .by_hour
+            .iter()
+            .flat_map(|hour| {
+                rrule.by_minute.iter().flat_map({
+                    let seconds = &seconds;
+                    move |minute| {
+                        seconds.iter().filter_map(move |second| {
+                            NaiveTime::from_hms_opt(
+                                u32::from(*hour),
+                                u32::from(*minute),
+                                u32::from(*second),
+                            )
+                        })
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+        return vec![locale.at_times(&times)];
+    }
+
+    let mut phrases = vec![];
+    if !rrule.by_hour.is_empty() {
+        phrases.push(locale.at_hours(&rrule.by_hour));
+    }
+    if !rrule.by_minute.is_empty() {
+        phrases.push(locale.at_minutes(&rrule.by_minute));
+    }
+    // The seconds are left out if they are on the full minute.
+    if !rrule.by_second.is_empty() && rrule.by_second != [0] {
+        phrases.push(locale.at_seconds(&rrule.by_second));
+    }
+    phrases
+}
+
+/// Returns the parts of `rrule` which can't be described by [`rrule_to_text`],
+/// in the iCalendar format, e.g. `BYSETPOS=-1`.
+pub(crate) fn unsupported_parts<S>(rrule: &RRule<S>) -> Vec<String> {
+    let mut parts = vec![];
+    if !rrule.by_set_pos.is_empty() {
+        let by_set_pos = rrule
+            .by_set_pos
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        parts.push(format!("BYSETPOS={}", by_set_pos.join(",")));
+    }
+    if let Some(by_easter) = rrule.by_easter {
+        parts.push(format!("BYEASTER={by_easter}"));
+    }
+    // The week start only matters if weeks are skipped or numbered.
+    let uses_week_start =
+        (rrule.freq == Frequency::Weekly && rrule.interval > 1) || !rrule.by_week_no.is_empty();
+    if rrule.week_start != Weekday::Mon && uses_week_start {
+        parts.push(format!("WKST={}", NWeekday::Every(rrule.week_start)));
+    }
+    // The months and days would be described as Gregorian ones.
+    if let Some(rscale) = rrule.rscale.filter(|rscale| *rscale != RScale::Gregorian) {
+        parts.push(format!("RSCALE={rscale}"));
+    }
+    if rrule.skip != Skip::Omit {
+        parts.push(format!("SKIP={}", rrule.skip));
+    }
+    parts
+}
+
+/// Describes all the rules of `rrule_set` with the words of `locale`.
+pub(crate) fn rrule_set_to_text(rrule_set: &RRuleSet, locale: &dyn Locale) -> String {
+    let rrules = rrule_set
+        .rrule
+        .iter()
+        .map(|rrule| rrule_to_text(rrule, locale))
+        .collect::<Vec<_>>();
+    locale.list(&rrules)
+}
+
+/// Returns the parts of `rrule_set` which can't be described by [`rrule_set_to_text`].
+pub(crate) fn rrule_set_unsupported_parts(rrule_set: &RRuleSet) -> Vec<String> {
+    let mut parts = rrule_set
+        .rrule
+        .iter()
+        .flat_map(unsupported_parts)
+        .collect::<Vec<_>>();
+    let properties = [
+        ("EXRULE", rrule_set.exrule.is_empty()),
+        (
+            "RDATE",
+            rrule_set.rdate.is_empty()
+                && rrule_set.rdate_date.is_empty()
+                && rrule_set.rdate_period.is_empty(),
+        ),
+        (
+            "EXDATE",
+            rrule_set.exdate.is_empty() && rrule_set.exdate_date.is_empty(),
+        ),
+        ("RECURRENCE-ID", rrule_set.overrides.is_empty()),
+    ];
+    parts.extend(
+        properties
+            .into_iter()
+            .filter(|(_, is_empty)| !is_empty)
+            .map(|(property, _)| property.to_string()),
+    );
+    parts
+}
@@ -0,0 +1,674 @@
+//! Parses English descriptions of recurrence rules, like "every other Tuesday at 9am until June".
+//!
+//! Everything [`English`](super::English) renders is understood, as well as some common
+//! shorthands like "daily", "every other week", "at 9:30pm" or "twice".
+use crate::{Frequency, NWeekday, ParseError, RRule, Tz, Unvalidated};
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveTime, TimeZone, Timelike, Weekday};
+
+/// Parses `text` into a rule, dates without a year are the first ones on or after `reference`.
+pub(crate) fn rrule_from_text(
+    text: &str,
+    reference: DateTime<Tz>,
+) -> Result<RRule<Unvalidated>, ParseError> {
+    let mut parser = TextParser::new(text, reference);
+    let mut rrule = parser.frequency()?;
+    parser.clauses(&mut rrule)?;
+
+    rrule.by_month.sort_unstable();
+    rrule.by_month.dedup();
+    rrule.by_month_day.sort_unstable();
+    rrule.by_month_day.dedup();
+    rrule.by_year_day.sort_unstable();
+    rrule.by_year_day.dedup();
+    rrule.by_week_no.sort_unstable();
+    rrule.by_week_no.dedup();
+    rrule.by_weekday.sort_unstable();
+    rrule.by_weekday.dedup();
+    rrule.by_hour.sort_unstable();
+    rrule.by_hour.dedup();
+    rrule.by_minute.sort_unstable();
+    rrule.by_minute.dedup();
+    rrule.by_second.sort_unstable();
+    rrule.by_second.dedup();
+    Ok(rrule)
+}
+
+/// A day given in an "on" clause, before it is known whether the plain ordinals are days of
+/// the month or of the year.
+enum Day {
+    Weekday(NWeekday),
+    OfMonth(i32),
+    Ordinal(i32),
+}
+
+struct TextParser {
+    tokens: Vec<String>,
+    position: usize,
+    reference: DateTime<Tz>,
+}
+
+impl TextParser {
+    fn new(text: &str, reference: DateTime<Tz>) -> Self {
+        let tokens = text
+            .to_lowercase()
+            .replace(',', " , ")
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect();
+        Self {
+            tokens,
+            position: 0,
+            reference,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&str> {
+        self.tokens.get(self.position + offset).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.position).cloned();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// Consumes the next token if it is one of `words`.
+    fn eat(&mut self, words: &[&str]) -> bool {
+        if self.peek().is_some_and(|token| words.contains(&token)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the separators of an enumeration, i.e. "," and "and".
+    fn eat_separators(&mut self) {
+        while self.eat(&[",", "and"]) {}
+    }
+
+    /// The text from the current token on, used in error messages.
+    fn rest(&self) -> String {
+        self.tokens[self.position.min(self.tokens.len())..]
+            .join(" ")
+            .replace(" ,", ",")
+    }
+
+    fn unrecognized(&self) -> ParseError {
+        ParseError::UnrecognizedText(self.rest())
+    }
+
+    /// Parses the frequency at the start of the text, e.g. "every 2 weeks", "every Tuesday"
+    /// or "daily".
+    fn frequency(&mut self) -> Result<RRule<Unvalidated>, ParseError> {
+        let freq = match self.peek() {
+            Some("yearly" | "annually") => Some(Frequency::Yearly),
+            Some("monthly") => Some(Frequency::Monthly),
+            Some("weekly") => Some(Frequency::Weekly),
+            Some("daily") => Some(Frequency::Daily),
+            Some("hourly") => Some(Frequency::Hourly),
+            _ => None,
+        };
+        if let Some(freq) = freq {
+            self.position += 1;
+            return Ok(RRule::new(freq));
+        }
+
+        if !self.eat(&["every", "each"]) {
+            return Err(ParseError::MissingTextFrequency(self.rest()));
+        }
+        let interval = if self.eat(&["other"]) {
+            2
+        } else if let Some(number) = self.peek().and_then(number) {
+            self.position += 1;
+            u16::try_from(number).map_err(|_| ParseError::InvalidTextNumber(number.to_string()))?
+        } else {
+            1
+        };
+
+        let Some(token) = self.peek() else {
+            return Err(ParseError::MissingTextFrequency(self.rest()));
+        };
+        let rrule = if let Some(freq) = frequency_unit(token) {
+            self.position += 1;
+            RRule::new(freq)
+        } else if matches!(token, "weekday" | "weekdays") {
+            self.position += 1;
+            RRule::new(Frequency::Weekly).by_weekday(
+                [
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                ]
+                .map(NWeekday::Every)
+                .to_vec(),
+            )
+        } else if matches!(token, "weekend" | "weekends") {
+            self.position += 1;
+            RRule::new(Frequency::Weekly).by_weekday(vec![
+                NWeekday::Every(Weekday::Sat),
+                NWeekday::Every(Weekday::Sun),
+            ])
+        } else if weekday(token).is_some() {
+            // "every Monday and Friday"
+            let mut weekdays = vec![];
+            while let Some(day) = self.list_item(|parser| parser.peek().and_then(weekday)) {
+                weekdays.push(NWeekday::Every(day));
+            }
+            RRule::new(Frequency::Weekly).by_weekday(weekdays)
+        } else if month(token).is_some() {
+            // "every January and July"
+            let mut rrule = RRule::new(Frequency::Yearly);
+            while let Some(month) = self.list_item(|parser| parser.peek().and_then(month)) {
+                rrule.by_month.push(month);
+            }
+            rrule
+        } else {
+            return Err(ParseError::MissingTextFrequency(self.rest()));
+        };
+        Ok(rrule.interval(interval))
+    }
+
+    /// Parses the next item of an enumeration with `item`, which only inspects the next token.
+    ///
+    /// The separators are only consumed if they are followed by an item.
+    fn list_item<T>(&mut self, item: impl Fn(&Self) -> Option<T>) -> Option<T> {
+        let start = self.position;
+        if start > 0 {
+            self.eat_separators();
+        }
+        if let Some(value) = item(self) {
+            self.position += 1;
+            Some(value)
+        } else {
+            self.position = start;
+            None
+        }
+    }
+
+    /// Parses the clauses which follow the frequency.
+    fn clauses(&mut self, rrule: &mut RRule<Unvalidated>) -> Result<(), ParseError> {
+        while let Some(token) = self.peek() {
+            match token {
+                "," | "and" => self.position += 1,
+                "in" => {
+                    self.position += 1;
+                    self.in_clause(rrule)?;
+                }
+                "on" => {
+                    self.position += 1;
+                    self.on_clause(rrule)?;
+                }
+                "at" => {
+                    self.position += 1;
+                    self.at_clause(rrule)?;
+                }
+                "until" | "till" => {
+                    self.position += 1;
+                    rrule.until = Some(self.date()?);
+                }
+                "for" | "once" | "twice" => rrule.count = Some(self.count()?),
+                token if number(token).is_some() && self.peek_at(1) == Some("times") => {
+                    rrule.count = Some(self.count()?);
+                }
+                _ => return Err(self.unrecognized()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the weeks and months after "in", e.g. "in week 1 and the last week" or
+    /// "in January and March".
+    fn in_clause(&mut self, rrule: &mut RRule<Unvalidated>) -> Result<(), ParseError> {
+        let start = self.position;
+        let mut after_week = false;
+        loop {
+            let before_item = self.position;
+            self.eat_separators();
+            self.eat(&["the"]);
+            let Some(token) = self.peek().map(ToString::to_string) else {
+                self.position = before_item;
+                break;
+            };
+            if let Some(month) = month(&token) {
+                self.position += 1;
+                rrule.by_month.push(month);
+                after_week = false;
+            } else if token == "week" {
+                self.position += 1;
+                let week = self.next().as_deref().and_then(number).ok_or_else(|| {
+                    ParseError::UnrecognizedText(self.tokens[before_item..].join(" "))
+                })?;
+                rrule.by_week_no.push(week_number(week)?);
+                after_week = true;
+            } else if let Some(ordinal) = self.ordinal() {
+                if !self.eat(&["week"]) {
+                    self.position = before_item;
+                    return Err(self.unrecognized());
+                }
+                rrule.by_week_no.push(week_number(ordinal)?);
+                after_week = true;
+            } else if let Some(week) = number(&token).filter(|_| after_week) {
+                // "in week 1 and 52"
+                self.position += 1;
+                rrule.by_week_no.push(week_number(week)?);
+            } else {
+                self.position = before_item;
+                break;
+            }
+        }
+        if self.position == start {
+            return Err(self.unrecognized());
+        }
+        Ok(())
+    }
+
+    /// Parses the days after "on", e.g. "on Monday and the last Friday", "on the 1st and the
+    /// 15th", "on Friday the 13th" or "on the 1st and 100th day of the year".
+    fn on_clause(&mut self, rrule: &mut RRule<Unvalidated>) -> Result<(), ParseError> {
+        let mut days = vec![];
+        loop {
+            let before_item = self.position;
+            if !days.is_empty() {
+                self.eat_separators();
+            }
+            self.eat(&["the"]);
+            if let Some(day) = self.peek().and_then(weekday) {
+                self.position += 1;
+                days.push(Day::Weekday(NWeekday::Every(day)));
+            } else if let Some(ordinal) = self.ordinal() {
+                if let Some(day) = self.peek().and_then(weekday) {
+                    self.position += 1;
+                    let number = i16::try_from(ordinal)
+                        .map_err(|_| ParseError::InvalidTextNumber(ordinal.to_string()))?;
+                    days.push(Day::Weekday(NWeekday::Nth(number, day)));
+                } else if self.peek() == Some("day") && self.peek_at(1) != Some("of") {
+                    self.position += 1;
+                    days.push(Day::OfMonth(ordinal));
+                } else {
+                    days.push(Day::Ordinal(ordinal));
+                }
+            } else {
+                self.position = before_item;
+                break;
+            }
+        }
+        if days.is_empty() {
+            return Err(self.unrecognized());
+        }
+
+        let year_days = self.peek() == Some("day")
+            && self.peek_at(1) == Some("of")
+            && self.peek_at(2) == Some("the")
+            && self.peek_at(3) == Some("year");
+        if year_days {
+            self.position += 4;
+        }
+        for day in days {
+            match day {
+                Day::Weekday(weekday) => rrule.by_weekday.push(weekday),
+                Day::Ordinal(year_day) if year_days => rrule.by_year_day.push(
+                    i16::try_from(year_day)
+                        .map_err(|_| ParseError::InvalidTextNumber(year_day.to_string()))?,
+                ),
+                Day::Ordinal(month_day) | Day::OfMonth(month_day) => rrule.by_month_day.push(
+                    i8::try_from(month_day)
+                        .map_err(|_| ParseError::InvalidTextNumber(month_day.to_string()))?,
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the times after "at", e.g. "at 9:00 and 17:00", "at 9am", "at noon" or
+    /// "at hour 9 at minute 30".
+    fn at_clause(&mut self, rrule: &mut RRule<Unvalidated>) -> Result<(), ParseError> {
+        let by_time = match self.peek() {
+            Some("hour" | "hours") => Some(&mut rrule.by_hour),
+            Some("minute" | "minutes") => Some(&mut rrule.by_minute),
+            Some("second" | "seconds") => Some(&mut rrule.by_second),
+            _ => None,
+        };
+        if let Some(by_time) = by_time {
+            self.position += 1;
+            let start = self.position;
+            while let Some(value) = self.list_item(|parser| parser.peek().and_then(number)) {
+                by_time.push(
+                    u8::try_from(value)
+                        .map_err(|_| ParseError::InvalidTextNumber(value.to_string()))?,
+                );
+            }
+            if self.position == start {
+                return Err(self.unrecognized());
+            }
+            return Ok(());
+        }
+
+        let start = self.position;
+        let mut times = vec![];
+        loop {
+            let before_item = self.position;
+            if !times.is_empty() {
+                self.eat_separators();
+            }
+            match self.time()? {
+                Some(time) => times.push(time),
+                None => {
+                    self.position = before_item;
+                    break;
+                }
+            }
+        }
+        if times.is_empty() {
+            return Err(self.unrecognized());
+        }
+
+        let mut hours = times.iter().map(|time| time.hour()).collect::<Vec<_>>();
+        let mut minutes = times.iter().map(|time| time.minute()).collect::<Vec<_>>();
+        let mut seconds = times.iter().map(|time| time.second()).collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+        for values in [&mut hours, &mut minutes, &mut seconds] {
+            values.sort_unstable();
+            values.dedup();
+        }
+        // A rule combines all hours, minutes and seconds, so "at 9:00 and 17:30" can't be
+        // described without also occurring at 9:30 and 17:00.
+        if hours.len() * minutes.len() * seconds.len() != times.len() {
+            return Err(ParseError::UnsupportedTextTimes(
+                self.tokens[start..self.position]
+                    .join(" ")
+                    .replace(" ,", ","),
+            ));
+        }
+        let to_u8 = |values: Vec<u32>| {
+            values
+                .into_iter()
+                .map(|value| u8::try_from(value).expect("time components are covered by u8"))
+                .collect::<Vec<_>>()
+        };
+        rrule.by_hour.extend(to_u8(hours));
+        rrule.by_minute.extend(to_u8(minutes));
+        rrule.by_second.extend(to_u8(seconds));
+        Ok(())
+    }
+
+    /// Parses a time of the day, e.g. "9:30", "9:30:15", "9am", "9 pm", "noon" or "midnight".
+    ///
+    /// Returns `None` if the next token doesn't start with a number.
+    fn time(&mut self) -> Result<Option<NaiveTime>, ParseError> {
+        let Some(token) = self.peek() else {
+            return Ok(None);
+        };
+        match token {
+            "noon" | "midday" => {
+                self.position += 1;
+                return Ok(NaiveTime::from_hms_opt(12, 0, 0));
+            }
+            "midnight" => {
+                self.position += 1;
+                return Ok(NaiveTime::from_hms_opt(0, 0, 0));
+            }
+            token if !token.starts_with(|c: char| c.is_ascii_digit()) => return Ok(None),
+            // "at 9:00 10 times" is followed by a count, not by another time.
+            _ if matches!(self.peek_at(1), Some("time" | "times")) => return Ok(None),
+            _ => {}
+        }
+
+        let token = token.to_string();
+        self.position += 1;
+        let (clock, mut meridiem) = match token.strip_suffix("am").or(token.strip_suffix("a.m.")) {
+            Some(clock) => (clock, Some(false)),
+            None => match token.strip_suffix("pm").or(token.strip_suffix("p.m.")) {
+                Some(clock) => (clock, Some(true)),
+                None => (token.as_str(), None),
+            },
+        };
+        if meridiem.is_none() {
+            if self.eat(&["am", "a.m."]) {
+                meridiem = Some(false);
+            } else if self.eat(&["pm", "p.m."]) {
+                meridiem = Some(true);
+            }
+        }
+
+        let invalid = || ParseError::InvalidTextTime(token.clone());
+        let components = clock
+            .split(':')
+            .map(|component| component.parse::<u32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (hour, minute, second) = match components[..] {
+            [hour] => (hour, 0, 0),
+            [hour, minute] => (hour, minute, 0),
+            [hour, minute, second] => (hour, minute, second),
+            _ => return Err(invalid()),
+        };
+        let hour = match meridiem {
+            Some(_) if !(1..=12).contains(&hour) => return Err(invalid()),
+            Some(false) => hour % 12,
+            Some(true) => hour % 12 + 12,
+            None => hour,
+        };
+        NaiveTime::from_hms_opt(hour, minute, second)
+            .map(Some)
+            .ok_or_else(invalid)
+    }
+
+    /// Parses the number of occurrences, e.g. "for 5 times", "10 times" or "once".
+    fn count(&mut self) -> Result<u32, ParseError> {
+        if self.eat(&["once"]) {
+            return Ok(1);
+        }
+        if self.eat(&["twice"]) {
+            return Ok(2);
+        }
+        let start = self.position;
+        self.eat(&["for"]);
+        let count = self.next().as_deref().and_then(number);
+        match count {
+            Some(count) if self.eat(&["time", "times"]) => {
+                u32::try_from(count).map_err(|_| ParseError::InvalidTextNumber(count.to_string()))
+            }
+            _ => {
+                self.position = start;
+                Err(self.unrecognized())
+            }
+        }
+    }
+
+    /// Parses the end of the recurrence, e.g. "March 3, 2025", "3rd of March 2025",
+    /// "2025-03-03", "June 2025" or "June".
+    ///
+    /// The whole day, or the whole month if no day is given, is included.
+    fn date(&mut self) -> Result<DateTime<Tz>, ParseError> {
+        let start = self.position;
+        let invalid = |parser: &Self| {
+            ParseError::InvalidTextDate(
+                parser.tokens[start..parser.position.max(start + 1).min(parser.tokens.len())]
+                    .join(" ")
+                    .replace(" ,", ","),
+            )
+        };
+
+        let Some(token) = self.next() else {
+            return Err(ParseError::InvalidTextDate(String::new()));
+        };
+        if let Ok(date) = NaiveDate::parse_from_str(&token, "%Y-%m-%d") {
+            return Ok(end_of_day(date));
+        }
+
+        let (month, day) = if let Some(month) = month(&token) {
+            // "March 3, 2025" or "June 2025"
+            let day = self.peek().and_then(day_of_month);
+            if day.is_some() {
+                self.position += 1;
+            }
+            (month, day)
+        } else if let Some(day) = day_of_month(&token) {
+            // "3 March 2025" or "3rd of March 2025"
+            self.eat(&["of"]);
+            let month = self.next().as_deref().and_then(month);
+            (month.ok_or_else(|| invalid(self))?, Some(day))
+        } else {
+            return Err(invalid(self));
+        };
+        if self.peek() == Some(",") && self.peek_at(1).and_then(year).is_some() {
+            self.position += 1;
+        }
+        let year = self.peek().and_then(year);
+        if year.is_some() {
+            self.position += 1;
+        }
+
+        let date = |year: i32| match day {
+            Some(day) => NaiveDate::from_ymd_opt(year, u32::from(month), day),
+            None => NaiveDate::from_ymd_opt(year, u32::from(month), 1)
+                .and_then(|first| first.checked_add_months(Months::new(1)))
+                .and_then(|next_month| next_month.pred_opt()),
+        };
+        let date = match year {
+            Some(year) => date(year),
+            // The first matching date on or after the reference date.
+            None => {
+                let today = self.reference.date_naive();
+                date(today.year())
+                    .filter(|date| *date >= today)
+                    .or_else(|| date(today.year() + 1))
+            }
+        };
+        date.map(end_of_day).ok_or_else(|| invalid(self))
+    }
+
+    /// Parses an ordinal, e.g. "1st", "first", "last" or "2nd to last".
+    ///
+    /// Returns `None` and consumes nothing if the next token isn't an ordinal.
+    fn ordinal(&mut self) -> Option<i32> {
+        let token = self.peek()?;
+        if token == "last" {
+            self.position += 1;
+            return Some(-1);
+        }
+        let ordinal = ordinal(token)?;
+        self.position += 1;
+        if self.peek() == Some("to") && self.peek_at(1) == Some("last") {
+            self.position += 2;
+            return Some(-ordinal);
+        }
+        Some(ordinal)
+    }
+}
+
+/// The last second of `date` in UTC, so occurrences on that day are included.
+fn end_of_day(date: NaiveDate) -> DateTime<Tz> {
+    Tz::UTC.from_utc_datetime(
+        &date
+            .and_hms_opt(23, 59, 59)
+            .expect("23:59:59 is a valid time"),
+    )
+}
+
+fn week_number(week: i32) -> Result<i8, ParseError> {
+    i8::try_from(week).map_err(|_| ParseError::InvalidTextNumber(week.to_string()))
+}
+
+fn frequency_unit(token: &str) -> Option<Frequency> {
+    let freq = match token {
+        "year" | "years" => Frequency::Yearly,
+        "month" | "months" => Frequency::Monthly,
+        "week" | "weeks" => Frequency::Weekly,
+        "day" | "days" => Frequency::Daily,
+        "hour" | "hours" => Frequency::Hourly,
+        "minute" | "minutes" => Frequency::Minutely,
+        "second" | "seconds" => Frequency::Secondly,
+        _ => return None,
+    };
+    Some(freq)
+}
+
+/// A cardinal number, either in digits or spelled out up to twelve.
+fn number(token: &str) -> Option<i32> {
+    const WORDS: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+        "twelve",
+    ];
+    if let Some(index) = WORDS.iter().position(|word| *word == token) {
+        return i32::try_from(index + 1).ok();
+    }
+    if token.starts_with(|c: char| c.is_ascii_digit()) {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// A positive ordinal, either like "1st" or spelled out up to "fifth".
+fn ordinal(token: &str) -> Option<i32> {
+    const WORDS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+    if let Some(index) = WORDS.iter().position(|word| *word == token) {
+        return i32::try_from(index + 1).ok();
+    }
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| token.strip_suffix(suffix))?;
+    if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// A day of the month in a date, e.g. "3" or "3rd".
+fn day_of_month(token: &str) -> Option<u32> {
+    let day = number(token).or_else(|| ordinal(token))?;
+    u32::try_from(day).ok().filter(|day| (1..=31).contains(day))
+}
+
+/// A four digit year.
+fn year(token: &str) -> Option<i32> {
+    if token.len() == 4 && token.chars().all(|c| c.is_ascii_digit()) {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn weekday(token: &str) -> Option<Weekday> {
+    let weekday = match token.strip_suffix('s').unwrap_or(token) {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn month(token: &str) -> Option<u8> {
+    let month = match token {
+        "january" | "jan" => 1,
+        "february" | "feb" => 2,
+        "march" | "mar" => 3,
+        "april" | "apr" => 4,
+        "may" => 5,
+        "june" | "jun" => 6,
+        "july" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "october" | "oct" => 10,
+        "november" | "nov" => 11,
+        "december" | "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
@@ -2,7 +2,7 @@
 
 use thiserror::Error;
 
-use crate::Frequency;
+use crate::{Frequency, RScale};
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ValidationError {
@@ -34,6 +34,8 @@ pub enum ValidationError {
     InvalidByRuleAndFrequency { by_rule: String, freq: Frequency },
     #[error("`UNTIL` is `{until}`, but `DTSTART` (`{dt_start}`) is later. That should not be happening.")]
     UntilBeforeStart { until: String, dt_start: String },
+    #[error("The end of the event is `{end}`, but `DTSTART` (`{dt_start}`) is later. That should not be happening.")]
+    EndBeforeStart { end: String, dt_start: String },
     #[error(
         "`INTERVAL` is `{0}`, is higher than expected, make sure this is correct. \
             See 'validator limits' in docs for more info."
@@ -51,6 +53,10 @@ pub enum ValidationError {
     #[cfg(feature = "by-easter")]
     #[error("`BYEASTER` can only be used when `BYHOUR`, `BYMINUTE` and `BYSECOND` are set.")]
     InvalidByRuleWithByEaster,
+    #[error("`{by_rule}` can not be used with `RSCALE={rscale}`.")]
+    UnsupportedByRuleWithRScale { by_rule: String, rscale: RScale },
+    #[error("`SKIP` can only be used when `RSCALE` is set.")]
+    SkipWithoutRScale,
     #[error(
         "The value of `DTSTART` was specified in {dt_start_tz} timezone, but `UNTIL` was specified in timezone {until_tz}. Allowed timezones for `UNTIL` with the given start date timezone are: `{expected:?}`"
     )]
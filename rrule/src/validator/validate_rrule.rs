@@ -1,6 +1,6 @@
 use std::ops::RangeInclusive;
 
-use crate::{Frequency, NWeekday, RRule, Tz, Unvalidated};
+use crate::{Frequency, NWeekday, RRule, RScale, Skip, Tz, Unvalidated};
 
 use super::ValidationError;
 
@@ -12,10 +12,35 @@ pub(crate) static MONTH_RANGE: RangeInclusive<u8> = 1..=12;
 /// Range: `-10_000..=10_000`
 pub(crate) static YEAR_RANGE: RangeInclusive<i32> = -10_000..=10_000;
 
+/// Range of values that a month day can be, except for `0`.
+/// Range: `-31..=31`
+pub(crate) static MONTH_DAY_RANGE: RangeInclusive<i8> = -31..=31;
+
+/// Range of values that a year day can be, except for `0`.
+/// Range: `-366..=366`
+pub(crate) static YEAR_DAY_RANGE: RangeInclusive<i16> = -366..=366;
+
+/// Range of values that a week number can be, except for `0`.
+/// Range: `-53..=53`
+pub(crate) static WEEK_NO_RANGE: RangeInclusive<i8> = -53..=53;
+
+/// Range of values that an hour can be.
+/// Range: `0..=23`
+pub(crate) static HOUR_RANGE: RangeInclusive<u8> = 0..=23;
+
+/// Range of values that a minute or a second can be.
+/// Range: `0..=59`
+pub(crate) static MINUTE_RANGE: RangeInclusive<u8> = 0..=59;
+
+/// Range of days that an easter offset can be.
+/// Range: `-366..=366`
+#[cfg(any(feature = "by-easter", feature = "json-schema", feature = "binary"))]
+pub(crate) static EASTER_RANGE: RangeInclusive<i16> = -366..=366;
+
 type Validator =
     &'static dyn Fn(&RRule<Unvalidated>, &chrono::DateTime<Tz>) -> Result<(), ValidationError>;
 
-const VALIDATION_PIPELINE: [Validator; 11] = [
+const VALIDATION_PIPELINE: [Validator; 13] = [
     &validate_until,
     &validate_by_set_pos,
     &validate_by_month,
@@ -27,6 +52,8 @@ const VALIDATION_PIPELINE: [Validator; 11] = [
     &validate_by_minute,
     &validate_by_second,
     &validate_by_easter,
+    &validate_skip,
+    &validate_rscale,
 ];
 
 /// Check if rules are valid as defined by the RFC and crate limitations.
@@ -67,7 +94,7 @@ fn validate_until(
                         });
                     }
                 }
-                Tz::Tz(_) => {
+                _ => {
                     if until.timezone() != Tz::UTC {
                         return Err(ValidationError::DtStartUntilMismatchTimezone {
                             dt_start_tz: dt_start.timezone().name().into(),
@@ -97,13 +124,7 @@ fn validate_by_set_pos(
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
     validate_not_equal_for_vec(&0, &rrule.by_set_pos, "BYSETPOS")?;
-    let range = match rrule.freq {
-        Frequency::Yearly | Frequency::Daily => -366..=366, // TODO is the daily range correct?
-        Frequency::Monthly => -31..=31,
-        Frequency::Weekly => -53..=53,
-        Frequency::Hourly => -24..=24,
-        Frequency::Minutely | Frequency::Secondly => -60..=60,
-    };
+    let range = by_set_pos_range(rrule.freq);
     if let Err(value) = validate_range_for_vec_error(&range, &rrule.by_set_pos) {
         return Err(ValidationError::InvalidFieldValueRangeWithFreq {
             field: "BYSETPOS".into(),
@@ -121,6 +142,7 @@ fn validate_by_set_pos(
         && rrule.by_second.is_empty()
         && rrule.by_month_day.is_empty()
         && rrule.by_month.is_empty()
+        && rrule.by_leap_month.is_empty()
         && rrule.by_year_day.is_empty()
         && rrule.by_week_no.is_empty()
         && rrule.by_weekday.is_empty()
@@ -130,13 +152,42 @@ fn validate_by_set_pos(
     Ok(())
 }
 
+/// Range of values that `BYSETPOS` can be with `freq`, except for `0`.
+pub(crate) fn by_set_pos_range(freq: Frequency) -> RangeInclusive<i32> {
+    match freq {
+        Frequency::Yearly | Frequency::Daily => -366..=366, // TODO is the daily range correct?
+        Frequency::Monthly => -31..=31,
+        Frequency::Weekly => -53..=53,
+        Frequency::Hourly => -24..=24,
+        Frequency::Minutely | Frequency::Secondly => -60..=60,
+    }
+}
+
 // By_month:
 // - Can be a value from 1 to 12.
 fn validate_by_month(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    validate_range_for_vec(&MONTH_RANGE, &rrule.by_month, "BYMONTH")
+    validate_range_for_vec(&MONTH_RANGE, &rrule.by_month, "BYMONTH")?;
+    validate_range_for_vec(&MONTH_RANGE, &rrule.by_leap_month, "BYMONTH")?;
+    // - Leap months can only be used with a RSCALE which has them (RFC 7529).
+    //   Validated below
+    let rscale = rrule.rscale.unwrap_or_default();
+    for leap_month in &rrule.by_leap_month {
+        let valid = match rscale {
+            RScale::Gregorian => false,
+            RScale::Hebrew => *leap_month == 5,
+            RScale::Chinese => true,
+        };
+        if !valid {
+            return Err(ValidationError::UnsupportedByRuleWithRScale {
+                by_rule: format!("BYMONTH={leap_month}L"),
+                rscale,
+            });
+        }
+    }
+    Ok(())
 }
 
 // By_month_day:
@@ -146,7 +197,9 @@ fn validate_by_month_day(
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
     validate_not_equal_for_vec(&0, &rrule.by_month_day, "BYMONTHDAY")?;
-    validate_range_for_vec(&(-31..=31), &rrule.by_month_day, "BYMONTHDAY")?;
+    validate_range_for_vec(&MONTH_DAY_RANGE, &rrule.by_month_day, "BYMONTHDAY")?;
+    // The negative days are moved to `by_n_month_day` before validation
+    validate_range_for_vec(&MONTH_DAY_RANGE, &rrule.by_n_month_day, "BYMONTHDAY")?;
     // - MUST NOT be specified when the FREQ rule part is set to WEEKLY.
     //   Validated below
     if !rrule.by_month_day.is_empty() {
@@ -168,7 +221,7 @@ fn validate_by_year_day(
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
     validate_not_equal_for_vec(&0, &rrule.by_year_day, "BYYEARDAY")?;
-    validate_range_for_vec(&(-366..=366), &rrule.by_year_day, "BYYEARDAY")?;
+    validate_range_for_vec(&YEAR_DAY_RANGE, &rrule.by_year_day, "BYYEARDAY")?;
     // - MUST NOT be specified when the FREQ rule part is set to DAILY, WEEKLY, or MONTHLY.
     //   Validated below
     if !rrule.by_year_day.is_empty() {
@@ -192,7 +245,7 @@ fn validate_by_week_number(
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
     validate_not_equal_for_vec(&0, &rrule.by_week_no, "BYWEEKNO")?;
-    validate_range_for_vec(&(-53..=53), &rrule.by_week_no, "BYWEEKNO")?;
+    validate_range_for_vec(&WEEK_NO_RANGE, &rrule.by_week_no, "BYWEEKNO")?;
     // - MUST NOT be used when the FREQ rule part is set to anything other than YEARLY.
     //   Validated below
     if !rrule.by_week_no.is_empty() {
@@ -208,21 +261,21 @@ fn validate_by_week_number(
 }
 
 // By_weekday:
-// - Check if value for `Nth` is within range.
+// - Check if value for `Nth` is not 0 and within range.
 //   The Range depends on frequency and can only happen weekly, so `/7` from normal count.
 fn validate_by_weekday(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    let range = match rrule.freq {
-        Frequency::Yearly | Frequency::Daily => (-366 / 7)..=(366 / 7 + 1), // TODO is the daily range correct?
-        Frequency::Monthly => (-31 / 7)..=(31 / 7 + 1),
-        Frequency::Weekly => (-53 / 7)..=(53 / 7 + 1),
-        Frequency::Hourly => (-24 / 7)..=(24 / 7 + 1),
-        Frequency::Minutely | Frequency::Secondly => (-60 / 7)..=(60 / 7 + 1),
-    };
+    let range = by_weekday_range(rrule.freq);
     for item in &rrule.by_weekday {
         if let NWeekday::Nth(number, _weekday) = item {
+            if *number == 0 {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: "BYDAY".into(),
+                    value: number.to_string(),
+                });
+            }
             // If value not in range = error
             if !range.contains(number) {
                 return Err(ValidationError::InvalidFieldValueRangeWithFreq {
@@ -238,13 +291,24 @@ fn validate_by_weekday(
     Ok(())
 }
 
+/// Range of values that the `n` of an `NWeekday::Nth` can be with `freq`.
+pub(crate) fn by_weekday_range(freq: Frequency) -> RangeInclusive<i16> {
+    match freq {
+        Frequency::Yearly | Frequency::Daily => (-366 / 7)..=(366 / 7 + 1), // TODO is the daily range correct?
+        Frequency::Monthly => (-31 / 7)..=(31 / 7 + 1),
+        Frequency::Weekly => (-53 / 7)..=(53 / 7 + 1),
+        Frequency::Hourly => (-24 / 7)..=(24 / 7 + 1),
+        Frequency::Minutely | Frequency::Secondly => (-60 / 7)..=(60 / 7 + 1),
+    }
+}
+
 // By_hour:
 // - Can be a value from 0 to 23.
 fn validate_by_hour(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    validate_range_for_vec(&(0..=23), &rrule.by_hour, "BYHOUR")
+    validate_range_for_vec(&HOUR_RANGE, &rrule.by_hour, "BYHOUR")
 }
 
 // By_minute:
@@ -253,7 +317,7 @@ fn validate_by_minute(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    validate_range_for_vec(&(0..=59), &rrule.by_minute, "BYMINUTE")
+    validate_range_for_vec(&MINUTE_RANGE, &rrule.by_minute, "BYMINUTE")
 }
 
 // By_second:
@@ -262,7 +326,7 @@ fn validate_by_second(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    validate_range_for_vec(&(0..=59), &rrule.by_second, "BYSECOND")
+    validate_range_for_vec(&MINUTE_RANGE, &rrule.by_second, "BYSECOND")
 }
 
 fn validate_by_easter(
@@ -275,7 +339,7 @@ fn validate_by_easter(
         // - Can be a value from -366 to 366.
         //   Validated below
         if let Some(by_easter) = &rrule.by_easter {
-            validate_range_for_vec(&(-366..=366), &[*by_easter], "BYEASTER")?;
+            validate_range_for_vec(&EASTER_RANGE, &[*by_easter], "BYEASTER")?;
         }
         // - Can only be used on frequency: Yearly, Monthly, Daily
         //   Validated below
@@ -313,6 +377,57 @@ fn validate_by_easter(
     Ok(())
 }
 
+// Skip:
+// - MUST NOT be present unless RSCALE is present (RFC 7529).
+fn validate_skip(
+    rrule: &RRule<Unvalidated>,
+    _dt_start: &chrono::DateTime<Tz>,
+) -> Result<(), ValidationError> {
+    if rrule.skip != Skip::Omit && rrule.rscale.is_none() {
+        return Err(ValidationError::SkipWithoutRScale);
+    }
+    Ok(())
+}
+
+// Rscale:
+// - Non-Gregorian calendars are iterated by filtering days on their calendar month and
+//   month day, so rule parts which count days or periods within a calendar year or
+//   month aren't supported.
+fn validate_rscale(
+    rrule: &RRule<Unvalidated>,
+    _dt_start: &chrono::DateTime<Tz>,
+) -> Result<(), ValidationError> {
+    let rscale = match rrule.rscale {
+        Some(RScale::Gregorian) | None => return Ok(()),
+        Some(rscale) => rscale,
+    };
+    let yearly_or_monthly = matches!(rrule.freq, Frequency::Yearly | Frequency::Monthly);
+    let unsupported = [
+        ("INTERVAL", yearly_or_monthly && rrule.interval > 1),
+        (
+            "BYSETPOS",
+            yearly_or_monthly && !rrule.by_set_pos.is_empty(),
+        ),
+        ("BYYEARDAY", !rrule.by_year_day.is_empty()),
+        ("BYWEEKNO", !rrule.by_week_no.is_empty()),
+        (
+            "BYDAY",
+            rrule
+                .by_weekday
+                .iter()
+                .any(|by_weekday| matches!(by_weekday, NWeekday::Nth(_, _))),
+        ),
+        ("BYEASTER", rrule.by_easter.is_some()),
+    ];
+    match unsupported.into_iter().find(|(_, used)| *used) {
+        Some((by_rule, _)) => Err(ValidationError::UnsupportedByRuleWithRScale {
+            by_rule: by_rule.into(),
+            rscale,
+        }),
+        None => Ok(()),
+    }
+}
+
 fn validate_range_for_vec_error<'a, T: PartialOrd>(
     range: &RangeInclusive<T>,
     list: &'a [T],
@@ -361,7 +476,7 @@ fn validate_not_equal_for_vec<T: PartialEq<T> + ToString>(
 
 #[cfg(test)]
 mod tests {
-    use chrono::TimeZone;
+    use chrono::{TimeZone, Weekday};
 
     use crate::core::Tz;
 
@@ -389,6 +504,73 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn rejects_skip_without_rscale() {
+        let rrule = RRule {
+            freq: Frequency::Monthly,
+            skip: Skip::Backward,
+            ..Default::default()
+        };
+        let dt_start = UTC.with_ymd_and_hms(1970, 1, 31, 0, 0, 0).unwrap();
+        let res = validate_rrule_forced(&rrule, &dt_start);
+        assert_eq!(res, Err(ValidationError::SkipWithoutRScale));
+
+        let rrule = rrule.rscale(RScale::Gregorian);
+        assert!(validate_rrule_forced(&rrule, &dt_start).is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_by_rules_with_rscale() {
+        let dt_start = UTC.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let tests = [
+            (
+                RRule::new(Frequency::Yearly).by_leap_month(vec![5]),
+                "BYMONTH=5L",
+                RScale::Gregorian,
+            ),
+            (
+                RRule::new(Frequency::Yearly)
+                    .rscale(RScale::Hebrew)
+                    .by_leap_month(vec![6]),
+                "BYMONTH=6L",
+                RScale::Hebrew,
+            ),
+            (
+                RRule::new(Frequency::Monthly)
+                    .rscale(RScale::Chinese)
+                    .interval(2),
+                "INTERVAL",
+                RScale::Chinese,
+            ),
+            (
+                RRule::new(Frequency::Yearly)
+                    .rscale(RScale::Hebrew)
+                    .by_year_day(vec![1]),
+                "BYYEARDAY",
+                RScale::Hebrew,
+            ),
+        ];
+        for (rrule, by_rule, rscale) in tests {
+            let res = validate_rrule_forced(&rrule, &dt_start);
+            assert_eq!(
+                res,
+                Err(ValidationError::UnsupportedByRuleWithRScale {
+                    by_rule: by_rule.into(),
+                    rscale,
+                })
+            );
+        }
+
+        let rrule = RRule::new(Frequency::Yearly)
+            .rscale(RScale::Chinese)
+            .by_leap_month(vec![6]);
+        assert!(validate_rrule_forced(&rrule, &dt_start).is_ok());
+        let rrule = RRule::new(Frequency::Daily)
+            .rscale(RScale::Hebrew)
+            .interval(2);
+        assert!(validate_rrule_forced(&rrule, &dt_start).is_ok());
+    }
+
     #[test]
     fn rejects_by_rule_field_with_invalid_value() {
         let tests = [
@@ -420,6 +602,13 @@ mod tests {
                     ..Default::default()
                 },
             ),
+            (
+                "BYDAY",
+                RRule {
+                    by_weekday: vec![NWeekday::Nth(0, Weekday::Mon)],
+                    ..Default::default()
+                },
+            ),
         ];
         for (field, rrule) in tests {
             let res =
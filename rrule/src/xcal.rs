@@ -0,0 +1,225 @@
+//! The xCal representation of recurrences, as defined in [RFC 6321](https://datatracker.ietf.org/doc/html/rfc6321).
+//!
+//! Like jCal, xCal maps the iCalendar text format to XML one to one, so the conversions go
+//! through the properties of the text form, see [`crate::property`].
+use roxmltree::{Document, Node};
+
+use crate::{
+    parser::ParseError,
+    property::{
+        is_recurrence_property, rrule_set_from_properties, rrule_set_to_properties, Property,
+        PropertyValues,
+    },
+    RRuleError, RRuleSet,
+};
+
+/// The namespace of xCal elements.
+const NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
+/// The components whose properties can describe recurrences.
+const COMPONENTS: [&str; 2] = ["vevent", "vtodo"];
+
+impl RRuleSet {
+    /// Converts the set to an xCal `properties` element, like
+    /// `<properties xmlns="urn:ietf:params:xml:ns:icalendar-2.0"><dtstart><date-time>2025-01-01T09:00:00Z</date-time></dtstart>...</properties>`.
+    ///
+    /// Rules become `recur` values, and the dates of `RDATE` and `EXDATE` become `date`,
+    /// `date-time` or `period` values.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20250101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+    ///     .parse()
+    ///     .unwrap();
+    /// let xcal = rrule_set.to_xcal();
+    /// assert!(xcal.contains("<rrule><recur><freq>DAILY</freq><count>3</count>"));
+    /// assert_eq!(RRuleSet::from_xcal(&xcal).unwrap(), rrule_set);
+    /// ```
+    #[must_use]
+    pub fn to_xcal(&self) -> String {
+        let mut xml = format!("<properties xmlns=\"{NAMESPACE}\">");
+        for property in rrule_set_to_properties(self) {
+            property_to_xcal(&mut xml, &property);
+        }
+        xml.push_str("</properties>");
+        xml
+    }
+
+    /// Creates a set from an xCal document, see [`RRuleSet::to_xcal`]. The document can be a
+    /// single `properties` element, or a full `icalendar` document of which the first `vevent`
+    /// or `vtodo` with a `DTSTART` is used. Properties which don't describe recurrences, like
+    /// the `summary` of an event, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the document isn't valid xCal or if its properties don't
+    /// describe a valid [`RRuleSet`].
+    pub fn from_xcal(xml: &str) -> Result<Self, RRuleError> {
+        let document =
+            Document::parse(xml).map_err(|error| ParseError::InvalidXCal(error.to_string()))?;
+        let root = document.root_element();
+        let properties = document
+            .descendants()
+            .find(|node| {
+                node.has_tag_name((NAMESPACE, "properties"))
+                    && (*node == root || node.parent_element().is_some_and(is_component))
+                    && elements(*node).any(|property| property.has_tag_name("dtstart"))
+            })
+            .ok_or_else(|| {
+                ParseError::InvalidXCal(
+                    "no `properties` element of a `vevent` or `vtodo` with a `dtstart`".into(),
+                )
+            })?;
+        let properties = elements(properties)
+            .filter(|property| is_recurrence_property(property.tag_name().name()))
+            .map(property_from_xcal)
+            .collect::<Result<Vec<_>, _>>()?;
+        rrule_set_from_properties(&properties)
+    }
+}
+
+/// Returns whether the node is a component which can describe recurrences.
+fn is_component(node: Node) -> bool {
+    COMPONENTS
+        .iter()
+        .any(|component| node.has_tag_name((NAMESPACE, *component)))
+}
+
+/// Returns the child elements of a node.
+fn elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(Node::is_element)
+}
+
+fn invalid(property: Node) -> ParseError {
+    ParseError::InvalidXCal(format!(
+        "the `{}` property isn't valid",
+        property.tag_name().name()
+    ))
+}
+
+/// Escapes the characters which can't be used in the text of an element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn push_element(xml: &mut String, name: &str, text: &str) {
+    xml.push_str(&format!("<{name}>{}</{name}>", escape(text)));
+}
+
+fn property_to_xcal(xml: &mut String, property: &Property) {
+    xml.push_str(&format!("<{}>", property.name));
+    if !property.parameters.is_empty() {
+        xml.push_str("<parameters>");
+        for (parameter, value) in &property.parameters {
+            xml.push_str(&format!("<{parameter}>"));
+            push_element(xml, "text", value);
+            xml.push_str(&format!("</{parameter}>"));
+        }
+        xml.push_str("</parameters>");
+    }
+    match &property.values {
+        PropertyValues::Recur(parts) => {
+            xml.push_str("<recur>");
+            for (key, values) in parts {
+                for value in values {
+                    push_element(xml, key, value);
+                }
+            }
+            xml.push_str("</recur>");
+        }
+        PropertyValues::Text(values) if property.value_type == "period" => {
+            for value in values {
+                let (start, end) = value.split_once('/').unwrap_or((value, ""));
+                xml.push_str("<period>");
+                push_element(xml, "start", start);
+                if end.starts_with(['P', '+', '-']) {
+                    push_element(xml, "duration", end);
+                } else {
+                    push_element(xml, "end", end);
+                }
+                xml.push_str("</period>");
+            }
+        }
+        PropertyValues::Text(values) => {
+            for value in values {
+                push_element(xml, &property.value_type, value);
+            }
+        }
+    }
+    xml.push_str(&format!("</{}>", property.name));
+}
+
+fn property_from_xcal(property: Node) -> Result<Property, ParseError> {
+    let mut parameters = vec![];
+    let mut value_type = None;
+    let mut recurs = vec![];
+    let mut values = vec![];
+    for child in elements(property) {
+        let name = child.tag_name().name();
+        if name == "parameters" {
+            for parameter in elements(child) {
+                let value = elements(parameter)
+                    .next()
+                    .and_then(|value| value.text())
+                    .unwrap_or_default();
+                parameters.push((parameter.tag_name().name().to_lowercase(), value.into()));
+            }
+            continue;
+        }
+
+        if value_type.is_some_and(|value_type| value_type != name) {
+            return Err(invalid(property));
+        }
+        value_type = Some(name);
+        match name {
+            "recur" => recurs.push(recur_from_xcal(child)),
+            "period" => values.push(period_from_xcal(child).ok_or_else(|| invalid(property))?),
+            _ => values.push(child.text().unwrap_or_default().trim().to_string()),
+        }
+    }
+
+    let values = match (value_type, recurs.len()) {
+        (Some("recur"), 1) => PropertyValues::Recur(recurs.remove(0)),
+        (Some(value_type), 0) if value_type != "recur" => PropertyValues::Text(values),
+        _ => return Err(invalid(property)),
+    };
+    Ok(Property {
+        name: property.tag_name().name().to_lowercase(),
+        parameters,
+        value_type: value_type.unwrap_or_default().into(),
+        values,
+    })
+}
+
+/// Converts a `recur` element to the parts of a `RECUR` value. Repeated elements, like the
+/// `byday` of every weekday, become the values of one part.
+fn recur_from_xcal(recur: Node) -> Vec<(String, Vec<String>)> {
+    let mut parts: Vec<(String, Vec<String>)> = vec![];
+    for part in elements(recur) {
+        let key = part.tag_name().name().to_lowercase();
+        let value = part.text().unwrap_or_default().trim().to_string();
+        match parts.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, values)) => values.push(value),
+            None => parts.push((key, vec![value])),
+        }
+    }
+    parts
+}
+
+/// Converts a `period` element to a `period` value, like `2025-01-01T09:00:00Z/PT1H`.
+fn period_from_xcal(period: Node) -> Option<String> {
+    let text = |name: &str| {
+        elements(period)
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .map(str::trim)
+    };
+    let end = text("end").or_else(|| text("duration"))?;
+    Some(format!("{}/{end}", text("start")?))
+}